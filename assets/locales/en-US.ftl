@@ -0,0 +1,145 @@
+# game texts in english, in fluent format: "key = text",
+# with { $name } where the game fills in values
+
+## main menu
+menu-title = Naval Battle
+menu-vs-bot = New game against the bot
+menu-hotseat = Two players on this computer
+menu-host-lan = Host on local network
+menu-join-lan = Join local network
+menu-load-game = Load game
+menu-replays = Replay the saved game
+menu-profiles = Profiles
+menu-settings = Settings
+menu-quit = Quit
+menu-back = Back
+
+## local network
+join-lan-hint = type the host address and press Enter
+join-lan-connect = Connect
+
+## profiles
+profiles-next = Next profile
+profiles-hint = type a name and press Enter to create or pick a profile
+profile-header = profile: { $name }
+profile-games = games: { $played } | wins: { $wins } | losses: { $losses } | resignations: { $resignations }
+profile-mode = { $mode }: { $played } games, { $wins } wins, { $losses } losses
+profile-accuracy = accuracy: { $accuracy }% | longest hit streak: { $streak }
+profile-wins = average shots to win: { $average } | fastest win: { $fastest }
+profile-sunk = ships sunk: { $ships }
+profile-achievements = achievements: { $unlocked }/{ $total }
+mode-bot = bot
+mode-external = external engine
+mode-network = network
+
+## settings
+setting-board-size = board
+setting-fleet = fleet
+setting-variant = rules
+setting-map = map
+setting-difficulty = bot difficulty
+setting-volume = master volume
+setting-music-volume = music
+setting-sfx-volume = sound effects
+setting-mute = sound
+setting-theme = theme
+setting-language = language
+sound-muted = muted
+sound-on = on
+fleet-classic = classic
+fleet-compact = compact
+fleet-armada = armada
+fleet-modern = modern
+variant-classic = classic
+variant-hit-again = hit, shoot again
+variant-advanced = advanced (abilities)
+variant-moving = moving ships
+map-open = open sea
+map-archipelago = archipelago
+map-minefield = minefield
+difficulty-easy = easy
+difficulty-normal = normal
+difficulty-hard = hard
+theme-classic = classic
+theme-dark = dark
+theme-high-contrast = high contrast
+theme-deuteranopia = deuteranopia
+
+## ships
+ship-submarine = submarine
+ship-destroyer = destroyer
+ship-battleship = battleship
+ship-large-battleship = large battleship
+ship-aircraft-carrier = aircraft carrier
+ship-corvette = corvette
+ship-frigate = frigate
+
+## abilities
+ability-airstrike = airstrike
+ability-sonar = sonar
+ability-torpedo = torpedo
+ability-sonar-found = sonar: ship in the area
+ability-sonar-empty = sonar: area clear
+
+## pause
+pause-title = Paused
+pause-resume = Resume
+pause-restart = Restart
+pause-resign = Resign
+pause-save = Save
+pause-quit-to-menu = Quit to menu
+resign-title = Resign the match?
+resign-hint = the match counts as a loss
+resign-confirm = Yes, resign
+resign-cancel = No
+
+## game over
+victory-winner = winner: { $winner }
+victory-player = player
+victory-bot = Bot
+victory-resigned = the player resigned
+victory-missed = { $count } enemy ship(s) were never found
+victory-commitment-mismatch = revealed fleet does not match the commitment
+victory-lies = opponent lied in { $count } answer(s)
+victory-fleet-invalid = revealed fleet breaks the match rules
+victory-lie-cells = false answers at: { $cells }
+victory-lie-sonar = sonar at ({ $row }, { $column })
+victory-verified = opponent fleet verified
+
+## achievements
+achievement-unlocked = achievement unlocked: { $title }
+achievement-intacto = win without losing a ship
+achievement-porta-avioes-certeiro = sink the aircraft carrier without missing after the first hit on it
+achievement-relampago = win with fewer than 30 shots
+achievement-mestre-do-bot = beat the bot on hard
+
+## spectator
+spectator-full-view = full view
+spectator-seat-view = { $name }'s view
+spectator-winner = winner: { $name }
+spectator-turn = { $name }'s turn
+spectator-connecting = connecting...
+spectator-waiting = waiting for the fleets
+spectator-status = { $view } (V switches) | { $status }
+spectator-boards = bottom: { $bottom } ({ $bottom-sunk } sunk) | top: { $top } ({ $top-sunk } sunk)
+
+## two players
+hotseat-player-1 = player 1
+hotseat-player-2 = player 2
+hotseat-pass = hand the computer to { $name }
+hotseat-hint = the other player should not look at the screen
+hotseat-ready = Ready
+
+## replay
+replay-status = replay: shot { $shown } of { $total } | { $status }
+replay-hint = ← and → step one shot, Home and End go to the start and the end
+
+## notices
+notice-island = islands can't be shot
+notice-ship-blocked = the ship can't move there
+notice-waiting-start = waiting for the match to start
+notice-already-marked = this cell was already shot
+notice-turn-timeout = time is up, the turn went to the opponent
+notice-opponent-disconnected = opponent disconnected
+notice-opponent-reconnected = opponent reconnected
+notice-server-error = server: { $error }
@@ -0,0 +1,145 @@
+# textos do jogo em português, no formato do fluent: "chave = texto",
+# com { $nome } no lugar dos valores preenchidos pelo jogo
+
+## menu principal
+menu-title = Batalha Naval
+menu-vs-bot = Novo jogo contra o bot
+menu-hotseat = Dois jogadores neste computador
+menu-host-lan = Hospedar na rede local
+menu-join-lan = Entrar na rede local
+menu-load-game = Carregar jogo
+menu-replays = Replay do jogo salvo
+menu-profiles = Perfis
+menu-settings = Configurações
+menu-quit = Sair
+menu-back = Voltar
+
+## rede local
+join-lan-hint = digite o endereço do anfitrião e aperte Enter
+join-lan-connect = Conectar
+
+## perfis
+profiles-next = Próximo perfil
+profiles-hint = digite um nome e aperte Enter para criar ou escolher um perfil
+profile-header = perfil: { $name }
+profile-games = partidas: { $played } | vitórias: { $wins } | derrotas: { $losses } | desistências: { $resignations }
+profile-mode = { $mode }: { $played } partidas, { $wins } vitórias, { $losses } derrotas
+profile-accuracy = precisão: { $accuracy }% | maior sequência de acertos: { $streak }
+profile-wins = média de tiros para vencer: { $average } | vitória mais rápida: { $fastest }
+profile-sunk = navios afundados: { $ships }
+profile-achievements = conquistas: { $unlocked }/{ $total }
+mode-bot = bot
+mode-external = motor externo
+mode-network = rede
+
+## configurações
+setting-board-size = tabuleiro
+setting-fleet = frota
+setting-variant = regras
+setting-map = mapa
+setting-difficulty = dificuldade do bot
+setting-volume = volume geral
+setting-music-volume = música
+setting-sfx-volume = efeitos sonoros
+setting-mute = som
+setting-theme = tema
+setting-language = idioma
+sound-muted = mudo
+sound-on = ligado
+fleet-classic = clássica
+fleet-compact = compacta
+fleet-armada = armada
+fleet-modern = moderna
+variant-classic = clássica
+variant-hit-again = acertou, joga de novo
+variant-advanced = avançada (habilidades)
+variant-moving = navios em movimento
+map-open = mar aberto
+map-archipelago = arquipélago
+map-minefield = campo minado
+difficulty-easy = fácil
+difficulty-normal = normal
+difficulty-hard = difícil
+theme-classic = clássico
+theme-dark = escuro
+theme-high-contrast = alto contraste
+theme-deuteranopia = deuteranopia
+
+## navios
+ship-submarine = submarino
+ship-destroyer = contratorpedeiro
+ship-battleship = encouraçado
+ship-large-battleship = encouraçado grande
+ship-aircraft-carrier = porta-aviões
+ship-corvette = corveta
+ship-frigate = fragata
+
+## habilidades
+ability-airstrike = ataque aéreo
+ability-sonar = sonar
+ability-torpedo = torpedo
+ability-sonar-found = sonar: navio na área
+ability-sonar-empty = sonar: área vazia
+
+## pausa
+pause-title = Pausa
+pause-resume = Continuar
+pause-restart = Reiniciar
+pause-resign = Desistir
+pause-save = Salvar
+pause-quit-to-menu = Sair para o menu
+resign-title = Desistir da partida?
+resign-hint = a partida conta como derrota
+resign-confirm = Sim, desistir
+resign-cancel = Não
+
+## fim de jogo
+victory-winner = vencedor: { $winner }
+victory-player = jogador
+victory-bot = Bot
+victory-resigned = o jogador desistiu
+victory-missed = { $count } navio(s) inimigo(s) não foram achados
+victory-commitment-mismatch = frota revelada não confere com o compromisso
+victory-lies = adversário mentiu em { $count } resposta(s)
+victory-fleet-invalid = frota revelada não obedece às regras da partida
+victory-lie-cells = respostas falsas em: { $cells }
+victory-lie-sonar = sonar em ({ $row }, { $column })
+victory-verified = frota do adversário verificada
+
+## conquistas
+achievement-unlocked = conquista desbloqueada: { $title }
+achievement-intacto = vencer sem perder nenhum navio
+achievement-porta-avioes-certeiro = afundar o porta-aviões sem errar depois do primeiro acerto nele
+achievement-relampago = vencer com menos de 30 tiros
+achievement-mestre-do-bot = vencer o bot no difícil
+
+## espectador
+spectator-full-view = visão completa
+spectator-seat-view = visão de { $name }
+spectator-winner = vencedor: { $name }
+spectator-turn = vez de { $name }
+spectator-connecting = conectando...
+spectator-waiting = aguardando as frotas
+spectator-status = { $view } (V troca) | { $status }
+spectator-boards = embaixo: { $bottom } ({ $bottom-sunk } afundados) | em cima: { $top } ({ $top-sunk } afundados)
+
+## dois jogadores
+hotseat-player-1 = jogador 1
+hotseat-player-2 = jogador 2
+hotseat-pass = passe o computador para o { $name }
+hotseat-hint = o outro jogador não deve olhar a tela
+hotseat-ready = Pronto
+
+## replay
+replay-status = replay: tiro { $shown } de { $total } | { $status }
+replay-hint = ← e → andam um tiro, Home e End vão ao começo e ao fim
+
+## avisos
+notice-island = ilha não recebe tiro
+notice-ship-blocked = o navio não pode andar para lá
+notice-waiting-start = aguardando o início da partida
+notice-already-marked = célula já atacada
+notice-turn-timeout = tempo esgotado, vez passada ao adversário
+notice-opponent-disconnected = adversário desconectou
+notice-opponent-reconnected = adversário reconectou
+notice-server-error = servidor: { $error }
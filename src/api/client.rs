@@ -0,0 +1,66 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, TryRecvError},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use super::protocol::{ClientMessage, ServerMessage};
+
+// conexão com o servidor dedicado; as mensagens chegam por um canal lido a cada frame
+pub struct ServerConnection {
+    writer: TcpStream,
+    incoming: Mutex<Receiver<ServerMessage>>,
+}
+
+impl ServerConnection {
+    pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let writer = TcpStream::connect(addr)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+
+                match ServerMessage::parse(&line) {
+                    Ok(message) => {
+                        if sender.send(message).is_err() {
+                            break;
+                        }
+                    }
+                    Err(error) => log::warn!("mensagem do servidor ignorada: {error}"),
+                }
+            }
+        });
+
+        Ok(ServerConnection {
+            writer,
+            incoming: Mutex::new(receiver),
+        })
+    }
+
+    pub fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        writeln!(self.writer, "{}", message.to_line())
+    }
+
+    // Ok(None) quando não há mensagem nova, Err quando a conexão caiu
+    pub fn try_recv(&self) -> Result<Option<ServerMessage>, TryRecvError> {
+        match self.incoming.lock().unwrap().try_recv() {
+            Ok(message) => Ok(Some(message)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    // espera bloqueante, para bots e clientes sem interface
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<ServerMessage, RecvTimeoutError> {
+        self.incoming.lock().unwrap().recv_timeout(timeout)
+    }
+}
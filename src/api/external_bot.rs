@@ -0,0 +1,279 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, TryRecvError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::logic::{
+    bots::BotPlayer,
+    engine::{Rules, ShipPlacement, ShipRule},
+    shot::ShotOutcome,
+};
+
+use super::protocol::outcome_to_str;
+
+/*
+ * oponente externo, no estilo dos motores UCI do xadrez: um executável
+ * qualquer que conversa por stdin/stdout, uma linha por mensagem
+ *
+ * jogo -> motor
+ *   newgame <linhas> <colunas> <navio> <navio> ...       frota na ordem em que deve ser posicionada: o
+ *                                                         tamanho do navio reto ou as células do formato
+ *                                                         (0,0;1,0;1,1)
+ *   map <células>                                         logo depois do newgame, uma por linha do lado
+ *                                                         quando há ilhas (o) ou minas (x); "." é água
+ *   place                                                 pede o posicionamento da frota
+ *   fire                                                  pede um tiro
+ *   result <linha> <coluna> MISS|HIT|SUNK                 resultado do último tiro do motor
+ *   sunk <tamanho>                                        o tiro afundou um navio desse tamanho
+ *   moved                                                 o adversário andou com um navio no lugar do
+ *                                                         tiro (regras "moving"): tiros na água já dados
+ *                                                         podem ser repetidos
+ *   gameover WIN|LOSS
+ *
+ * motor -> jogo
+ *   resposta a place: <linha>,<coluna>,H|V ... um navio por navio do newgame; navio com formato
+ *                     usa R<giros>[M] no lugar de H|V (quartos de volta no sentido horário,
+ *                     M espelha antes de girar) e a linha e a coluna são do canto do formato
+ *   resposta a fire:  <linha> <coluna>
+ *   linhas começando com # são ignoradas (log do motor)
+ */
+
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(2);
+
+pub struct ExternalBot {
+    command: String,
+    child: Child,
+    stdin: ChildStdin,
+    lines: Mutex<Receiver<String>>,
+    pub time_limit: Duration,
+    fleet: Vec<ShipRule>,
+    fire_deadline: Option<Instant>, // tiro pedido por poll_fire e ainda sem resposta
+}
+
+impl ExternalBot {
+    // `command` é o executável seguido dos argumentos, separados por espaço
+    pub fn spawn(command: &str, time_limit: Duration) -> io::Result<Self> {
+        let mut parts = command.split_whitespace();
+        let program = parts
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "comando vazio"))?;
+
+        let mut child = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take().expect("stdin configurado como pipe");
+        let stdout = child.stdout.take().expect("stdout configurado como pipe");
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(ExternalBot {
+            command: command.to_string(),
+            child,
+            stdin,
+            lines: Mutex::new(receiver),
+            time_limit,
+            fleet: Vec::new(),
+            fire_deadline: None,
+        })
+    }
+
+    fn send(&mut self, line: &str) -> Result<(), String> {
+        writeln!(self.stdin, "{line}").map_err(|error| format!("motor não recebeu '{line}': {error}"))
+    }
+
+    // descarta respostas atrasadas de uma jogada que já estourou o tempo
+    fn drain(&self) {
+        while self.lines.lock().unwrap().try_recv().is_ok() {}
+    }
+
+    // próxima linha útil da saída do motor, respeitando o limite de tempo da jogada
+    fn reply(&self) -> Result<String, String> {
+        let lines = self.lines.lock().unwrap();
+
+        loop {
+            match lines.recv_timeout(self.time_limit) {
+                Ok(line) if line.trim().is_empty() || line.starts_with('#') => continue,
+                Ok(line) => return Ok(line),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!("tempo esgotado ({:?})", self.time_limit))
+                }
+                Err(RecvTimeoutError::Disconnected) => return Err("motor encerrou".to_string()),
+            }
+        }
+    }
+}
+
+// "<linha> <coluna>", a resposta do motor ao fire
+fn parse_shot(reply: &str) -> Result<(usize, usize), String> {
+    let mut fields = reply.split_whitespace().map(|field| field.parse::<usize>());
+
+    match (fields.next(), fields.next()) {
+        (Some(Ok(row)), Some(Ok(column))) => Ok((row, column)),
+        _ => Err(format!("tiro inválido: {reply}")),
+    }
+}
+
+impl BotPlayer for ExternalBot {
+    fn name(&self) -> String {
+        self.command.clone()
+    }
+
+    fn new_game(&mut self, rules: &Rules) -> Result<(), String> {
+        self.fleet = rules.fleet.clone();
+        self.fire_deadline = None;
+
+        let ships: Vec<String> = self
+            .fleet
+            .iter()
+            .map(|ship| {
+                if ship.shape.is_line() {
+                    ship.length.to_string()
+                } else {
+                    ship.shape.to_string()
+                }
+            })
+            .collect();
+        self.send(&format!("newgame {} {} {}", rules.rows, rules.columns, ships.join(" ")))?;
+
+        if !rules.terrain.is_empty() {
+            for line in rules.terrain.to_string().lines() {
+                self.send(line)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn place(&mut self, _rules: &Rules) -> Result<Vec<ShipPlacement>, String> {
+        self.drain();
+        self.send("place")?;
+        let reply = self.reply()?;
+        let ships: Vec<&str> = reply.split_whitespace().collect();
+
+        if ships.len() != self.fleet.len() {
+            return Err(format!("posicionamento com {} navios: {reply}", ships.len()));
+        }
+
+        ships
+            .iter()
+            .zip(&self.fleet)
+            .map(|(ship, rule)| {
+                let fields: Vec<&str> = ship.split(',').collect();
+                let [row, column, direction] = fields[..] else {
+                    return Err(format!("navio inválido: {ship}"));
+                };
+
+                let row = row.parse().map_err(|_| format!("linha inválida: {ship}"))?;
+                let column = column.parse().map_err(|_| format!("coluna inválida: {ship}"))?;
+
+                if !rule.shape.is_line() {
+                    let (turns, mirrored) =
+                        parse_orientation(direction).ok_or_else(|| format!("giro inválido: {ship}"))?;
+                    let shape = rule.shape.oriented(turns, mirrored);
+                    return Ok(ShipPlacement::from_shape(&rule.name, row, column, &shape));
+                }
+
+                Ok(ShipPlacement {
+                    name: rule.name.clone(),
+                    row,
+                    column,
+                    length: rule.length,
+                    vertical: direction.eq_ignore_ascii_case("V"),
+                    shape: None,
+                })
+            })
+            .collect()
+    }
+
+    fn fire(&mut self) -> Result<(usize, usize), String> {
+        self.fire_deadline = None;
+        self.drain();
+        self.send("fire")?;
+        parse_shot(&self.reply()?)
+    }
+
+    // o pedido vai no primeiro chamado; nos seguintes só olha o canal da thread que lê o motor
+    fn poll_fire(&mut self) -> Option<Result<(usize, usize), String>> {
+        let deadline = match self.fire_deadline {
+            Some(deadline) => deadline,
+            None => {
+                self.drain();
+                if let Err(error) = self.send("fire") {
+                    return Some(Err(error));
+                }
+                *self.fire_deadline.insert(Instant::now() + self.time_limit)
+            }
+        };
+
+        let reply = loop {
+            match self.lines.lock().unwrap().try_recv() {
+                Ok(line) if line.trim().is_empty() || line.starts_with('#') => continue,
+                Ok(line) => break Ok(line),
+                Err(TryRecvError::Empty) if Instant::now() < deadline => return None,
+                Err(TryRecvError::Empty) => break Err(format!("tempo esgotado ({:?})", self.time_limit)),
+                Err(TryRecvError::Disconnected) => break Err("motor encerrou".to_string()),
+            }
+        };
+
+        self.fire_deadline = None;
+        Some(reply.and_then(|reply| parse_shot(&reply)))
+    }
+
+    fn is_thinking(&self) -> bool {
+        self.fire_deadline.is_some()
+    }
+
+    fn result(&mut self, row: usize, column: usize, outcome: ShotOutcome, sunk: Option<usize>) {
+        let _ = self.send(&format!("result {row} {column} {}", outcome_to_str(outcome)));
+
+        if let Some(length) = sunk {
+            let _ = self.send(&format!("sunk {length}"));
+        }
+    }
+
+    fn game_over(&mut self, won: bool) {
+        let _ = self.send(if won { "gameover WIN" } else { "gameover LOSS" });
+    }
+
+    fn opponent_moved(&mut self) {
+        let _ = self.send("moved");
+    }
+}
+
+// R<giros>[M], como em "R1" ou "R3M"
+fn parse_orientation(value: &str) -> Option<(usize, bool)> {
+    let value = value.to_ascii_uppercase();
+    let turns = value.strip_prefix('R')?;
+    let (turns, mirrored) = match turns.strip_suffix('M') {
+        Some(turns) => (turns, true),
+        None => (turns, false),
+    };
+
+    Some((turns.parse().ok()?, mirrored))
+}
+
+impl Drop for ExternalBot {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,676 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Condvar, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::logic::{
+    commitment::{random_salt, to_hex},
+    engine::{random_fleet, Match, RuleError, Rules, ShipPlacement},
+    shape::Shape,
+    shot::ShotOutcome,
+    terrain::Terrain,
+};
+
+/*
+ * api HTTP/JSON para bots e clientes scriptados, com as mesmas regras do
+ * servidor dedicado. só é compilada com a feature `http-api`
+ *
+ * POST /games                 cria a partida ({"opponent": "bot"} para jogar contra o bot)
+ * POST /games/{id}/join       entra no segundo lugar
+ * POST /games/{id}/ships      posiciona a frota
+ * POST /games/{id}/fire       atira
+ * GET  /games/{id}            estado do tabuleiro visto por quem pergunta
+ * GET  /games/{id}/events     eventos da partida via Server-Sent Events
+ *
+ * com exceção da criação e do join, as rotas pedem `Authorization: Bearer <token>`.
+ * partidas encerradas somem depois de FINISHED_GAME_TTL e as paradas depois de IDLE_GAME_TTL
+ */
+
+const SSE_KEEPALIVE: Duration = Duration::from_secs(15);
+const MAX_BODY_SIZE: usize = 64 * 1024;
+const MAX_HEADER_SIZE: u64 = 16 * 1024; // linha de requisição e cabeçalhos juntos
+const FINISHED_GAME_TTL: Duration = Duration::from_secs(10 * 60);
+const IDLE_GAME_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiEvent {
+    Joined { seat: usize },
+    Start { first: usize },
+    Turn { seat: usize },
+    Shot {
+        seat: usize,
+        row: usize,
+        column: usize,
+        outcome: &'static str,
+    },
+    GameOver { winner: usize },
+}
+
+impl ApiEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            ApiEvent::Joined { .. } => "joined",
+            ApiEvent::Start { .. } => "start",
+            ApiEvent::Turn { .. } => "turn",
+            ApiEvent::Shot { .. } => "shot",
+            ApiEvent::GameOver { .. } => "game_over",
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct CreateGameBody {
+    #[serde(default)]
+    opponent: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct ShipBody {
+    name: String,
+    row: usize,
+    column: usize,
+    length: usize,
+    #[serde(default)]
+    vertical: bool,
+    // células do navio com formato, já giradas: [[0,0],[1,0],[1,1]]
+    #[serde(default)]
+    cells: Option<Vec<(usize, usize)>>,
+}
+
+#[derive(Deserialize)]
+struct ShipsBody {
+    ships: Vec<ShipBody>,
+}
+
+#[derive(Deserialize)]
+struct FireBody {
+    row: usize,
+    column: usize,
+}
+
+#[derive(Serialize)]
+struct ShotBody {
+    row: usize,
+    column: usize,
+    outcome: &'static str,
+}
+
+fn outcome_name(outcome: ShotOutcome) -> &'static str {
+    match outcome {
+        ShotOutcome::Miss => "miss",
+        ShotOutcome::Hit => "hit",
+        ShotOutcome::Sunk => "sunk",
+    }
+}
+
+struct ApiGame {
+    game: Match,
+    tokens: [Option<String>; 2],
+    bot_seat: Option<usize>,
+    events: Vec<ApiEvent>,
+    last_activity: Instant,
+    finished_at: Option<Instant>,
+}
+
+impl ApiGame {
+    fn new(rules: &Rules, token: String) -> Self {
+        ApiGame {
+            game: Match::new(rules.clone()),
+            tokens: [Some(token), None],
+            bot_seat: None,
+            events: vec![ApiEvent::Joined { seat: 0 }],
+            last_activity: Instant::now(),
+            finished_at: None,
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        match self.finished_at {
+            Some(finished_at) => finished_at.elapsed() >= FINISHED_GAME_TTL,
+            None => self.last_activity.elapsed() >= IDLE_GAME_TTL,
+        }
+    }
+
+    fn seat_for(&self, token: Option<&str>) -> Result<usize, ApiError> {
+        let token = token.ok_or(ApiError::new(401, "token ausente"))?;
+
+        self.tokens
+            .iter()
+            .position(|seat_token| seat_token.as_deref() == Some(token))
+            .ok_or(ApiError::new(403, "token inválido para esta partida"))
+    }
+
+    fn after_fleet_placed(&mut self) {
+        if self.game.is_started() {
+            self.events.push(ApiEvent::Start {
+                first: self.game.turn,
+            });
+            self.events.push(ApiEvent::Turn {
+                seat: self.game.turn,
+            });
+            self.play_bot();
+        }
+    }
+
+    fn fire(&mut self, seat: usize, row: usize, column: usize) -> Result<ShotOutcome, RuleError> {
+        let outcome = self.game.fire(seat, row, column)?;
+
+        self.events.push(ApiEvent::Shot {
+            seat,
+            row,
+            column,
+            outcome: outcome_name(outcome),
+        });
+        self.last_activity = Instant::now();
+        self.events.push(match self.game.winner {
+            Some(winner) => {
+                self.finished_at = Some(Instant::now());
+                ApiEvent::GameOver { winner }
+            }
+            None => ApiEvent::Turn {
+                seat: self.game.turn,
+            },
+        });
+
+        Ok(outcome)
+    }
+
+    /**
+     * o bot embutido atira em células aleatórias enquanto for a vez dele: água
+     * primeiro e mina só quando não sobra água, nunca em ilha. se nenhum tiro
+     * for aceito ele desiste da partida em vez de ficar com a vez para sempre
+     */
+    fn play_bot(&mut self) {
+        let Some(bot_seat) = self.bot_seat else {
+            return;
+        };
+
+        let mut rng = rand::thread_rng();
+
+        while self.game.winner.is_none() && self.game.is_started() && self.game.turn == bot_seat {
+            let rules = &self.game.rules;
+            let shots = self.game.shots_by(bot_seat);
+
+            let (mut water, mut mines): (Vec<_>, Vec<_>) = (0..rules.rows)
+                .flat_map(|row| (0..rules.columns).map(move |column| (row, column)))
+                .filter(|cell| !shots.contains_key(cell))
+                .filter(|(row, column)| rules.terrain.get(*row, *column) != Terrain::Island)
+                .partition(|(row, column)| rules.terrain.is_water(*row, *column));
+            water.shuffle(&mut rng);
+            mines.shuffle(&mut rng);
+
+            let fired = water
+                .into_iter()
+                .chain(mines)
+                .any(|(row, column)| self.fire(bot_seat, row, column).is_ok());
+
+            if !fired {
+                self.forfeit(bot_seat);
+            }
+        }
+    }
+
+    fn forfeit(&mut self, seat: usize) {
+        self.game.forfeit(seat);
+
+        if let Some(winner) = self.game.winner {
+            self.finished_at = Some(Instant::now());
+            self.events.push(ApiEvent::GameOver { winner });
+        }
+    }
+
+    fn board_for(&self, seat: usize) -> serde_json::Value {
+        let shots = |side: usize| -> Vec<ShotBody> {
+            let mut shots: Vec<ShotBody> = self
+                .game
+                .shots_by(side)
+                .iter()
+                .map(|((row, column), outcome)| ShotBody {
+                    row: *row,
+                    column: *column,
+                    outcome: outcome_name(*outcome),
+                })
+                .collect();
+            shots.sort_by_key(|shot| (shot.row, shot.column));
+            shots
+        };
+
+        // navios do adversário nunca aparecem, só os resultados dos tiros contra ele
+        let own_ships: Vec<serde_json::Value> = self
+            .game
+            .fleet_layout(seat)
+            .ships
+            .iter()
+            .map(|ship| json!({ "name": ship.name, "cells": ship.cells }))
+            .collect();
+
+        json!({
+            "seat": seat,
+            "rows": self.game.rules.rows,
+            "columns": self.game.rules.columns,
+            "started": self.game.is_started(),
+            "turn": self.game.turn,
+            "winner": self.game.winner,
+            "own_ships": own_ships,
+            "incoming_shots": shots(1 - seat),
+            "outgoing_shots": shots(seat),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: &str) -> Self {
+        ApiError {
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<RuleError> for ApiError {
+    fn from(error: RuleError) -> Self {
+        let status = match error {
+            RuleError::OutOfBounds | RuleError::Overlap | RuleError::WrongFleet => 400,
+            _ => 409,
+        };
+
+        ApiError {
+            status,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(error: serde_json::Error) -> Self {
+        ApiError {
+            status: 400,
+            message: format!("json inválido: {error}"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Games {
+    games: Mutex<HashMap<String, ApiGame>>,
+    changed: Condvar, // acorda os streams de eventos quando algo acontece
+}
+
+struct Request {
+    method: String,
+    path: Vec<String>,
+    query: HashMap<String, String>,
+    token: Option<String>,
+    body: String,
+}
+
+// linha terminada em \n dentro do que ainda resta do limite dos cabeçalhos
+fn read_header_line(reader: &mut BufReader<&TcpStream>, remaining: &mut u64) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    let read = reader.by_ref().take(*remaining).read_line(&mut line)?;
+    *remaining -= read as u64;
+
+    if !line.ends_with('\n') && read > 0 {
+        return Ok(None);
+    }
+    Ok(Some(line))
+}
+
+impl Request {
+    /**
+     * lê a requisição inteira; o erro de fora é do socket, o de dentro é uma
+     * requisição que precisa ser recusada (cabeçalhos ou corpo grandes demais,
+     * Content-Length que não é número)
+     */
+    fn read(stream: &TcpStream) -> io::Result<Result<Self, ApiError>> {
+        let too_large = || ApiError::new(431, "cabeçalhos grandes demais");
+        let mut reader = BufReader::new(stream);
+        let mut remaining = MAX_HEADER_SIZE;
+
+        let Some(request_line) = read_header_line(&mut reader, &mut remaining)? else {
+            return Ok(Err(too_large()));
+        };
+
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or("/");
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let mut content_length = 0;
+        let mut token = None;
+
+        loop {
+            let Some(header) = read_header_line(&mut reader, &mut remaining)? else {
+                return Ok(Err(too_large()));
+            };
+            let header = header.trim();
+            if header.is_empty() {
+                break;
+            }
+
+            let Some((name, value)) = header.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match name.to_ascii_lowercase().as_str() {
+                "content-length" => {
+                    content_length = match value.parse::<usize>() {
+                        Ok(length) if length <= MAX_BODY_SIZE => length,
+                        Ok(_) => return Ok(Err(ApiError::new(413, "corpo grande demais"))),
+                        Err(_) => return Ok(Err(ApiError::new(400, "Content-Length inválido"))),
+                    }
+                }
+                "authorization" => {
+                    token = value.strip_prefix("Bearer ").map(|token| token.to_string())
+                }
+                _ => {}
+            }
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body)?;
+
+        Ok(Ok(Request {
+            method,
+            path: path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| segment.to_string())
+                .collect(),
+            query: query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            token,
+            body: String::from_utf8_lossy(&body).to_string(),
+        }))
+    }
+
+    fn json<'a, T: Deserialize<'a>>(&'a self) -> Result<T, ApiError> {
+        Ok(serde_json::from_str(&self.body)?)
+    }
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Error",
+    }
+}
+
+fn write_json(stream: &mut TcpStream, status: u16, body: &serde_json::Value) -> io::Result<()> {
+    let body = body.to_string();
+
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        status_text(status),
+        body.len()
+    )
+}
+
+/**
+ * responde a uma requisição recusada na leitura. o resto do que o cliente
+ * mandou é descartado (até um limite) antes de fechar, senão o socket
+ * fecha com dados pendentes e o cliente recebe um reset no lugar da resposta
+ */
+fn refuse(mut stream: TcpStream, error: ApiError) -> io::Result<()> {
+    write_json(&mut stream, error.status, &json!({ "error": error.message }))?;
+    stream.shutdown(Shutdown::Write)?;
+    stream.set_read_timeout(Some(Duration::from_millis(200)))?;
+    io::copy(&mut stream.take(MAX_BODY_SIZE as u64), &mut io::sink()).ok();
+    Ok(())
+}
+
+pub struct HttpApi {
+    listener: TcpListener,
+    games: Arc<Games>,
+    rules: Rules,
+}
+
+impl HttpApi {
+    pub fn bind(addr: impl ToSocketAddrs, rules: Rules) -> io::Result<Self> {
+        Ok(HttpApi {
+            listener: TcpListener::bind(addr)?,
+            games: Arc::new(Games::default()),
+            rules,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // sobe a api numa thread, para testar bots contra um servidor local
+    pub fn spawn(self) -> io::Result<SocketAddr> {
+        let addr = self.local_addr()?;
+        thread::spawn(move || self.run());
+        Ok(addr)
+    }
+
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let games = self.games.clone();
+            let rules = self.rules.clone();
+
+            thread::spawn(move || {
+                if let Err(error) = handle_request(stream, &games, &rules) {
+                    log::debug!("requisição http encerrada: {error}");
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn handle_request(mut stream: TcpStream, games: &Games, rules: &Rules) -> io::Result<()> {
+    let request = match Request::read(&stream)? {
+        Ok(request) => request,
+        Err(error) => return refuse(stream, error),
+    };
+    let path: Vec<&str> = request.path.iter().map(|segment| segment.as_str()).collect();
+
+    if let (["games", id, "events"], "GET") = (path.as_slice(), request.method.as_str()) {
+        return stream_events(stream, games, id, &request);
+    }
+
+    let (status, body) = match route(&request, &path, games, rules) {
+        Ok((status, body)) => (status, body),
+        Err(error) => (error.status, json!({ "error": error.message })),
+    };
+
+    games.changed.notify_all();
+    write_json(&mut stream, status, &body)
+}
+
+fn route(
+    request: &Request,
+    path: &[&str],
+    games: &Games,
+    rules: &Rules,
+) -> Result<(u16, serde_json::Value), ApiError> {
+    let mut games = games.games.lock().unwrap();
+    games.retain(|_, game| !game.is_expired());
+
+    match (request.method.as_str(), path) {
+        ("POST", ["games"]) => {
+            let body: CreateGameBody = if request.body.trim().is_empty() {
+                CreateGameBody::default()
+            } else {
+                request.json()?
+            };
+
+            let id = to_hex(&random_salt()[..6]);
+            let token = to_hex(&random_salt());
+            let mut game = ApiGame::new(rules, token.clone());
+
+            if body.opponent.as_deref() == Some("bot") {
+                let fleet = random_fleet(rules, &mut rand::thread_rng()).map_err(|error| ApiError {
+                    status: 500,
+                    message: error,
+                })?;
+                game.game.place_fleet(1, &fleet)?;
+                game.bot_seat = Some(1);
+                game.tokens[1] = Some(to_hex(&random_salt()));
+                game.events.push(ApiEvent::Joined { seat: 1 });
+            }
+
+            games.insert(id.clone(), game);
+            Ok((201, json!({ "id": id, "seat": 0, "token": token })))
+        }
+        ("POST", ["games", id, "join"]) => {
+            let game = games.get_mut(*id).ok_or(ApiError::new(404, "partida não encontrada"))?;
+
+            if game.tokens[1].is_some() {
+                return Err(ApiError::new(409, "partida cheia"));
+            }
+
+            let token = to_hex(&random_salt());
+            game.tokens[1] = Some(token.clone());
+            game.events.push(ApiEvent::Joined { seat: 1 });
+            game.last_activity = Instant::now();
+
+            Ok((201, json!({ "id": id, "seat": 1, "token": token })))
+        }
+        ("POST", ["games", id, "ships"]) => {
+            let game = games.get_mut(*id).ok_or(ApiError::new(404, "partida não encontrada"))?;
+            let seat = game.seat_for(request.token.as_deref())?;
+            let body: ShipsBody = request.json()?;
+
+            let placements: Vec<ShipPlacement> = body
+                .ships
+                .into_iter()
+                .map(|ship| match ship.cells {
+                    Some(cells) => Shape::new(&cells)
+                        .map(|shape| ShipPlacement::from_shape(&ship.name, ship.row, ship.column, &shape))
+                        .map_err(|error| ApiError::new(400, &error)),
+                    None => Ok(ShipPlacement {
+                        name: ship.name,
+                        row: ship.row,
+                        column: ship.column,
+                        length: ship.length,
+                        vertical: ship.vertical,
+                        shape: None,
+                    }),
+                })
+                .collect::<Result<_, _>>()?;
+
+            game.game.place_fleet(seat, &placements)?;
+            game.last_activity = Instant::now();
+            game.after_fleet_placed();
+
+            Ok((200, game.board_for(seat)))
+        }
+        ("POST", ["games", id, "fire"]) => {
+            let game = games.get_mut(*id).ok_or(ApiError::new(404, "partida não encontrada"))?;
+            let seat = game.seat_for(request.token.as_deref())?;
+            let body: FireBody = request.json()?;
+
+            let outcome = game.fire(seat, body.row, body.column)?;
+            game.play_bot();
+
+            Ok((
+                200,
+                json!({
+                    "outcome": outcome_name(outcome),
+                    "turn": game.game.turn,
+                    "winner": game.game.winner,
+                }),
+            ))
+        }
+        ("GET", ["games", id]) => {
+            let game = games.get(*id).ok_or(ApiError::new(404, "partida não encontrada"))?;
+            let seat = game.seat_for(request.token.as_deref())?;
+
+            Ok((200, game.board_for(seat)))
+        }
+        (_, ["games", ..]) => Err(ApiError::new(405, "método não suportado")),
+        _ => Err(ApiError::new(404, "rota não encontrada")),
+    }
+}
+
+// envia os eventos da partida (a partir de ?since=N) e segue aberto até o fim do jogo; só para quem joga
+fn stream_events(mut stream: TcpStream, games: &Games, id: &str, request: &Request) -> io::Result<()> {
+    let mut sent: usize = request
+        .query
+        .get("since")
+        .and_then(|since| since.parse().ok())
+        .unwrap_or(0);
+
+    let allowed = match games.games.lock().unwrap().get(id) {
+        Some(game) => game.seat_for(request.token.as_deref()).map(|_| ()),
+        None => Err(ApiError::new(404, "partida não encontrada")),
+    };
+    if let Err(error) = allowed {
+        return write_json(&mut stream, error.status, &json!({ "error": error.message }));
+    }
+
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n"
+    )?;
+
+    loop {
+        // copia os eventos pendentes para não segurar o lock enquanto escreve no socket
+        let (pending, finished) = {
+            let state = games.games.lock().unwrap();
+            let Some(game) = state.get(id) else {
+                return Ok(());
+            };
+
+            let pending: Vec<ApiEvent> = game.events.iter().skip(sent).cloned().collect();
+            (pending, game.game.winner.is_some())
+        };
+
+        for event in pending {
+            let data = serde_json::to_string(&event).unwrap_or_default();
+            write!(stream, "id: {sent}\nevent: {}\ndata: {data}\n\n", event.name())?;
+            sent += 1;
+        }
+
+        if finished {
+            return Ok(());
+        }
+
+        let state = games.games.lock().unwrap();
+        if state.get(id).is_some_and(|game| game.events.len() > sent) {
+            continue;
+        }
+
+        let (state, timeout) = games.changed.wait_timeout(state, SSE_KEEPALIVE).unwrap();
+        drop(state);
+
+        // comentário vazio para detectar cliente que fechou a conexão
+        if timeout.timed_out() {
+            write!(stream, ": keepalive\n\n")?;
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod client;
+pub mod external_bot;
+pub mod protocol;
+pub mod server;
+
+#[cfg(feature = "http-api")]
+pub mod http;
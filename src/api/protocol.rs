@@ -0,0 +1,434 @@
+use crate::logic::{
+    ability::Ability,
+    commitment::{from_hex, to_hex, FleetCommitment, FleetLayout, FleetReveal},
+    engine::ShipPlacement,
+    shape::Shape,
+    shot::ShotOutcome,
+};
+
+/*
+ * protocolo de texto do servidor de partidas: uma mensagem por linha,
+ * campos separados por espaço. navios são escritos como
+ * nome:linha,coluna,tamanho,H|V. compromissos de frota vão em hexadecimal
+ * e a frota revelada na forma canônica do compromisso. habilidades (regras
+ * avançadas) vão pelo nome do arquivo de regras: ABILITY torpedo 2 0. com
+ * navios em movimento, MOVE leva o índice do navio na ordem do FLEET
+ */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClientMessage {
+    Join { room: String, name: String },
+    Reconnect { token: String },
+    Fleet(Vec<ShipPlacement>),
+    Fire { row: usize, column: usize },
+    Ability { ability: Ability, row: usize, column: usize },
+    Move { ship: usize, forward: bool },
+    Commit(FleetCommitment),
+    Reveal(FleetReveal),
+    Spectate { room: String, view: SpectatorView },
+    View(SpectatorView),
+    Leave,
+}
+
+// o que um espectador enxerga: as duas frotas ou a neblina de um dos lugares
+// (numa sala com jogadores as frotas só chegam no fim, ver Room::shows_fleet)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpectatorView {
+    Full,
+    Seat(usize),
+}
+
+impl SpectatorView {
+    pub fn shows_fleet(self, seat: usize) -> bool {
+        match self {
+            SpectatorView::Full => true,
+            SpectatorView::Seat(viewer) => viewer == seat,
+        }
+    }
+
+    // lugar cujo tabuleiro fica na metade de baixo da tela
+    pub fn perspective(self) -> usize {
+        match self {
+            SpectatorView::Full => 0,
+            SpectatorView::Seat(seat) => seat,
+        }
+    }
+
+    // FULL -> 0 -> 1 -> FULL
+    pub fn next(self) -> Self {
+        match self {
+            SpectatorView::Full => SpectatorView::Seat(0),
+            SpectatorView::Seat(0) => SpectatorView::Seat(1),
+            SpectatorView::Seat(_) => SpectatorView::Full,
+        }
+    }
+
+    pub fn to_str(self) -> String {
+        match self {
+            SpectatorView::Full => "FULL".to_string(),
+            SpectatorView::Seat(seat) => seat.to_string(),
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "FULL" => Ok(SpectatorView::Full),
+            "0" => Ok(SpectatorView::Seat(0)),
+            "1" => Ok(SpectatorView::Seat(1)),
+            _ => Err(format!("visão inválida: {value}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServerMessage {
+    Welcome { seat: usize, token: String },
+    Spectating { view: SpectatorView },
+    Players { names: [String; 2] },
+    YourFleet(Vec<ShipPlacement>),
+    FleetOf { seat: usize, placements: Vec<ShipPlacement> }, // só para espectadores
+    FleetAccepted,
+    Start { first: usize },
+    Turn { seat: usize },
+    Shot {
+        seat: usize,
+        row: usize,
+        column: usize,
+        outcome: ShotOutcome,
+    },
+    // habilidade usada no lugar do tiro; os tiros dela chegam depois como SHOT
+    Ability {
+        seat: usize,
+        ability: Ability,
+        row: usize,
+        column: usize,
+    },
+    Sonar {
+        seat: usize,
+        row: usize,
+        column: usize,
+        found: bool,
+    },
+    Moved { seat: usize }, // qual navio andou fica em segredo, como a frota
+    Timeout { seat: usize },
+    GameOver { winner: usize },
+    Commit { seat: usize, commitment: FleetCommitment }, // repassado ao adversário
+    Reveal { seat: usize, reveal: FleetReveal },         // repassado ao adversário no fim
+    OpponentDisconnected,
+    OpponentReconnected,
+    Error(String),
+}
+
+pub fn outcome_to_str(outcome: ShotOutcome) -> &'static str {
+    match outcome {
+        ShotOutcome::Miss => "MISS",
+        ShotOutcome::Hit => "HIT",
+        ShotOutcome::Sunk => "SUNK",
+    }
+}
+
+pub fn parse_outcome(value: &str) -> Result<ShotOutcome, String> {
+    match value {
+        "MISS" => Ok(ShotOutcome::Miss),
+        "HIT" => Ok(ShotOutcome::Hit),
+        "SUNK" => Ok(ShotOutcome::Sunk),
+        _ => Err(format!("resultado inválido: {value}")),
+    }
+}
+
+// navio com formato leva as células no fim: Nome:linha,coluna,tamanho,S,0,0;1,0;1,1
+pub fn placement_to_str(placement: &ShipPlacement) -> String {
+    let direction = match &placement.shape {
+        Some(shape) => format!("S,{shape}"),
+        None if placement.vertical => "V".to_string(),
+        None => "H".to_string(),
+    };
+
+    format!(
+        "{}:{},{},{},{direction}",
+        placement.name, placement.row, placement.column, placement.length
+    )
+}
+
+pub fn parse_placement(value: &str) -> Result<ShipPlacement, String> {
+    let invalid = || format!("navio inválido: {value}");
+
+    let (name, position) = value.split_once(':').ok_or_else(invalid)?;
+    let fields: Vec<&str> = position.splitn(5, ',').collect();
+
+    let (row, column, length, direction, shape) = match fields[..] {
+        [row, column, length, direction] => (row, column, length, direction, None),
+        [row, column, length, "S", shape] => (row, column, length, "S", Some(Shape::parse(shape)?)),
+        _ => return Err(invalid()),
+    };
+
+    let placement = ShipPlacement {
+        name: name.to_string(),
+        row: row.parse().map_err(|_| invalid())?,
+        column: column.parse().map_err(|_| invalid())?,
+        length: length.parse().map_err(|_| invalid())?,
+        vertical: match (direction, &shape) {
+            ("V", _) => true,
+            ("H", _) => false,
+            // mesmo eixo que o posicionamento do formato teve do outro lado
+            ("S", Some(shape)) => shape.size().0 > 1,
+            _ => return Err(invalid()),
+        },
+        shape,
+    };
+
+    if placement.cells().len() != placement.length {
+        return Err(invalid());
+    }
+
+    Ok(placement)
+}
+
+fn parse_number(value: Option<&str>) -> Result<usize, String> {
+    let value = value.ok_or("campo numérico ausente")?;
+    value.parse().map_err(|_| format!("número inválido: {value}"))
+}
+
+fn parse_word(value: Option<&str>) -> Result<String, String> {
+    value
+        .map(|value| value.to_string())
+        .ok_or_else(|| "campo ausente".to_string())
+}
+
+fn parse_ability(value: Option<&str>) -> Result<Ability, String> {
+    let value = value.ok_or("habilidade ausente")?;
+    Ability::from_name(value).ok_or_else(|| format!("habilidade inválida: {value}"))
+}
+
+// sentido do MOVE: FORWARD é para a linha ou coluna maior
+fn parse_direction(value: Option<&str>) -> Result<bool, String> {
+    match value {
+        Some("FORWARD") => Ok(true),
+        Some("BACK") => Ok(false),
+        _ => Err(format!("sentido inválido: {}", value.unwrap_or(""))),
+    }
+}
+
+fn parse_commitment(value: Option<&str>) -> Result<FleetCommitment, String> {
+    let value = value.ok_or("compromisso ausente")?;
+    FleetCommitment::from_hex(value).ok_or_else(|| format!("compromisso inválido: {value}"))
+}
+
+// salt em hexadecimal seguido da frota canônica (vazia se a frota não tem navios)
+fn parse_reveal<'a>(mut fields: impl Iterator<Item = &'a str>) -> Result<FleetReveal, String> {
+    let salt = parse_word(fields.next())?;
+    let layout = FleetLayout::parse(fields.next().unwrap_or(""))?;
+
+    Ok(FleetReveal {
+        layout,
+        salt: from_hex(&salt)
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| format!("salt inválido: {salt}"))?,
+    })
+}
+
+fn reveal_to_str(reveal: &FleetReveal) -> String {
+    format!("{} {}", to_hex(&reveal.salt), reveal.layout.canonical())
+        .trim_end()
+        .to_string()
+}
+
+fn fleet_to_str(placements: &[ShipPlacement]) -> String {
+    placements
+        .iter()
+        .map(placement_to_str)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+impl ClientMessage {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut fields = line.split_whitespace();
+
+        match fields.next() {
+            Some("JOIN") => Ok(ClientMessage::Join {
+                room: parse_word(fields.next())?,
+                name: parse_word(fields.next()).unwrap_or_else(|_| "jogador".to_string()),
+            }),
+            Some("RECONNECT") => Ok(ClientMessage::Reconnect {
+                token: parse_word(fields.next())?,
+            }),
+            Some("FLEET") => Ok(ClientMessage::Fleet(
+                fields.map(parse_placement).collect::<Result<_, _>>()?,
+            )),
+            Some("FIRE") => Ok(ClientMessage::Fire {
+                row: parse_number(fields.next())?,
+                column: parse_number(fields.next())?,
+            }),
+            Some("ABILITY") => Ok(ClientMessage::Ability {
+                ability: parse_ability(fields.next())?,
+                row: parse_number(fields.next())?,
+                column: parse_number(fields.next())?,
+            }),
+            Some("MOVE") => Ok(ClientMessage::Move {
+                ship: parse_number(fields.next())?,
+                forward: parse_direction(fields.next())?,
+            }),
+            Some("COMMIT") => Ok(ClientMessage::Commit(parse_commitment(fields.next())?)),
+            Some("REVEAL") => Ok(ClientMessage::Reveal(parse_reveal(fields)?)),
+            Some("SPECTATE") => Ok(ClientMessage::Spectate {
+                room: parse_word(fields.next())?,
+                view: fields
+                    .next()
+                    .map_or(Ok(SpectatorView::Full), SpectatorView::parse)?,
+            }),
+            Some("VIEW") => Ok(ClientMessage::View(SpectatorView::parse(&parse_word(
+                fields.next(),
+            )?)?)),
+            Some("LEAVE") => Ok(ClientMessage::Leave),
+            _ => Err(format!("comando desconhecido: {line}")),
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        match self {
+            ClientMessage::Join { room, name } => format!("JOIN {room} {name}"),
+            ClientMessage::Reconnect { token } => format!("RECONNECT {token}"),
+            ClientMessage::Fleet(placements) => format!("FLEET {}", fleet_to_str(placements)),
+            ClientMessage::Fire { row, column } => format!("FIRE {row} {column}"),
+            ClientMessage::Ability { ability, row, column } => {
+                format!("ABILITY {} {row} {column}", ability.name())
+            }
+            ClientMessage::Move { ship, forward } => {
+                format!("MOVE {ship} {}", if *forward { "FORWARD" } else { "BACK" })
+            }
+            ClientMessage::Commit(commitment) => format!("COMMIT {}", commitment.to_hex()),
+            ClientMessage::Reveal(reveal) => format!("REVEAL {}", reveal_to_str(reveal)),
+            ClientMessage::Spectate { room, view } => {
+                format!("SPECTATE {room} {}", view.to_str())
+            }
+            ClientMessage::View(view) => format!("VIEW {}", view.to_str()),
+            ClientMessage::Leave => "LEAVE".to_string(),
+        }
+    }
+}
+
+impl ServerMessage {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut fields = line.split_whitespace();
+
+        match fields.next() {
+            Some("WELCOME") => Ok(ServerMessage::Welcome {
+                seat: parse_number(fields.next())?,
+                token: parse_word(fields.next())?,
+            }),
+            Some("SPECTATING") => Ok(ServerMessage::Spectating {
+                view: SpectatorView::parse(&parse_word(fields.next())?)?,
+            }),
+            Some("PLAYERS") => Ok(ServerMessage::Players {
+                names: [parse_word(fields.next())?, parse_word(fields.next())?],
+            }),
+            Some("YOURFLEET") => Ok(ServerMessage::YourFleet(
+                fields.map(parse_placement).collect::<Result<_, _>>()?,
+            )),
+            Some("FLEETOF") => Ok(ServerMessage::FleetOf {
+                seat: parse_number(fields.next())?,
+                placements: fields.map(parse_placement).collect::<Result<_, _>>()?,
+            }),
+            Some("FLEETOK") => Ok(ServerMessage::FleetAccepted),
+            Some("START") => Ok(ServerMessage::Start {
+                first: parse_number(fields.next())?,
+            }),
+            Some("TURN") => Ok(ServerMessage::Turn {
+                seat: parse_number(fields.next())?,
+            }),
+            Some("SHOT") => Ok(ServerMessage::Shot {
+                seat: parse_number(fields.next())?,
+                row: parse_number(fields.next())?,
+                column: parse_number(fields.next())?,
+                outcome: parse_outcome(&parse_word(fields.next())?)?,
+            }),
+            Some("ABILITY") => Ok(ServerMessage::Ability {
+                seat: parse_number(fields.next())?,
+                ability: parse_ability(fields.next())?,
+                row: parse_number(fields.next())?,
+                column: parse_number(fields.next())?,
+            }),
+            Some("SONAR") => Ok(ServerMessage::Sonar {
+                seat: parse_number(fields.next())?,
+                row: parse_number(fields.next())?,
+                column: parse_number(fields.next())?,
+                found: match fields.next() {
+                    Some("FOUND") => true,
+                    Some("CLEAR") => false,
+                    other => return Err(format!("resposta do sonar inválida: {}", other.unwrap_or(""))),
+                },
+            }),
+            Some("MOVED") => Ok(ServerMessage::Moved {
+                seat: parse_number(fields.next())?,
+            }),
+            Some("TIMEOUT") => Ok(ServerMessage::Timeout {
+                seat: parse_number(fields.next())?,
+            }),
+            Some("GAMEOVER") => Ok(ServerMessage::GameOver {
+                winner: parse_number(fields.next())?,
+            }),
+            Some("COMMIT") => Ok(ServerMessage::Commit {
+                seat: parse_number(fields.next())?,
+                commitment: parse_commitment(fields.next())?,
+            }),
+            Some("REVEAL") => Ok(ServerMessage::Reveal {
+                seat: parse_number(fields.next())?,
+                reveal: parse_reveal(fields)?,
+            }),
+            Some("OPPONENT_DISCONNECTED") => Ok(ServerMessage::OpponentDisconnected),
+            Some("OPPONENT_RECONNECTED") => Ok(ServerMessage::OpponentReconnected),
+            Some("ERROR") => Ok(ServerMessage::Error(
+                fields.collect::<Vec<&str>>().join(" "),
+            )),
+            _ => Err(format!("mensagem desconhecida: {line}")),
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        match self {
+            ServerMessage::Welcome { seat, token } => format!("WELCOME {seat} {token}"),
+            ServerMessage::Spectating { view } => format!("SPECTATING {}", view.to_str()),
+            ServerMessage::Players { names } => format!("PLAYERS {} {}", names[0], names[1]),
+            ServerMessage::YourFleet(placements) => {
+                format!("YOURFLEET {}", fleet_to_str(placements))
+            }
+            ServerMessage::FleetOf { seat, placements } => {
+                format!("FLEETOF {seat} {}", fleet_to_str(placements))
+            }
+            ServerMessage::FleetAccepted => "FLEETOK".to_string(),
+            ServerMessage::Start { first } => format!("START {first}"),
+            ServerMessage::Turn { seat } => format!("TURN {seat}"),
+            ServerMessage::Shot {
+                seat,
+                row,
+                column,
+                outcome,
+            } => format!("SHOT {seat} {row} {column} {}", outcome_to_str(*outcome)),
+            ServerMessage::Ability {
+                seat,
+                ability,
+                row,
+                column,
+            } => format!("ABILITY {seat} {} {row} {column}", ability.name()),
+            ServerMessage::Sonar {
+                seat,
+                row,
+                column,
+                found,
+            } => format!("SONAR {seat} {row} {column} {}", if *found { "FOUND" } else { "CLEAR" }),
+            ServerMessage::Moved { seat } => format!("MOVED {seat}"),
+            ServerMessage::Timeout { seat } => format!("TIMEOUT {seat}"),
+            ServerMessage::GameOver { winner } => format!("GAMEOVER {winner}"),
+            ServerMessage::Commit { seat, commitment } => {
+                format!("COMMIT {seat} {}", commitment.to_hex())
+            }
+            ServerMessage::Reveal { seat, reveal } => {
+                format!("REVEAL {seat} {}", reveal_to_str(reveal))
+            }
+            ServerMessage::OpponentDisconnected => "OPPONENT_DISCONNECTED".to_string(),
+            ServerMessage::OpponentReconnected => "OPPONENT_RECONNECTED".to_string(),
+            ServerMessage::Error(message) => format!("ERROR {message}"),
+        }
+    }
+}
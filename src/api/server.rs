@@ -0,0 +1,849 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::logic::{
+    ability::{Ability, AbilityOutcome},
+    commitment::{random_salt, to_hex, FleetCommitment, FleetReveal},
+    engine::{Match, Rules, ShipPlacement},
+    shot::ShotOutcome,
+    tournament::{GameReport, MatchObserver},
+};
+
+use super::protocol::{ClientMessage, ServerMessage, SpectatorView};
+
+/*
+ * servidor dedicado: hospeda várias partidas em salas nomeadas, valida
+ * cada tiro com o motor de regras, controla a vez e o tempo de cada
+ * jogada e permite reconectar com o token recebido no WELCOME. qualquer
+ * um pode assistir uma sala com SPECTATE, sem poder jogar
+ */
+
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    pub rules: Rules,
+    pub turn_timeout: Duration,
+    pub max_timeouts: u32, // tempos esgotados seguidos até perder por abandono
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            rules: Rules::default(),
+            turn_timeout: Duration::from_secs(30),
+            max_timeouts: 3,
+        }
+    }
+}
+
+struct Seat {
+    token: String,
+    writer: Option<TcpStream>,
+    connection: usize, // conexão dona do writer; uma queda antiga não derruba a reconexão
+    timeouts: u32,
+}
+
+struct Spectator {
+    writer: Option<TcpStream>,
+    view: SpectatorView,
+}
+
+struct Room {
+    game: Match,
+    seats: [Option<Seat>; 2],
+    names: [String; 2],
+    fleets: [Vec<ShipPlacement>; 2],
+    commitments: [Option<FleetCommitment>; 2], // hash da frota de cada lado, repassado ao outro
+    reveals: [Option<FleetReveal>; 2],         // frota e salt revelados depois do fim
+    spectators: HashMap<usize, Spectator>,
+    history: Vec<ServerMessage>, // eventos públicos, reenviados na reconexão
+    turn_started: Instant,
+    mirrored: bool, // partida jogada fora do servidor (torneio), só para espectadores
+}
+
+impl Room {
+    fn new(rules: Rules) -> Self {
+        Room {
+            game: Match::new(rules),
+            seats: [None, None],
+            names: ["-".to_string(), "-".to_string()],
+            fleets: [Vec::new(), Vec::new()],
+            commitments: [None, None],
+            reveals: [None, None],
+            spectators: HashMap::new(),
+            history: Vec::new(),
+            turn_started: Instant::now(),
+            mirrored: false,
+        }
+    }
+
+    fn send(&mut self, seat: usize, message: &ServerMessage) {
+        if let Some(seat) = self.seats[seat].as_mut() {
+            send_to(&mut seat.writer, message);
+        }
+    }
+
+    fn send_to_spectators(&mut self, message: &ServerMessage) {
+        for spectator in self.spectators.values_mut() {
+            send_to(&mut spectator.writer, message);
+        }
+    }
+
+    fn broadcast(&mut self, message: ServerMessage) {
+        for seat in 0..2 {
+            self.send(seat, &message);
+        }
+        self.send_to_spectators(&message);
+
+        // fim de jogo: todos os espectadores passam a ver as duas frotas
+        if matches!(message, ServerMessage::GameOver { .. }) {
+            for seat in 0..2 {
+                self.reveal_fleet(seat, true);
+            }
+        }
+
+        // a vez atual é reenviada à parte na reconexão
+        if !matches!(message, ServerMessage::Turn { .. }) {
+            self.history.push(message);
+        }
+    }
+
+    // compromisso e revelação do adversário, para quem entrou ou reconectou depois deles
+    fn send_opponent_commitment(&mut self, seat: usize) {
+        let opponent = 1 - seat;
+
+        if let Some(commitment) = self.commitments[opponent] {
+            self.send(seat, &ServerMessage::Commit {
+                seat: opponent,
+                commitment,
+            });
+        }
+        if let Some(reveal) = self.reveals[opponent].clone() {
+            self.send(seat, &ServerMessage::Reveal {
+                seat: opponent,
+                reveal,
+            });
+        }
+    }
+
+    /**
+     * espectador não se identifica, então qualquer um (até quem joga, numa
+     * segunda conexão) pode assistir: numa sala com jogadores as frotas só
+     * aparecem depois do fim. a sala espelhada de bots mostra durante a partida
+     */
+    fn shows_fleet(&self, view: SpectatorView, seat: usize) -> bool {
+        self.game.winner.is_some() || (self.mirrored && view.shows_fleet(seat))
+    }
+
+    // frota de `seat` para os espectadores que podem vê-la (ou todos, com `everyone`)
+    fn reveal_fleet(&mut self, seat: usize, everyone: bool) {
+        if self.fleets[seat].is_empty() || !(everyone || self.mirrored) {
+            return;
+        }
+
+        let message = ServerMessage::FleetOf {
+            seat,
+            placements: self.fleets[seat].clone(),
+        };
+
+        for spectator in self.spectators.values_mut() {
+            if everyone || spectator.view.shows_fleet(seat) {
+                send_to(&mut spectator.writer, &message);
+            }
+        }
+    }
+
+    // estado completo da sala para um espectador que chegou agora ou trocou de visão
+    fn send_snapshot(&mut self, id: usize) {
+        let mut messages = Vec::new();
+
+        let Some(spectator) = self.spectators.get(&id) else {
+            return;
+        };
+
+        messages.push(ServerMessage::Spectating {
+            view: spectator.view,
+        });
+        messages.push(ServerMessage::Players {
+            names: self.names.clone(),
+        });
+        for seat in 0..2 {
+            if !self.fleets[seat].is_empty() && self.shows_fleet(spectator.view, seat) {
+                messages.push(ServerMessage::FleetOf {
+                    seat,
+                    placements: self.fleets[seat].clone(),
+                });
+            }
+        }
+        messages.extend(self.history.iter().cloned());
+        if self.in_progress() {
+            messages.push(ServerMessage::Turn {
+                seat: self.game.turn,
+            });
+        }
+
+        let spectator = self.spectators.get_mut(&id).unwrap();
+        for message in &messages {
+            send_to(&mut spectator.writer, message);
+        }
+    }
+
+    fn in_progress(&self) -> bool {
+        self.game.is_started() && self.game.winner.is_none()
+    }
+
+    fn is_abandoned(&self) -> bool {
+        !self.mirrored
+            && self.seats.iter().flatten().all(|seat| seat.writer.is_none())
+    }
+}
+
+#[derive(Default)]
+struct ServerState {
+    rooms: HashMap<String, Room>,
+    tokens: HashMap<String, (String, usize)>, // token -> (sala, lugar)
+    next_connection: usize,
+}
+
+enum Session {
+    Player(String, usize),    // sala e lugar
+    Spectator(String, usize), // sala e id do espectador
+}
+
+// nomes viajam como uma palavra só no protocolo
+fn single_word(name: &str) -> String {
+    let word = name.split_whitespace().collect::<Vec<&str>>().join("_");
+
+    if word.is_empty() {
+        "-".to_string()
+    } else {
+        word
+    }
+}
+
+// sala de quem joga; ela pode ter sido removida enquanto a conexão estava parada
+fn player_room<'a>(
+    state: &'a mut ServerState,
+    room: &str,
+    reply: &mut Option<TcpStream>,
+) -> Option<&'a mut Room> {
+    let found = state.rooms.get_mut(room);
+
+    if found.is_none() {
+        send_to(reply, &ServerMessage::Error("sala inexistente".to_string()));
+    }
+    found
+}
+
+fn send_to(writer: &mut Option<TcpStream>, message: &ServerMessage) {
+    if let Some(stream) = writer {
+        if writeln!(stream, "{}", message.to_line()).is_err() {
+            *writer = None;
+        }
+    }
+}
+
+pub struct GameServer {
+    listener: TcpListener,
+    state: Arc<Mutex<ServerState>>,
+    config: ServerConfig,
+}
+
+impl GameServer {
+    pub fn bind(addr: impl ToSocketAddrs, config: ServerConfig) -> io::Result<Self> {
+        Ok(GameServer {
+            listener: TcpListener::bind(addr)?,
+            state: Arc::new(Mutex::new(ServerState::default())),
+            config,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /**
+     * sala só para espectadores alimentada por partidas jogadas fora do
+     * servidor, como as do torneio de bots; `pace` é a pausa entre tiros
+     */
+    pub fn feed(&self, room: &str, pace: Duration) -> RoomFeed {
+        let mut state = self.state.lock().unwrap();
+        let mut mirrored = Room::new(self.config.rules.clone());
+        mirrored.mirrored = true;
+        state.rooms.insert(room.to_string(), mirrored);
+
+        RoomFeed {
+            state: self.state.clone(),
+            room: room.to_string(),
+            pace,
+        }
+    }
+
+    // roda o servidor numa thread separada, útil para um servidor local em 127.0.0.1
+    pub fn spawn(self) -> io::Result<SocketAddr> {
+        let addr = self.local_addr()?;
+        thread::spawn(move || self.run());
+        Ok(addr)
+    }
+
+    pub fn run(self) -> io::Result<()> {
+        let watchdog_state = self.state.clone();
+        let watchdog_config = self.config.clone();
+        thread::spawn(move || watch_turn_timeouts(watchdog_state, watchdog_config));
+
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let state = self.state.clone();
+            let config = self.config.clone();
+
+            thread::spawn(move || {
+                if let Err(error) = handle_connection(stream, state, config) {
+                    log::debug!("conexão encerrada: {error}");
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    state: Arc<Mutex<ServerState>>,
+    config: ServerConfig,
+) -> io::Result<()> {
+    let reader = BufReader::new(stream.try_clone()?);
+    let mut reply = Some(stream.try_clone()?);
+    let mut session: Option<Session> = None;
+    let connection = {
+        let mut state = state.lock().unwrap();
+        state.next_connection += 1;
+        state.next_connection
+    };
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message = match ClientMessage::parse(&line) {
+            Ok(message) => message,
+            Err(error) => {
+                send_to(&mut reply, &ServerMessage::Error(error));
+                continue;
+            }
+        };
+
+        let mut state = state.lock().unwrap();
+
+        match (message, &session) {
+            (ClientMessage::Join { room, name }, None) => {
+                match join(&mut state, &config, &room, &name, stream.try_clone()?, connection) {
+                    Ok(seat) => {
+                        log::info!("{name} entrou na sala {room} no lugar {seat}");
+                        session = Some(Session::Player(room, seat));
+                    }
+                    Err(error) => send_to(&mut reply, &ServerMessage::Error(error.to_string())),
+                }
+            }
+            (ClientMessage::Reconnect { token }, None) => {
+                match reconnect(&mut state, &token, stream.try_clone()?, connection) {
+                    Some((room, seat)) => session = Some(Session::Player(room, seat)),
+                    None => {
+                        send_to(&mut reply, &ServerMessage::Error("token inválido".to_string()))
+                    }
+                }
+            }
+            (ClientMessage::Spectate { room, view }, None) => {
+                let id = connection;
+
+                match state.rooms.get_mut(&room) {
+                    Some(watched) => {
+                        watched.spectators.insert(
+                            id,
+                            Spectator {
+                                writer: Some(stream.try_clone()?),
+                                view,
+                            },
+                        );
+                        watched.send_snapshot(id);
+                        session = Some(Session::Spectator(room, id));
+                    }
+                    None => {
+                        send_to(&mut reply, &ServerMessage::Error("sala inexistente".to_string()))
+                    }
+                }
+            }
+            (
+                ClientMessage::Join { .. }
+                | ClientMessage::Reconnect { .. }
+                | ClientMessage::Spectate { .. },
+                Some(_),
+            ) => {
+                send_to(&mut reply, &ServerMessage::Error("já está numa sala".to_string()));
+            }
+            (_, None) => {
+                send_to(&mut reply, &ServerMessage::Error("entre numa sala primeiro".to_string()));
+            }
+            (ClientMessage::View(view), Some(Session::Spectator(room, id))) => {
+                if let Some(watched) = state.rooms.get_mut(room) {
+                    if let Some(spectator) = watched.spectators.get_mut(id) {
+                        spectator.view = view;
+                    }
+                    watched.send_snapshot(*id);
+                }
+            }
+            (ClientMessage::Leave, Some(Session::Spectator(..))) => break,
+            (_, Some(Session::Spectator(..))) => {
+                send_to(&mut reply, &ServerMessage::Error("espectadores só assistem".to_string()));
+            }
+            (ClientMessage::View(_), Some(Session::Player(..))) => {
+                send_to(&mut reply, &ServerMessage::Error("VIEW é só para espectadores".to_string()));
+            }
+            (ClientMessage::Fleet(placements), Some(Session::Player(room, seat))) => {
+                let Some(room) = player_room(&mut state, room, &mut reply) else {
+                    continue;
+                };
+                place_fleet(room, *seat, placements);
+            }
+            (ClientMessage::Fire { row, column }, Some(Session::Player(room, seat))) => {
+                let Some(room) = player_room(&mut state, room, &mut reply) else {
+                    continue;
+                };
+                fire(room, *seat, row, column);
+            }
+            (ClientMessage::Ability { ability, row, column }, Some(Session::Player(room, seat))) => {
+                let Some(room) = player_room(&mut state, room, &mut reply) else {
+                    continue;
+                };
+                use_ability(room, *seat, ability, row, column);
+            }
+            (ClientMessage::Move { ship, forward }, Some(Session::Player(room, seat))) => {
+                let Some(room) = player_room(&mut state, room, &mut reply) else {
+                    continue;
+                };
+                move_ship(room, *seat, ship, forward);
+            }
+            (ClientMessage::Commit(commitment), Some(Session::Player(room, seat))) => {
+                let Some(room) = player_room(&mut state, room, &mut reply) else {
+                    continue;
+                };
+                commit(room, *seat, commitment);
+            }
+            (ClientMessage::Reveal(reveal), Some(Session::Player(room, seat))) => {
+                let Some(room) = player_room(&mut state, room, &mut reply) else {
+                    continue;
+                };
+                reveal_commitment(room, *seat, reveal);
+            }
+            (ClientMessage::Leave, Some(Session::Player(room, seat))) => {
+                let Some(room) = player_room(&mut state, room, &mut reply) else {
+                    break;
+                };
+                if room.in_progress() {
+                    room.game.forfeit(*seat);
+                    room.broadcast(ServerMessage::GameOver {
+                        winner: 1 - *seat,
+                    });
+                }
+                break;
+            }
+        }
+    }
+
+    // conexão caiu: o lugar fica reservado para o token até a sala ser abandonada
+    if let Some(Session::Spectator(room_name, id)) = &session {
+        let mut state = state.lock().unwrap();
+
+        if let Some(room) = state.rooms.get_mut(room_name) {
+            room.spectators.remove(id);
+        }
+    }
+
+    if let Some(Session::Player(room_name, seat)) = session {
+        let mut state = state.lock().unwrap();
+
+        if let Some(room) = state.rooms.get_mut(&room_name) {
+            // o lugar já pode estar com uma reconexão mais nova, que continua valendo
+            let Some(current) = room.seats[seat].as_mut().filter(|current| current.connection == connection)
+            else {
+                return Ok(());
+            };
+            current.writer = None;
+            room.send(1 - seat, &ServerMessage::OpponentDisconnected);
+
+            if room.is_abandoned() && !room.in_progress() {
+                remove_room(&mut state, &room_name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_room(state: &mut ServerState, room_name: &str) {
+    state.rooms.remove(room_name);
+    state.tokens.retain(|_, (room, _)| room != room_name);
+}
+
+fn join(
+    state: &mut ServerState,
+    config: &ServerConfig,
+    room_name: &str,
+    name: &str,
+    writer: TcpStream,
+    connection: usize,
+) -> Result<usize, &'static str> {
+    let room = state
+        .rooms
+        .entry(room_name.to_string())
+        .or_insert_with(|| Room::new(config.rules.clone()));
+
+    if room.mirrored {
+        return Err("sala reservada para espectadores");
+    }
+
+    let seat = room
+        .seats
+        .iter()
+        .position(|seat| seat.is_none())
+        .ok_or("sala cheia")?;
+    let token = to_hex(&random_salt());
+
+    room.seats[seat] = Some(Seat {
+        token: token.clone(),
+        writer: Some(writer),
+        connection,
+        timeouts: 0,
+    });
+    room.names[seat] = single_word(name);
+    room.send(seat, &ServerMessage::Welcome {
+        seat,
+        token: token.clone(),
+    });
+    room.send_opponent_commitment(seat);
+    room.send_to_spectators(&ServerMessage::Players {
+        names: room.names.clone(),
+    });
+
+    state
+        .tokens
+        .insert(token, (room_name.to_string(), seat));
+
+    Ok(seat)
+}
+
+fn reconnect(
+    state: &mut ServerState,
+    token: &str,
+    writer: TcpStream,
+    connection: usize,
+) -> Option<(String, usize)> {
+    let (room_name, seat) = state.tokens.get(token)?.clone();
+    let room = state.rooms.get_mut(&room_name)?;
+
+    let current = room.seats[seat].as_mut()?;
+    if current.token != token {
+        return None;
+    }
+    current.writer = Some(writer);
+    current.connection = connection;
+    let fleet = room.fleets[seat].clone();
+
+    room.send(seat, &ServerMessage::Welcome {
+        seat,
+        token: token.to_string(),
+    });
+    if !fleet.is_empty() {
+        room.send(seat, &ServerMessage::YourFleet(fleet));
+    }
+    for message in room.history.clone() {
+        room.send(seat, &message);
+    }
+    room.send_opponent_commitment(seat);
+    if room.in_progress() {
+        room.send(seat, &ServerMessage::Turn {
+            seat: room.game.turn,
+        });
+    }
+    room.send(1 - seat, &ServerMessage::OpponentReconnected);
+
+    Some((room_name, seat))
+}
+
+fn place_fleet(room: &mut Room, seat: usize, placements: Vec<ShipPlacement>) {
+    if let Err(error) = room.game.place_fleet(seat, &placements) {
+        room.send(seat, &ServerMessage::Error(error.to_string()));
+        return;
+    }
+
+    room.fleets[seat] = placements;
+    room.send(seat, &ServerMessage::FleetAccepted);
+    room.reveal_fleet(seat, false);
+
+    if room.game.is_started() {
+        room.turn_started = Instant::now();
+        room.broadcast(ServerMessage::Start {
+            first: room.game.turn,
+        });
+        room.broadcast(ServerMessage::Turn {
+            seat: room.game.turn,
+        });
+    }
+}
+
+// o compromisso vale só se chegar antes do primeiro tiro, e uma vez por lugar
+fn commit(room: &mut Room, seat: usize, commitment: FleetCommitment) {
+    let error = if room.commitments[seat].is_some() {
+        Some("compromisso já enviado")
+    } else if room.game.is_started() {
+        Some("compromisso só antes da partida começar")
+    } else {
+        None
+    };
+
+    if let Some(error) = error {
+        room.send(seat, &ServerMessage::Error(error.to_string()));
+        return;
+    }
+
+    room.commitments[seat] = Some(commitment);
+    room.send(1 - seat, &ServerMessage::Commit { seat, commitment });
+}
+
+// a revelação só é repassada depois do fim, quando já não dá vantagem a ninguém
+fn reveal_commitment(room: &mut Room, seat: usize, reveal: FleetReveal) {
+    if room.game.winner.is_none() {
+        room.send(seat, &ServerMessage::Error("revelação só depois do fim da partida".to_string()));
+        return;
+    }
+    if room.reveals[seat].is_some() {
+        return;
+    }
+
+    room.reveals[seat] = Some(reveal.clone());
+    room.send(1 - seat, &ServerMessage::Reveal { seat, reveal });
+}
+
+fn fire(room: &mut Room, seat: usize, row: usize, column: usize) {
+    let outcome = match room.game.fire(seat, row, column) {
+        Ok(outcome) => outcome,
+        Err(error) => {
+            room.send(seat, &ServerMessage::Error(error.to_string()));
+            return;
+        }
+    };
+
+    if let Some(current) = room.seats[seat].as_mut() {
+        current.timeouts = 0;
+    }
+
+    room.broadcast(ServerMessage::Shot {
+        seat,
+        row,
+        column,
+        outcome,
+    });
+    finish_turn(room);
+}
+
+// habilidade no lugar do tiro: o aviso vai antes dos tiros dela ou da resposta do sonar
+fn use_ability(room: &mut Room, seat: usize, ability: Ability, row: usize, column: usize) {
+    let outcome = match room.game.use_ability(seat, ability, row, column) {
+        Ok(outcome) => outcome,
+        Err(error) => {
+            room.send(seat, &ServerMessage::Error(error.to_string()));
+            return;
+        }
+    };
+
+    if let Some(current) = room.seats[seat].as_mut() {
+        current.timeouts = 0;
+    }
+
+    room.broadcast(ServerMessage::Ability {
+        seat,
+        ability,
+        row,
+        column,
+    });
+    match outcome {
+        AbilityOutcome::Sonar(found) => room.broadcast(ServerMessage::Sonar {
+            seat,
+            row,
+            column,
+            found,
+        }),
+        AbilityOutcome::Shots(strikes) => {
+            for (row, column, outcome) in strikes {
+                room.broadcast(ServerMessage::Shot {
+                    seat,
+                    row,
+                    column,
+                    outcome,
+                });
+            }
+        }
+    }
+    finish_turn(room);
+}
+
+/**
+ * navio em movimento no lugar do tiro. os jogadores só ficam sabendo que o
+ * lado andou; a posição nova vai para a frota guardada da sala, que os
+ * espectadores recebem quando a visão deixa e todos no fim da partida
+ */
+fn move_ship(room: &mut Room, seat: usize, ship: usize, forward: bool) {
+    if let Err(error) = room.game.move_ship(seat, ship, forward) {
+        room.send(seat, &ServerMessage::Error(error.to_string()));
+        return;
+    }
+
+    if let Some(current) = room.seats[seat].as_mut() {
+        current.timeouts = 0;
+    }
+
+    // o canto do navio é a menor linha e a menor coluna das células, com ou sem formato
+    let moved = room.game.fleet_layout(seat).ships.get(ship).map(|placed| placed.cells.clone());
+    if let (Some(cells), Some(placement)) = (moved, room.fleets[seat].get_mut(ship)) {
+        placement.row = cells.iter().map(|(row, _)| *row).min().unwrap_or(placement.row);
+        placement.column = cells.iter().map(|(_, column)| *column).min().unwrap_or(placement.column);
+    }
+
+    room.broadcast(ServerMessage::Moved { seat });
+    room.reveal_fleet(seat, false);
+    finish_turn(room);
+}
+
+fn finish_turn(room: &mut Room) {
+    room.turn_started = Instant::now();
+
+    match room.game.winner {
+        Some(winner) => room.broadcast(ServerMessage::GameOver { winner }),
+        None => room.broadcast(ServerMessage::Turn {
+            seat: room.game.turn,
+        }),
+    }
+}
+
+// passa a vez de quem estourou o tempo; depois de max_timeouts seguidos a partida é perdida
+fn watch_turn_timeouts(state: Arc<Mutex<ServerState>>, config: ServerConfig) {
+    loop {
+        thread::sleep(Duration::from_millis(200));
+
+        let mut state = state.lock().unwrap();
+        let mut abandoned = Vec::new();
+
+        for (room_name, room) in state.rooms.iter_mut() {
+            if room.mirrored {
+                continue; // quem controla o tempo é quem joga a partida
+            }
+
+            if !room.in_progress() {
+                if room.is_abandoned() {
+                    abandoned.push(room_name.clone());
+                }
+                continue;
+            }
+
+            if room.turn_started.elapsed() < config.turn_timeout {
+                continue;
+            }
+
+            let seat = room.game.turn;
+            let timeouts = room.seats[seat].as_mut().map_or(config.max_timeouts, |current| {
+                current.timeouts += 1;
+                current.timeouts
+            });
+
+            room.broadcast(ServerMessage::Timeout { seat });
+
+            if timeouts >= config.max_timeouts {
+                room.game.forfeit(seat);
+            } else {
+                room.game.pass_turn();
+            }
+
+            finish_turn(room);
+        }
+
+        for room_name in abandoned {
+            remove_room(&mut state, &room_name);
+        }
+    }
+}
+
+// repassa a uma sala de espectadores cada lance de uma partida jogada fora do servidor
+pub struct RoomFeed {
+    state: Arc<Mutex<ServerState>>,
+    room: String,
+    pace: Duration,
+}
+
+impl RoomFeed {
+    fn with_room(&self, update: impl FnOnce(&mut Room)) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(room) = state.rooms.get_mut(&self.room) {
+            update(room);
+        }
+    }
+}
+
+impl MatchObserver for RoomFeed {
+    fn new_game(&mut self, names: [String; 2], rules: &Rules) {
+        self.with_room(|room| {
+            let spectators = std::mem::take(&mut room.spectators);
+
+            *room = Room::new(rules.clone());
+            room.mirrored = true;
+            room.names = names.map(|name| single_word(&name));
+            room.spectators = spectators;
+
+            let ids: Vec<usize> = room.spectators.keys().copied().collect();
+            for id in ids {
+                room.send_snapshot(id);
+            }
+        });
+    }
+
+    fn fleet(&mut self, seat: usize, placements: &[ShipPlacement]) {
+        self.with_room(|room| place_fleet(room, seat, placements.to_vec()));
+    }
+
+    fn shot(&mut self, seat: usize, row: usize, column: usize, _outcome: ShotOutcome) {
+        self.with_room(|room| fire(room, seat, row, column));
+        thread::sleep(self.pace);
+    }
+
+    fn ability(&mut self, seat: usize, ability: Ability, row: usize, column: usize, _outcome: &AbilityOutcome) {
+        self.with_room(|room| use_ability(room, seat, ability, row, column));
+        thread::sleep(self.pace);
+    }
+
+    fn moved(&mut self, seat: usize, ship: usize, forward: bool) {
+        self.with_room(|room| move_ship(room, seat, ship, forward));
+        thread::sleep(self.pace);
+    }
+
+    fn game_over(&mut self, report: &GameReport) {
+        self.with_room(|room| {
+            // partida decidida por W.O. não termina no motor da sala
+            if room.game.winner.is_none() {
+                room.game.forfeit(1 - report.winner);
+                room.broadcast(ServerMessage::GameOver {
+                    winner: report.winner,
+                });
+            }
+        });
+
+        // tempo para quem assiste ver o resultado antes da próxima partida
+        thread::sleep(self.pace * 10);
+    }
+}
@@ -0,0 +1,22 @@
+use std::io;
+
+use naval_battle_game_interface::{api::http::HttpApi, arg_value, logic::engine::Rules};
+
+/**
+ * api HTTP/JSON para bots, compilada só com a feature `http-api`
+ *
+ * uso: cargo run --features http-api --bin http_api -- [--bind 127.0.0.1:8080] [--rules <arquivo>]
+ */
+fn main() -> std::io::Result<()> {
+    env_logger::init();
+
+    let bind = arg_value("--bind").unwrap_or_else(|| "127.0.0.1:8080".to_string());
+    let rules = match arg_value("--rules") {
+        Some(path) => Rules::load(&path).map_err(io::Error::other)?,
+        None => Rules::default(),
+    };
+    let api = HttpApi::bind(&bind, rules)?;
+    println!("api http ouvindo em http://{}", api.local_addr()?);
+
+    api.run()
+}
@@ -0,0 +1,34 @@
+use std::{io, time::Duration};
+
+use naval_battle_game_interface::{
+    api::server::{GameServer, ServerConfig},
+    arg_value,
+    logic::engine::Rules,
+};
+
+/**
+ * servidor dedicado sem interface gráfica
+ *
+ * uso: server [--bind 127.0.0.1:7878] [--turn-timeout 30] [--max-timeouts 3] [--rules <arquivo>]
+ */
+fn main() -> std::io::Result<()> {
+    env_logger::init();
+
+    let bind = arg_value("--bind").unwrap_or_else(|| "127.0.0.1:7878".to_string());
+    let mut config = ServerConfig::default();
+
+    if let Some(seconds) = arg_value("--turn-timeout").and_then(|value| value.parse().ok()) {
+        config.turn_timeout = Duration::from_secs(seconds);
+    }
+    if let Some(max_timeouts) = arg_value("--max-timeouts").and_then(|value| value.parse().ok()) {
+        config.max_timeouts = max_timeouts;
+    }
+    if let Some(path) = arg_value("--rules") {
+        config.rules = Rules::load(&path).map_err(io::Error::other)?;
+    }
+
+    let server = GameServer::bind(&bind, config)?;
+    println!("servidor ouvindo em {}", server.local_addr()?);
+
+    server.run()
+}
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use naval_battle_game_interface::{
+    api::{
+        external_bot::{ExternalBot, DEFAULT_TIME_LIMIT},
+        server::{GameServer, ServerConfig},
+    },
+    arg_value,
+    logic::{
+        bots::{BotPlayer, HunterBot, RandomBot},
+        engine::Rules,
+        tournament::{round_robin, MatchObserver, NoObserver},
+    },
+};
+
+/*
+ * torneio headless entre bots: todos contra todos com as regras padrão
+ * ou as de --rules <arquivo>
+ *
+ * uso: tournament [--games 10] [--time-limit-ms 2000] [--rules <arquivo>] <jogador> <jogador> ...
+ * jogadores: random, hunter, parity ou exe:"<comando do motor externo>"
+ *
+ * com --watch <endereço> as partidas são transmitidas ao vivo na sala
+ * --watch-room (padrão "torneio") para quem entrar com SPECTATE, com
+ * --pace-ms de pausa entre os tiros
+ */
+fn main() {
+    env_logger::init();
+
+    let games = arg_value("--games")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(10);
+    let time_limit = arg_value("--time-limit-ms")
+        .and_then(|value| value.parse().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_TIME_LIMIT);
+    let rules = match arg_value("--rules").map(|path| Rules::load(&path)) {
+        Some(Ok(rules)) => rules,
+        Some(Err(error)) => {
+            eprintln!("regras inválidas: {error}");
+            std::process::exit(1);
+        }
+        None => Rules::default(),
+    };
+
+    let mut players: Vec<Box<dyn BotPlayer>> = Vec::new();
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--") {
+            args.next(); // valor da opção, já lido acima
+            continue;
+        }
+
+        match arg.as_str() {
+            "random" => players.push(Box::<RandomBot>::default()),
+            "hunter" => players.push(Box::<HunterBot>::default()),
+            "parity" => players.push(Box::new(HunterBot::with_parity())),
+            _ => match arg.strip_prefix("exe:") {
+                Some(command) => match ExternalBot::spawn(command, time_limit) {
+                    Ok(bot) => players.push(Box::new(bot)),
+                    Err(error) => {
+                        eprintln!("não foi possível iniciar '{command}': {error}");
+                        std::process::exit(1);
+                    }
+                },
+                None => {
+                    eprintln!("jogador desconhecido: {arg}");
+                    std::process::exit(1);
+                }
+            },
+        }
+    }
+
+    if players.len() < 2 {
+        eprintln!("uso: tournament [--games N] [--time-limit-ms MS] <jogador> <jogador> ...");
+        std::process::exit(1);
+    }
+
+    let mut observer: Box<dyn MatchObserver> = match arg_value("--watch") {
+        Some(address) => {
+            let room = arg_value("--watch-room").unwrap_or_else(|| "torneio".to_string());
+            let pace = arg_value("--pace-ms")
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_millis)
+                .unwrap_or(Duration::from_millis(300));
+
+            let config = ServerConfig {
+                rules: rules.clone(),
+                ..ServerConfig::default()
+            };
+            let server = match GameServer::bind(&address, config) {
+                Ok(server) => server,
+                Err(error) => {
+                    eprintln!("não foi possível abrir {address}: {error}");
+                    std::process::exit(1);
+                }
+            };
+            let feed = server.feed(&room, pace);
+
+            match server.spawn() {
+                Ok(addr) => println!("transmitindo em {addr}, sala {room}"),
+                Err(error) => {
+                    eprintln!("não foi possível transmitir: {error}");
+                    std::process::exit(1);
+                }
+            }
+
+            Box::new(feed)
+        }
+        None => Box::new(NoObserver),
+    };
+
+    let standings = round_robin(&mut players, &rules, games, observer.as_mut());
+
+    println!("{:<30} {:>8} {:>8} {:>6} {:>14}", "jogador", "vitórias", "derrotas", "W.O.", "tiros/vitória");
+    for standing in standings {
+        let average = standing
+            .average_shots_to_win()
+            .map(|average| format!("{average:.1}"))
+            .unwrap_or_else(|| "-".to_string());
+
+        println!(
+            "{:<30} {:>8} {:>8} {:>6} {:>14}",
+            standing.name, standing.wins, standing.losses, standing.forfeits, average
+        );
+    }
+}
@@ -0,0 +1,13 @@
+pub mod api;
+pub mod logic;
+pub mod ui;
+
+// valor do argumento de linha de comando logo depois de `flag`, ex: --connect 127.0.0.1:7878
+pub fn arg_value(flag: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .cloned()
+}
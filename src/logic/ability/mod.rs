@@ -0,0 +1,85 @@
+use super::shot::ShotOutcome;
+
+/**
+ * habilidades das regras avançadas: cada navio com uma delas pode usá-la
+ * uma vez, enquanto não for afundado, no lugar do tiro da vez. a geometria
+ * e a escolha das células atingidas ficam aqui para o motor de regras e o
+ * cliente local resolverem do mesmo jeito
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ability {
+    Airstrike, // três células em linha, centradas no alvo
+    Sonar,     // diz se há navio numa área 3x3, sem atingir nada
+    Torpedo,   // corre a linha do alvo a partir da coluna 0 e explode no primeiro navio
+}
+
+// o que uma habilidade revelou
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AbilityOutcome {
+    Shots(Vec<(usize, usize, ShotOutcome)>), // na ordem em que caíram
+    Sonar(bool),
+}
+
+impl Ability {
+    pub const ALL: [Ability; 3] = [Ability::Airstrike, Ability::Sonar, Ability::Torpedo];
+
+    // nome usado no arquivo de regras
+    pub fn name(self) -> &'static str {
+        match self {
+            Ability::Airstrike => "airstrike",
+            Ability::Sonar => "sonar",
+            Ability::Torpedo => "torpedo",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Ability> {
+        Ability::ALL.into_iter().find(|ability| ability.name() == name)
+    }
+
+    // células cobertas com o alvo em (linha, coluna), cortadas nas bordas do lado
+    pub fn area(self, row: usize, column: usize, rows: usize, columns: usize) -> Vec<(usize, usize)> {
+        let around = |center: usize, size: usize| center.saturating_sub(1)..(center + 2).min(size);
+
+        match self {
+            Ability::Airstrike => around(column, columns).map(|column| (row, column)).collect(),
+            Ability::Sonar => around(row, rows)
+                .flat_map(|row| around(column, columns).map(move |column| (row, column)))
+                .collect(),
+            Ability::Torpedo => (0..columns).map(|column| (row, column)).collect(),
+        }
+    }
+
+    /**
+     * células da área que levam tiro: o ataque aéreo cai em todas as ainda
+     * não atacadas. o torpedo só explode onde para: na primeira célula não
+     * atacada com navio, ou na última da linha se não achar nenhum; as células
+     * por onde passou continuam sem tiro. o sonar não atira
+     */
+    pub fn strikes(
+        self,
+        area: &[(usize, usize)],
+        fired: impl Fn(&(usize, usize)) -> bool,
+        occupied: impl Fn(&(usize, usize)) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let open = area.iter().copied().filter(|cell| !fired(cell));
+
+        match self {
+            Ability::Airstrike => open.collect(),
+            Ability::Sonar => Vec::new(),
+            Ability::Torpedo => {
+                let path: Vec<(usize, usize)> = open.collect();
+                let stop = path.iter().copied().find(|cell| occupied(cell)).or(path.last().copied());
+                stop.into_iter().collect()
+            }
+        }
+    }
+
+    // o sonar só acusa pedaços de navio que ainda não levaram tiro
+    pub fn detects(
+        area: &[(usize, usize)],
+        fired: impl Fn(&(usize, usize)) -> bool,
+        occupied: impl Fn(&(usize, usize)) -> bool,
+    ) -> bool {
+        area.iter().any(|cell| occupied(cell) && !fired(cell))
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, seq::IteratorRandom, Rng, SeedableRng};
+
+use super::{
+    ability::Ability,
+    engine::{random_fleet, Rules, ShipPlacement},
+    shot::ShotOutcome,
+};
+
+/*
+ * jogadores sem interface: os bots embutidos e, pelo mesmo trait, os
+ * motores externos (api::external_bot). o torneio headless só conhece isso
+ */
+
+pub trait BotPlayer {
+    fn name(&self) -> String;
+    fn new_game(&mut self, rules: &Rules) -> Result<(), String>;
+    fn place(&mut self, rules: &Rules) -> Result<Vec<ShipPlacement>, String>;
+    fn fire(&mut self) -> Result<(usize, usize), String>;
+    // resultado do próprio tiro; `sunk` traz o tamanho do navio afundado
+    fn result(&mut self, row: usize, column: usize, outcome: ShotOutcome, sunk: Option<usize>);
+    fn game_over(&mut self, won: bool);
+
+    // nas regras avançadas: habilidade e alvo no lugar do próximo tiro, ou None para só atirar.
+    // os tiros da habilidade voltam um a um por `result`
+    fn ability(&mut self, _available: &[Ability]) -> Option<(Ability, usize, usize)> {
+        None
+    }
+
+    // resposta do sonar usado com o alvo em (linha, coluna)
+    fn sonar(&mut self, _row: usize, _column: usize, _found: bool) {}
+
+    // com navios em movimento: um dos `moves` (navio na ordem do place, true para frente)
+    // no lugar do próximo tiro, ou None para atirar
+    fn maneuver(&mut self, _moves: &[(usize, bool)]) -> Option<(usize, bool)> {
+        None
+    }
+
+    // o adversário andou com um navio: os tiros na água dados até aqui podem estar velhos
+    fn opponent_moved(&mut self) {}
+
+    /**
+     * tiro sem travar quem chama, para o jogo perguntar a cada frame: None
+     * enquanto a resposta não chegou. os bots embutidos respondem na hora
+     */
+    fn poll_fire(&mut self) -> Option<Result<(usize, usize), String>> {
+        Some(self.fire())
+    }
+
+    // um tiro pedido por `poll_fire` ainda espera resposta
+    fn is_thinking(&self) -> bool {
+        false
+    }
+}
+
+// atira em qualquer célula ainda não atacada, como o bot_turn do jogo
+pub struct RandomBot {
+    rng: StdRng, // Send, para o bot poder viver num recurso do bevy
+    rows: usize,
+    columns: usize,
+    fired: HashSet<(usize, usize)>,
+    misses: Vec<(usize, usize)>, // tiros na água, esquecidos quando o adversário anda
+}
+
+impl Default for RandomBot {
+    fn default() -> Self {
+        RandomBot {
+            rng: StdRng::from_entropy(),
+            rows: 0,
+            columns: 0,
+            fired: HashSet::new(),
+            misses: Vec::new(),
+        }
+    }
+}
+
+impl RandomBot {
+    // tiro aleatório entre as células ainda não atacadas que passam no filtro
+    fn fire_where(&mut self, filter: impl Fn(&(usize, usize)) -> bool) -> Result<(usize, usize), String> {
+        let columns = self.columns;
+        let target = (0..self.rows)
+            .flat_map(|row| (0..columns).map(move |column| (row, column)))
+            .filter(|cell| !self.fired.contains(cell) && filter(cell))
+            .choose(&mut self.rng)
+            .ok_or("nenhuma célula disponível")?;
+
+        self.fired.insert(target);
+        Ok(target)
+    }
+}
+
+impl BotPlayer for RandomBot {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    // ilha não aceita tiro e mina custa a vez: nenhum bot mira nelas
+    fn new_game(&mut self, rules: &Rules) -> Result<(), String> {
+        self.rows = rules.rows;
+        self.columns = rules.columns;
+        self.fired.clear();
+        self.fired
+            .extend(rules.terrain.obstacles().map(|(row, column, _)| (row, column)));
+        self.misses.clear();
+        Ok(())
+    }
+
+    fn place(&mut self, rules: &Rules) -> Result<Vec<ShipPlacement>, String> {
+        random_fleet(rules, &mut self.rng)
+    }
+
+    fn fire(&mut self) -> Result<(usize, usize), String> {
+        self.fire_where(|_| true)
+    }
+
+    // tiros de habilidades não passam por fire
+    fn result(&mut self, row: usize, column: usize, outcome: ShotOutcome, _sunk: Option<usize>) {
+        self.fired.insert((row, column));
+        if outcome == ShotOutcome::Miss {
+            self.misses.push((row, column));
+        }
+    }
+
+    // acertos continuam valendo, porque navio avariado não anda
+    fn opponent_moved(&mut self) {
+        for cell in self.misses.drain(..) {
+            self.fired.remove(&cell);
+        }
+    }
+
+    fn game_over(&mut self, _won: bool) {}
+}
+
+/**
+ * caça e alvo: atira aleatório até acertar e então persegue as
+ * células vizinhas dos acertos até o navio afundar. com `parity` a caça
+ * só atira nas casas de uma cor do xadrez, onde todo navio de 2+ casas
+ * precisa passar. nas regras avançadas usa as habilidades durante a caça:
+ * sonar para achar uma área com navio, torpedo e ataque aéreo para varrer.
+ * com navios em movimento esquece os tiros na água quando o adversário anda
+ */
+#[derive(Default)]
+pub struct HunterBot {
+    random: RandomBot,
+    hits: Vec<(usize, usize)>, // acertos em navios ainda não afundados
+    detected: Vec<(usize, usize)>, // células de uma área onde o sonar achou navio
+    parity: bool,
+}
+
+impl HunterBot {
+    pub fn with_parity() -> Self {
+        HunterBot {
+            parity: true,
+            ..Default::default()
+        }
+    }
+
+    fn neighbours(&self, (row, column): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+
+        if row > 0 {
+            cells.push((row - 1, column));
+        }
+        if row + 1 < self.random.rows {
+            cells.push((row + 1, column));
+        }
+        if column > 0 {
+            cells.push((row, column - 1));
+        }
+        if column + 1 < self.random.columns {
+            cells.push((row, column + 1));
+        }
+
+        cells
+    }
+
+    fn is_open(&self, cell: &(usize, usize)) -> bool {
+        !self.random.fired.contains(cell)
+    }
+
+    // alvo cuja área da habilidade tem mais células ainda não atacadas
+    fn best_target(&mut self, ability: Ability) -> Option<(usize, usize)> {
+        let (rows, columns) = (self.random.rows, self.random.columns);
+        let open = |cell: (usize, usize)| {
+            ability
+                .area(cell.0, cell.1, rows, columns)
+                .iter()
+                .filter(|cell| self.is_open(cell))
+                .count()
+        };
+
+        let scored: Vec<((usize, usize), usize)> = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (row, column)))
+            .map(|cell| (cell, open(cell)))
+            .collect();
+        let best = scored.iter().map(|(_, open)| *open).max().filter(|best| *best > 0)?;
+
+        scored
+            .into_iter()
+            .filter(|(_, open)| *open == best)
+            .map(|(cell, _)| cell)
+            .choose(&mut self.random.rng)
+    }
+}
+
+impl BotPlayer for HunterBot {
+    fn name(&self) -> String {
+        if self.parity {
+            "parity".to_string()
+        } else {
+            "hunter".to_string()
+        }
+    }
+
+    fn new_game(&mut self, rules: &Rules) -> Result<(), String> {
+        self.hits.clear();
+        self.detected.clear();
+        self.random.new_game(rules)
+    }
+
+    fn place(&mut self, rules: &Rules) -> Result<Vec<ShipPlacement>, String> {
+        self.random.place(rules)
+    }
+
+    fn fire(&mut self) -> Result<(usize, usize), String> {
+        let candidates: Vec<(usize, usize)> = self
+            .hits
+            .iter()
+            .flat_map(|hit| self.neighbours(*hit))
+            .filter(|cell| !self.random.fired.contains(cell))
+            .collect();
+
+        // sem acertos a perseguir, a área que o sonar apontou vem antes da caça
+        self.detected.retain(|cell| !self.random.fired.contains(cell));
+        if candidates.is_empty() && !self.detected.is_empty() {
+            let target = self.detected.remove(self.random.rng.gen_range(0..self.detected.len()));
+            self.random.fired.insert(target);
+            return Ok(target);
+        }
+
+        if candidates.is_empty() {
+            // sem casas de xadrez sobrando restam só submarinos: qualquer célula serve
+            if self.parity {
+                if let Ok(target) = self.random.fire_where(|(row, column)| (row + column).is_multiple_of(2)) {
+                    return Ok(target);
+                }
+            }
+            return self.random.fire();
+        }
+
+        let target = candidates[self.random.rng.gen_range(0..candidates.len())];
+        self.random.fired.insert(target);
+        Ok(target)
+    }
+
+    fn result(&mut self, row: usize, column: usize, outcome: ShotOutcome, sunk: Option<usize>) {
+        self.random.fired.insert((row, column));
+
+        match outcome {
+            ShotOutcome::Miss => self.random.misses.push((row, column)),
+            ShotOutcome::Hit => self.hits.push((row, column)),
+            ShotOutcome::Sunk => {
+                // sem saber quais células eram do navio, descarta os acertos mais recentes
+                self.hits.push((row, column));
+                let length = sunk.unwrap_or(1).min(self.hits.len());
+                self.hits.truncate(self.hits.len() - length);
+            }
+        }
+    }
+
+    fn game_over(&mut self, _won: bool) {}
+
+    // só na caça: com um navio já achado, o tiro comum basta
+    fn ability(&mut self, available: &[Ability]) -> Option<(Ability, usize, usize)> {
+        if !self.hits.is_empty() {
+            return None;
+        }
+
+        let ability = if self.detected.is_empty() && available.contains(&Ability::Sonar) {
+            Ability::Sonar
+        } else {
+            [Ability::Torpedo, Ability::Airstrike]
+                .into_iter()
+                .find(|ability| available.contains(ability))?
+        };
+
+        // o torpedo segue a linha de uma área detectada, se houver
+        if ability == Ability::Torpedo {
+            if let Some((row, _)) = self.detected.first() {
+                return Some((ability, *row, 0));
+            }
+        }
+
+        let (row, column) = self.best_target(ability)?;
+        Some((ability, row, column))
+    }
+
+    // área sem navio não merece tiro; área com navio vira prioridade da caça
+    fn sonar(&mut self, row: usize, column: usize, found: bool) {
+        let area = Ability::Sonar.area(row, column, self.random.rows, self.random.columns);
+        let open: Vec<(usize, usize)> = area.into_iter().filter(|cell| self.is_open(cell)).collect();
+
+        if found {
+            self.detected.extend(open);
+        } else {
+            // como tiros na água: deixam de valer se o adversário andar
+            self.random.fired.extend(open.iter().copied());
+            self.random.misses.extend(open);
+        }
+    }
+
+    // só na caça e de vez em quando: o navio que anda deixa velhos os tiros do adversário em volta dele
+    fn maneuver(&mut self, moves: &[(usize, bool)]) -> Option<(usize, bool)> {
+        if !self.hits.is_empty() || !self.random.rng.gen_ratio(1, 6) {
+            return None;
+        }
+
+        moves.iter().copied().choose(&mut self.random.rng)
+    }
+
+    // a área que o sonar apontou também pode ter mudado
+    fn opponent_moved(&mut self) {
+        self.random.opponent_moved();
+        self.detected.clear();
+    }
+}
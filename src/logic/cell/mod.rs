@@ -1,71 +1,137 @@
 use bevy::prelude::*;
 
-use crate::ui::components::{board::GameState, ships::Ship};
+use crate::{
+    logic::{shot::ShotOutcome, terrain::Terrain},
+    ui::components::{board::{BoardIndex, GameState, ShotGrid, ShotState}, settings::RuleVariant, ships::Fleet, theme::Palette},
+};
 
 #[derive(Component, Debug)]
 pub struct Cell {
     pub row: usize,
     pub column: usize,
-    pub marked: bool,
+    pub side: Side, // dono da metade do tabuleiro onde a célula está
+    pub terrain: Terrain,
 }
 
-#[derive(Component, Debug, PartialEq)]
-pub enum CellSide {
+/**
+ * lado da partida: dono de células e navios, quem atira e quem vence.
+ * o índice é o mesmo lugar usado no servidor, o jogador local é o 0
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
     Player,
     Enemy,
 }
 
+impl Side {
+    pub const ALL: [Side; 2] = [Side::Player, Side::Enemy];
+
+    pub fn opponent(&self) -> Side {
+        match self {
+            Side::Player => Side::Enemy,
+            Side::Enemy => Side::Player,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            Side::Player => 0,
+            Side::Enemy => 1,
+        }
+    }
+}
+
 impl Cell {
     pub fn mark(
-        &mut self,
+        &self,
         sprite: &mut Sprite,
-        ships_query: &mut Query<(Entity, &mut Ship)>,
+        fleet: &mut Fleet,
         entity_alvo: Entity,
-        clicked_cells: &mut Vec<Entity>, //referencia do vetor de celulas clicadas
+        grid: &mut ShotGrid, //o que cada lado já sabe do tabuleiro
+        index: &BoardIndex,
         game_state: &mut ResMut<GameState>,
-        
-    ) {
-        clicked_cells.push(entity_alvo);
+        palette: &Palette,
+    ) -> ShotOutcome {
+        let Some((side, row, column)) = index.position(entity_alvo) else {
+            return ShotOutcome::Miss;
+        };
+        // marca como acerto antes de conferir o navio; tiro na água é corrigido no fim
+        grid.set(&side, row, column, ShotState::Hit);
+
+        // com navios em movimento pode haver tiro na água velho embaixo do navio; só acerto conta
+        let known = |grid: &ShotGrid, cell: &Entity| {
+            index.position(*cell).is_some_and(|(side, row, column)| {
+                matches!(grid.get(&side, row, column), ShotState::Hit | ShotState::Sunk)
+            })
+        };
 
         let mut validator: bool = false;
+        let mut sunk: bool = false;
+        let mut sunk_side = None;
 
-        for (_entity, mut ship) in ships_query.iter_mut() {
-            if ship.cells.contains(&entity_alvo) {
-                validator = true;
-                let all_cells_clicked = ship.cells.iter().all(|cell| clicked_cells.contains(cell));
-
-                if all_cells_clicked && game_state.is_player_turn {
-                    game_state.player_score += 1;
-                    ship.sunk = true;
-                } else if all_cells_clicked && !game_state.is_player_turn {
-                    game_state.bot_score += 1;
-                    ship.sunk = true;
+        // o navio da célula vem direto do índice, sem procurar entre todos (revelado ou não)
+        let alvo = index.ship_at(entity_alvo);
+        if let Some(ship) = alvo.and_then(|ship| fleet.get_mut(ship)) {
+            validator = true;
+            let all_cells_clicked = ship.cells.iter().all(|cell| known(grid, cell));
+            sunk = all_cells_clicked;
+
+            // o navio inteiro passa a constar como afundado
+            if sunk {
+                for (side, row, column) in ship.cells.iter().filter_map(|cell| index.position(*cell)) {
+                    grid.set(&side, row, column, ShotState::Sunk);
                 }
+            }
 
-                break;
+            //contabiliza o navio afundado
+            if all_cells_clicked {
+                game_state.record_sunk(ship.side);
+                ship.sunk = true;
+                sunk_side = Some(ship.side);
             }
         }
 
-        if validator && game_state.is_player_turn {
-            //se o jogador acertar
-            sprite.color = Color::srgb(0.0, 1.0, 0.0);
-        } else if validator && !game_state.is_player_turn {
-            // se o bot acertar
-            sprite.color = Color::srgb(1.0, 0.0, 0.0);
+        //validar fim de game: a frota é a dos navios posicionados, sem contagem separada
+        if let Some(side) = sunk_side {
+            let fleet_sunk = fleet
+                .iter()
+                .filter(|(_, ship)| ship.side == side)
+                .all(|(_, ship)| ship.sunk);
+
+            if fleet_sunk && game_state.winner.is_none() {
+                game_state.winner = Some(side.opponent());
+            }
+        }
+
+        let outcome = if sunk {
+            ShotOutcome::Sunk
+        } else if validator {
+            ShotOutcome::Hit
         } else {
-            sprite.color = Color::srgb(0.28, 0.28, 0.28);
+            ShotOutcome::Miss
+        };
+
+        if outcome == ShotOutcome::Miss {
+            grid.set(&side, row, column, ShotState::Miss);
         }
+        self.show_outcome(sprite, outcome, game_state.shooter(), palette);
 
-        //validar fim de game
-        if game_state.total_ships_bot == game_state.player_score {
-            //jogador ganha
-            game_state.winner = Some(0);
-        } else if game_state.total_ships_player == game_state.bot_score {
-            //bot ganha
-            game_state.winner = Some(1);
+        // quem cai numa mina perde a próxima vez
+        if self.terrain == Terrain::Mine {
+            game_state.penalty = Some(game_state.shooter());
         }
 
-        game_state.is_player_turn = !game_state.is_player_turn; //alternar jogada
-        self.marked = true;
+        // na variante "acertou, joga de novo" só o tiro na água passa a vez
+        if game_state.variant != RuleVariant::HitAgain || outcome == ShotOutcome::Miss {
+            game_state.pass_turn(); //alternar jogada
+        }
+
+        outcome
+    }
+
+    // pinta a célula com o resultado do tiro, seja ele calculado aqui ou vindo do servidor
+    pub fn show_outcome(&self, sprite: &mut Sprite, outcome: ShotOutcome, shooter: Side, palette: &Palette) {
+        sprite.color = palette.outcome(outcome, shooter);
     }
 }
+
@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use super::{
+    ability::Ability,
+    engine::{Match, Rules, ShipPlacement},
+    shape::Shape,
+    shot::ShotOutcome,
+};
+
+pub const SALT_SIZE: usize = 16;
+
+// navio posicionado, com as células ocupadas em (linha, coluna)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlacedShip {
+    pub name: String,
+    pub cells: Vec<(usize, usize)>,
+}
+
+// disposição completa da frota de um lado, é isso que entra no compromisso
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FleetLayout {
+    pub ships: Vec<PlacedShip>,
+}
+
+impl FleetLayout {
+    /**
+     * representação canônica da frota: a ordem dos navios e das células
+     * não muda o hash, só a posição de fato
+     */
+    pub fn canonical(&self) -> String {
+        let mut ships: Vec<(String, Vec<(usize, usize)>)> = self
+            .ships
+            .iter()
+            .map(|ship| {
+                let mut cells = ship.cells.clone();
+                cells.sort();
+                (ship.name.clone(), cells)
+            })
+            .collect();
+
+        ships.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+        ships
+            .iter()
+            .map(|(name, cells)| {
+                let cells: Vec<String> = cells
+                    .iter()
+                    .map(|(row, column)| format!("{row},{column}"))
+                    .collect();
+                format!("{name}:{}", cells.join(";"))
+            })
+            .collect::<Vec<String>>()
+            .join("|")
+    }
+
+    pub fn ship_at(&self, row: usize, column: usize) -> Option<usize> {
+        self.ships
+            .iter()
+            .position(|ship| ship.cells.contains(&(row, column)))
+    }
+
+    // inverso de `canonical`, como a frota viaja no REVEAL do protocolo
+    pub fn parse(value: &str) -> Result<FleetLayout, String> {
+        let invalid = || format!("frota inválida: {value}");
+
+        if value.is_empty() {
+            return Ok(FleetLayout::default());
+        }
+
+        let ships = value
+            .split('|')
+            .map(|ship| {
+                let (name, cells) = ship.split_once(':').ok_or_else(invalid)?;
+                let cells = cells
+                    .split(';')
+                    .map(|cell| {
+                        let (row, column) = cell.split_once(',').ok_or_else(invalid)?;
+                        Ok((
+                            row.parse().map_err(|_| invalid())?,
+                            column.parse().map_err(|_| invalid())?,
+                        ))
+                    })
+                    .collect::<Result<Vec<(usize, usize)>, String>>()?;
+
+                Ok(PlacedShip {
+                    name: name.to_string(),
+                    cells,
+                })
+            })
+            .collect::<Result<Vec<PlacedShip>, String>>()?;
+
+        Ok(FleetLayout { ships })
+    }
+
+    // a frota como posicionamentos do motor, com o canto de cada navio no menor retângulo
+    pub fn placements(&self) -> Result<Vec<ShipPlacement>, String> {
+        self.ships
+            .iter()
+            .map(|ship| {
+                let shape = Shape::new(&ship.cells)?;
+                let row = ship.cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+                let column = ship.cells.iter().map(|(_, column)| *column).min().unwrap_or(0);
+
+                Ok(ShipPlacement::from_shape(&ship.name, row, column, &shape))
+            })
+            .collect()
+    }
+
+    /**
+     * a frota revelada precisa valer nas regras da partida: mesmos navios,
+     * dentro do tabuleiro, sem sobreposição. quem confere é o próprio motor
+     */
+    pub fn fits(&self, rules: &Rules) -> bool {
+        self.placements().is_ok_and(|placements| {
+            Match::new(rules.clone()).place_fleet(0, &placements).is_ok()
+        })
+    }
+}
+
+// o que cada lado revela no fim da partida
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FleetReveal {
+    pub layout: FleetLayout,
+    pub salt: [u8; SALT_SIZE],
+}
+
+// hash sha-256 de (salt || frota canônica), enviado antes do primeiro tiro
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FleetCommitment(pub [u8; 32]);
+
+impl FleetCommitment {
+    pub fn new(layout: &FleetLayout, salt: &[u8; SALT_SIZE]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(salt);
+        hasher.update(layout.canonical().as_bytes());
+
+        FleetCommitment(hasher.finalize().into())
+    }
+
+    pub fn verify(&self, reveal: &FleetReveal) -> bool {
+        *self == FleetCommitment::new(&reveal.layout, &reveal.salt)
+    }
+
+    pub fn to_hex(self) -> String {
+        to_hex(&self.0)
+    }
+
+    pub fn from_hex(value: &str) -> Option<Self> {
+        from_hex(value)?.try_into().ok().map(FleetCommitment)
+    }
+}
+
+pub fn random_salt() -> [u8; SALT_SIZE] {
+    let mut salt = [0u8; SALT_SIZE];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// disparo feito contra a frota auditada, com o resultado que o dono da frota anunciou
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReportedShot {
+    pub row: usize,
+    pub column: usize,
+    pub reported: ShotOutcome,
+}
+
+// sonar usado contra a frota auditada; `after` é quantos disparos vieram antes dele
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReportedSonar {
+    pub after: usize,
+    pub row: usize,
+    pub column: usize,
+    pub found: bool,
+}
+
+// resposta que não bate com a frota revelada
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lie {
+    pub shot_index: usize,
+    pub reported: ShotOutcome,
+    pub actual: ShotOutcome,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditReport {
+    pub commitment_valid: bool,
+    pub fleet_valid: bool, // a frota revelada obedece às regras da partida
+    pub lies: Vec<Lie>,
+    pub sonar_lies: Vec<usize>, // posição em `sonars` de cada sonar com a resposta trocada
+}
+
+impl AuditReport {
+    pub fn is_honest(&self) -> bool {
+        self.commitment_valid && self.fleet_valid && self.lies.is_empty() && self.sonar_lies.is_empty()
+    }
+}
+
+/**
+ * confere a frota revelada contra o compromisso enviado no início e
+ * refaz cada disparo na ordem, comparando o resultado real com o anunciado.
+ * o sonar é refeito com os disparos que vieram antes dele, como o jogo
+ * responde. a frota também passa pelas regras da partida, para ninguém
+ * esconder um navio ou revelar uma frota menor do que a combinada
+ */
+pub fn audit(
+    rules: &Rules,
+    commitment: &FleetCommitment,
+    reveal: &FleetReveal,
+    shots: &[ReportedShot],
+    sonars: &[ReportedSonar],
+) -> AuditReport {
+    let commitment_valid = commitment.verify(reveal);
+    let fleet_valid = reveal.layout.fits(rules);
+
+    let mut hits: HashSet<(usize, usize)> = HashSet::new();
+    let mut lies = Vec::new();
+
+    for (shot_index, shot) in shots.iter().enumerate() {
+        let actual = match reveal.layout.ship_at(shot.row, shot.column) {
+            Some(ship_index) => {
+                hits.insert((shot.row, shot.column));
+
+                let ship = &reveal.layout.ships[ship_index];
+                if ship.cells.iter().all(|cell| hits.contains(cell)) {
+                    ShotOutcome::Sunk
+                } else {
+                    ShotOutcome::Hit
+                }
+            }
+            None => ShotOutcome::Miss,
+        };
+
+        if actual != shot.reported {
+            lies.push(Lie {
+                shot_index,
+                reported: shot.reported,
+                actual,
+            });
+        }
+    }
+
+    let sonar_lies = sonars
+        .iter()
+        .enumerate()
+        .filter(|(_, sonar)| {
+            let fired = |cell: &(usize, usize)| {
+                shots[..sonar.after.min(shots.len())]
+                    .iter()
+                    .any(|shot| (shot.row, shot.column) == *cell)
+            };
+            let occupied = |cell: &(usize, usize)| reveal.layout.ship_at(cell.0, cell.1).is_some();
+            let area = Ability::Sonar.area(sonar.row, sonar.column, rules.rows, rules.columns);
+
+            Ability::detects(&area, fired, occupied) != sonar.found
+        })
+        .map(|(position, _)| position)
+        .collect();
+
+    AuditReport {
+        commitment_valid,
+        fleet_valid,
+        lies,
+        sonar_lies,
+    }
+}
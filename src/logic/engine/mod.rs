@@ -0,0 +1,724 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+};
+
+use rand::Rng;
+
+use super::{
+    ability::{Ability, AbilityOutcome},
+    commitment::{FleetLayout, PlacedShip},
+    shape::Shape,
+    shot::ShotOutcome,
+    terrain::{Terrain, TerrainMap},
+};
+
+/*
+ * motor de regras sem dependência do bevy: é o mesmo usado pelo servidor
+ * dedicado, pelo cliente em rede e por qualquer outro lado que precise
+ * validar uma partida
+ */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShipRule {
+    pub name: String,
+    pub length: usize, // quantidade de células, também nos navios com formato
+    pub shape: Shape,
+    pub ability: Option<Ability>, // só nas regras avançadas
+}
+
+impl ShipRule {
+    pub fn line(name: &str, length: usize) -> Self {
+        ShipRule {
+            name: name.to_string(),
+            length,
+            shape: Shape::line(length),
+            ability: None,
+        }
+    }
+
+    pub fn shaped(name: &str, shape: Shape) -> Self {
+        ShipRule {
+            name: name.to_string(),
+            length: shape.len(),
+            shape,
+            ability: None,
+        }
+    }
+
+    pub fn with_ability(mut self, ability: Option<Ability>) -> Self {
+        self.ability = ability;
+        self
+    }
+
+    // navio posicionado com este nome precisa ter o mesmo formato, em qualquer giro ou espelho
+    fn accepts(&self, placement: &ShipPlacement) -> bool {
+        match &placement.shape {
+            Some(shape) => self.shape.matches(shape),
+            None => self.shape.is_line() && self.length == placement.length,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    pub rows: usize,
+    pub columns: usize,
+    pub fleet: Vec<ShipRule>,
+    pub terrain: TerrainMap, // ilhas e minas, iguais nos dois lados
+    pub moving: bool,        // navios sem dano podem andar no lugar do tiro
+}
+
+impl Default for Rules {
+    // mesmo tabuleiro e frota do cliente: metade de 10x10 para cada lado
+    fn default() -> Self {
+        Rules {
+            rows: 5,
+            columns: 10,
+            fleet: vec![
+                ShipRule::line("AircraftCarrier", 5),
+                ShipRule::line("LargeBattleship", 4),
+                ShipRule::line("Battleship", 3),
+                ShipRule::line("Destroyer", 2),
+                ShipRule::line("Submarine", 1),
+            ],
+            terrain: TerrainMap::default(),
+            moving: false,
+        }
+    }
+}
+
+impl Rules {
+    /**
+     * arquivo de regras: uma linha "board <linhas> <colunas>" com o tamanho
+     * de um lado e uma linha "ship <nome> <formato>" por navio, em que o
+     * formato é o comprimento do navio reto ou as células do poliominó
+     * ("0,0;1,0;1,1"), seguido da habilidade do navio nas regras avançadas
+     * (airstrike, sonar ou torpedo). linhas "map" desenham ilhas e minas como
+     * em TerrainMap::parse e a linha "moving" deixa os navios andarem. linhas
+     * vazias e começando com # são ignoradas
+     */
+    pub fn parse(text: &str) -> Result<Rules, String> {
+        let mut rules = Rules {
+            rows: 0,
+            columns: 0,
+            fleet: Vec::new(),
+            terrain: TerrainMap::default(),
+            moving: false,
+        };
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words[..] {
+                ["board", rows, columns] => {
+                    rules.rows = rows.parse().map_err(|_| format!("linhas inválidas: {line}"))?;
+                    rules.columns = columns.parse().map_err(|_| format!("colunas inválidas: {line}"))?;
+                }
+                ["ship", name, shape, ref ability @ ..] if ability.len() <= 1 => {
+                    let ship = match shape.parse::<usize>() {
+                        Ok(length) if length > 0 => ShipRule::line(name, length),
+                        Ok(_) => return Err(format!("navio vazio: {line}")),
+                        Err(_) => ShipRule::shaped(name, Shape::parse(shape)?),
+                    };
+                    let ability = match ability.first() {
+                        Some(ability) => {
+                            Some(Ability::from_name(ability).ok_or_else(|| format!("habilidade inválida: {line}"))?)
+                        }
+                        None => None,
+                    };
+                    rules.fleet.push(ship.with_ability(ability));
+                }
+                ["map", cells] => rules.terrain.push_row(TerrainMap::parse_row(cells)?),
+                ["moving"] => rules.moving = true,
+                _ => return Err(format!("linha inválida: {line}")),
+            }
+        }
+
+        if rules.rows == 0 || rules.columns == 0 {
+            return Err("tamanho do tabuleiro faltando".to_string());
+        }
+        if rules.fleet.is_empty() {
+            return Err("regras sem navios".to_string());
+        }
+        rules.check_fits()?;
+
+        Ok(rules)
+    }
+
+    /**
+     * o que dá para saber sem sortear: cada navio cabe no lado em algum giro
+     * e a água que sobra tem células para a frota inteira. um encaixe que
+     * ainda assim não existe fica para random_fleet, que desiste depois de
+     * algumas tentativas
+     */
+    pub fn check_fits(&self) -> Result<(), String> {
+        for ship in &self.fleet {
+            let fits = ship.shape.orientations().iter().any(|shape| {
+                let (rows, columns) = shape.size();
+                rows <= self.rows && columns <= self.columns
+            });
+            if !fits {
+                return Err(format!("navio não cabe no tabuleiro: {}", ship.name));
+            }
+        }
+
+        let water = (0..self.rows)
+            .flat_map(|row| (0..self.columns).map(move |column| (row, column)))
+            .filter(|(row, column)| self.terrain.is_water(*row, *column))
+            .count();
+        let needed: usize = self.fleet.iter().map(|ship| ship.length).sum();
+        if needed > water {
+            return Err(format!("a frota precisa de {needed} células e só há {water} de água"));
+        }
+
+        Ok(())
+    }
+
+    pub fn load(path: &str) -> Result<Rules, String> {
+        let text = fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?;
+        Rules::parse(&text).map_err(|error| format!("{path}: {error}"))
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![format!("board {} {}", self.rows, self.columns)];
+
+        for ship in &self.fleet {
+            let mut line = if ship.shape.is_line() {
+                format!("ship {} {}", ship.name, ship.length)
+            } else {
+                format!("ship {} {}", ship.name, ship.shape)
+            };
+            if let Some(ability) = ship.ability {
+                line = format!("{line} {}", ability.name());
+            }
+            lines.push(line);
+        }
+        if self.moving {
+            lines.push("moving".to_string());
+        }
+
+        lines.join("\n") + "\n" + &self.terrain.to_string()
+    }
+}
+
+// navio no tabuleiro de um lado. `shape` só existe nos navios que não são retos,
+// já no giro e espelho escolhidos; os retos continuam com comprimento e direção
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShipPlacement {
+    pub name: String,
+    pub row: usize,
+    pub column: usize,
+    pub length: usize,
+    pub vertical: bool,
+    pub shape: Option<Shape>,
+}
+
+impl ShipPlacement {
+    // posicionamento de um formato já girado, com o canto em (linha, coluna)
+    pub fn from_shape(name: &str, row: usize, column: usize, shape: &Shape) -> Self {
+        let (rows, _) = shape.size();
+
+        ShipPlacement {
+            name: name.to_string(),
+            row,
+            column,
+            length: shape.len(),
+            vertical: rows > 1,
+            shape: (!shape.is_line()).then(|| shape.clone()),
+        }
+    }
+
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        if let Some(shape) = &self.shape {
+            return shape.at(self.row, self.column);
+        }
+
+        (0..self.length)
+            .map(|i| {
+                if self.vertical {
+                    (self.row + i, self.column)
+                } else {
+                    (self.row, self.column + i)
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleError {
+    OutOfBounds,
+    Overlap,
+    WrongFleet,
+    FleetAlreadyPlaced,
+    NotStarted,
+    NotYourTurn,
+    AlreadyFired,
+    AbilityUnavailable,
+    CannotMove,
+    OnTerrain,
+    Island,
+    GameOver,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            RuleError::OutOfBounds => "posição fora do tabuleiro",
+            RuleError::Overlap => "navios sobrepostos",
+            RuleError::WrongFleet => "frota não corresponde às regras",
+            RuleError::FleetAlreadyPlaced => "frota já posicionada",
+            RuleError::NotStarted => "partida ainda não começou",
+            RuleError::NotYourTurn => "não é a sua vez",
+            RuleError::AlreadyFired => "célula já atacada",
+            RuleError::AbilityUnavailable => "habilidade indisponível",
+            RuleError::CannotMove => "navio não pode andar para lá",
+            RuleError::OnTerrain => "navio em cima de ilha ou mina",
+            RuleError::Island => "não dá para atirar numa ilha",
+            RuleError::GameOver => "partida encerrada",
+        };
+
+        write!(f, "{message}")
+    }
+}
+
+#[derive(Clone, Debug)]
+struct FleetShip {
+    name: String,
+    cells: Vec<(usize, usize)>,
+    vertical: bool, // eixo do movimento quando o navio é tão largo quanto alto
+    ability: Option<Ability>,
+    ability_used: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Match {
+    pub rules: Rules,
+    fleets: [Option<Vec<FleetShip>>; 2],
+    shots: [HashMap<(usize, usize), ShotOutcome>; 2], // tiros dados por cada lado
+    penalty: [bool; 2], // o lado acertou uma mina e perde a próxima vez
+    pub turn: usize,
+    pub winner: Option<usize>,
+}
+
+impl Match {
+    pub fn new(rules: Rules) -> Self {
+        Match {
+            rules,
+            fleets: [None, None],
+            shots: [HashMap::new(), HashMap::new()],
+            penalty: [false, false],
+            turn: 0,
+            winner: None,
+        }
+    }
+
+    pub fn place_fleet(&mut self, side: usize, placements: &[ShipPlacement]) -> Result<(), RuleError> {
+        if self.fleets[side].is_some() {
+            return Err(RuleError::FleetAlreadyPlaced);
+        }
+
+        let mut expected: Vec<usize> = self.rules.fleet.iter().map(|ship| ship.length).collect();
+        let mut received: Vec<usize> = placements.iter().map(|ship| ship.cells().len()).collect();
+        expected.sort();
+        received.sort();
+
+        if expected != received {
+            return Err(RuleError::WrongFleet);
+        }
+
+        // nome fora das regras só vale para navio reto, como antes dos formatos
+        for placement in placements {
+            let accepted = match self.rules.fleet.iter().find(|ship| ship.name == placement.name) {
+                Some(rule) => rule.accepts(placement),
+                None => placement.shape.is_none(),
+            };
+
+            if !accepted {
+                return Err(RuleError::WrongFleet);
+            }
+        }
+
+        let mut occupied: HashSet<(usize, usize)> = HashSet::new();
+        let mut fleet = Vec::new();
+
+        for placement in placements {
+            let cells = placement.cells();
+
+            for (row, column) in &cells {
+                if *row >= self.rules.rows || *column >= self.rules.columns {
+                    return Err(RuleError::OutOfBounds);
+                }
+                if !self.rules.terrain.is_water(*row, *column) {
+                    return Err(RuleError::OnTerrain);
+                }
+                if !occupied.insert((*row, *column)) {
+                    return Err(RuleError::Overlap);
+                }
+            }
+
+            let ability = self
+                .rules
+                .fleet
+                .iter()
+                .find(|ship| ship.name == placement.name)
+                .and_then(|ship| ship.ability);
+
+            fleet.push(FleetShip {
+                name: placement.name.clone(),
+                cells,
+                vertical: placement.vertical,
+                ability,
+                ability_used: false,
+            });
+        }
+
+        self.fleets[side] = Some(fleet);
+        Ok(())
+    }
+
+    pub fn has_fleet(&self, side: usize) -> bool {
+        self.fleets[side].is_some()
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.fleets.iter().all(|fleet| fleet.is_some())
+    }
+
+    pub fn fire(&mut self, side: usize, row: usize, column: usize) -> Result<ShotOutcome, RuleError> {
+        self.check_turn(side, row, column)?;
+        // com navios em movimento um tiro na água pode ter ficado velho e vale repetir
+        match self.shots[side].get(&(row, column)) {
+            Some(ShotOutcome::Miss) if self.rules.moving => {}
+            Some(_) => return Err(RuleError::AlreadyFired),
+            None => {}
+        }
+        if self.rules.terrain.get(row, column) == Terrain::Island {
+            return Err(RuleError::Island);
+        }
+
+        let outcome = self.resolve(side, row, column);
+        self.end_turn(side);
+
+        Ok(outcome)
+    }
+
+    // habilidades ainda disponíveis para `side`: de navios que flutuam e não a usaram
+    pub fn abilities(&self, side: usize) -> Vec<Ability> {
+        self.fleets[side]
+            .iter()
+            .flatten()
+            .filter(|ship| !ship.ability_used && !self.is_sunk(side, ship))
+            .filter_map(|ship| ship.ability)
+            .collect()
+    }
+
+    /**
+     * habilidade no lugar do tiro da vez, com o alvo no lado adversário.
+     * gasta a habilidade de um navio de `side` e passa a vez como um tiro
+     */
+    pub fn use_ability(
+        &mut self,
+        side: usize,
+        ability: Ability,
+        row: usize,
+        column: usize,
+    ) -> Result<AbilityOutcome, RuleError> {
+        self.check_turn(side, row, column)?;
+
+        let ship_index = self.fleets[side]
+            .iter()
+            .flatten()
+            .position(|ship| ship.ability == Some(ability) && !ship.ability_used && !self.is_sunk(side, ship))
+            .ok_or(RuleError::AbilityUnavailable)?;
+
+        let target = 1 - side;
+        let area = ability.area(row, column, self.rules.rows, self.rules.columns);
+        // ilha não leva tiro: para as habilidades é como se já tivesse sido atacada
+        let fired = |cell: &(usize, usize)| {
+            self.shots[side].contains_key(cell) || self.rules.terrain.get(cell.0, cell.1) == Terrain::Island
+        };
+        let occupied = |cell: &(usize, usize)| self.ship_at(target, cell.0, cell.1).is_some();
+
+        let outcome = if ability == Ability::Sonar {
+            AbilityOutcome::Sonar(Ability::detects(&area, fired, occupied))
+        } else {
+            let strikes = ability.strikes(&area, fired, occupied);
+            if strikes.is_empty() {
+                return Err(RuleError::AlreadyFired);
+            }
+
+            AbilityOutcome::Shots(
+                strikes
+                    .into_iter()
+                    .map(|(row, column)| (row, column, self.resolve(side, row, column)))
+                    .collect(),
+            )
+        };
+
+        if let Some(fleet) = self.fleets[side].as_mut() {
+            fleet[ship_index].ability_used = true;
+        }
+        self.end_turn(side);
+
+        Ok(outcome)
+    }
+
+    // movimentos possíveis de `side`: índice do navio na ordem do posicionamento e sentido
+    pub fn moves(&self, side: usize) -> Vec<(usize, bool)> {
+        let ships = self.fleets[side].as_ref().map_or(0, Vec::len);
+
+        (0..ships)
+            .flat_map(|ship| [(ship, true), (ship, false)])
+            .filter(|(ship, forward)| self.moved_ship(side, *ship, *forward).is_some())
+            .collect()
+    }
+
+    /**
+     * navio em movimento no lugar do tiro da vez: o navio `ship` de `side`,
+     * ainda sem dano, anda uma célula para frente (linha ou coluna maior) ou
+     * para trás no eixo dele e a vez passa como num tiro
+     */
+    pub fn move_ship(&mut self, side: usize, ship: usize, forward: bool) -> Result<(), RuleError> {
+        self.check_side(side)?;
+        let cells = self.moved_ship(side, ship, forward).ok_or(RuleError::CannotMove)?;
+
+        if let Some(fleet) = self.fleets[side].as_mut() {
+            fleet[ship].cells = cells;
+        }
+        self.end_turn(side);
+
+        Ok(())
+    }
+
+    // células do navio depois de andar, se as regras deixam e o destino está livre
+    fn moved_ship(&self, side: usize, ship: usize, forward: bool) -> Option<Vec<(usize, usize)>> {
+        let fleet = self.fleets[side].as_ref()?;
+        let current = fleet.get(ship).filter(|_| self.rules.moving)?;
+
+        if current.cells.iter().any(|cell| self.is_hit(1 - side, cell)) {
+            return None;
+        }
+
+        let cells = moved_cells(&current.cells, current.vertical, forward)?;
+        let free = cells.iter().all(|(row, column)| {
+            *row < self.rules.rows
+                && *column < self.rules.columns
+                && self.rules.terrain.is_water(*row, *column)
+                && fleet
+                    .iter()
+                    .enumerate()
+                    .all(|(other, placed)| other == ship || !placed.cells.contains(&(*row, *column)))
+        });
+
+        free.then_some(cells)
+    }
+
+    fn check_side(&self, side: usize) -> Result<(), RuleError> {
+        if self.winner.is_some() {
+            return Err(RuleError::GameOver);
+        }
+        if !self.is_started() {
+            return Err(RuleError::NotStarted);
+        }
+        if self.turn != side {
+            return Err(RuleError::NotYourTurn);
+        }
+
+        Ok(())
+    }
+
+    fn check_turn(&self, side: usize, row: usize, column: usize) -> Result<(), RuleError> {
+        self.check_side(side)?;
+        if row >= self.rules.rows || column >= self.rules.columns {
+            return Err(RuleError::OutOfBounds);
+        }
+
+        Ok(())
+    }
+
+    // tiro de `side` numa célula ainda não atacada, sem mexer na vez; mina é tiro na água com castigo
+    fn resolve(&mut self, side: usize, row: usize, column: usize) -> ShotOutcome {
+        if self.rules.terrain.get(row, column) == Terrain::Mine {
+            self.penalty[side] = true;
+        }
+
+        let fleet = self.fleets[1 - side].as_ref().unwrap();
+
+        let outcome = match fleet.iter().find(|ship| ship.cells.contains(&(row, column))) {
+            Some(ship) => {
+                let sunk = ship
+                    .cells
+                    .iter()
+                    .all(|cell| *cell == (row, column) || self.is_hit(side, cell));
+
+                if sunk {
+                    ShotOutcome::Sunk
+                } else {
+                    ShotOutcome::Hit
+                }
+            }
+            None => ShotOutcome::Miss,
+        };
+
+        self.shots[side].insert((row, column), outcome);
+        outcome
+    }
+
+    fn end_turn(&mut self, side: usize) {
+        let target = 1 - side;
+        let fleet_size = self.fleets[target].as_ref().map_or(0, Vec::len);
+
+        if self.sunk_ships(target) == fleet_size {
+            self.winner = Some(side);
+        } else if self.penalty[target] {
+            // quem acertou a mina fica sem esta vez
+            self.penalty[target] = false;
+            self.turn = side;
+        } else {
+            self.turn = target;
+        }
+    }
+
+    // navio de `side` com todas as células atingidas pelo adversário
+    fn is_sunk(&self, side: usize, ship: &FleetShip) -> bool {
+        ship.cells.iter().all(|cell| self.is_hit(1 - side, cell))
+    }
+
+    // tiro de `shooter` que acertou a célula; um tiro na água velho não conta
+    fn is_hit(&self, shooter: usize, cell: &(usize, usize)) -> bool {
+        self.shots[shooter]
+            .get(cell)
+            .is_some_and(|outcome| *outcome != ShotOutcome::Miss)
+    }
+
+    // turno perdido por tempo esgotado
+    pub fn pass_turn(&mut self) {
+        if self.winner.is_none() {
+            self.turn = 1 - self.turn;
+        }
+    }
+
+    pub fn forfeit(&mut self, side: usize) {
+        if self.winner.is_none() {
+            self.winner = Some(1 - side);
+        }
+    }
+
+    pub fn shots_by(&self, side: usize) -> &HashMap<(usize, usize), ShotOutcome> {
+        &self.shots[side]
+    }
+
+    pub fn sunk_ships(&self, side: usize) -> usize {
+        self.shots[1 - side]
+            .values()
+            .filter(|outcome| **outcome == ShotOutcome::Sunk)
+            .count()
+    }
+
+    // nome e tamanho do navio de `side` que ocupa a célula
+    pub fn ship_at(&self, side: usize, row: usize, column: usize) -> Option<(&str, usize)> {
+        self.fleets[side]
+            .iter()
+            .flatten()
+            .find(|ship| ship.cells.contains(&(row, column)))
+            .map(|ship| (ship.name.as_str(), ship.cells.len()))
+    }
+
+    pub fn fleet_layout(&self, side: usize) -> FleetLayout {
+        FleetLayout {
+            ships: self.fleets[side]
+                .iter()
+                .flatten()
+                .map(|ship| PlacedShip {
+                    name: ship.name.clone(),
+                    cells: ship.cells.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/**
+ * células de um navio depois de andar uma célula no eixo dele, o lado mais
+ * comprido, ou no de `vertical` quando ele é tão largo quanto alto. None se
+ * passar da linha ou coluna 0; o outro limite fica com quem chama
+ */
+pub fn moved_cells(cells: &[(usize, usize)], vertical: bool, forward: bool) -> Option<Vec<(usize, usize)>> {
+    let vertical = moves_vertically(cells, vertical);
+    let step = |value: usize| if forward { Some(value + 1) } else { value.checked_sub(1) };
+
+    cells
+        .iter()
+        .map(|(row, column)| {
+            if vertical {
+                Some((step(*row)?, *column))
+            } else {
+                Some((*row, step(*column)?))
+            }
+        })
+        .collect()
+}
+
+// eixo em que o navio anda: vertical se ele é mais alto que largo
+pub fn moves_vertically(cells: &[(usize, usize)], vertical: bool) -> bool {
+    let span = |values: Vec<usize>| {
+        values.iter().max().zip(values.iter().min()).map_or(0, |(max, min)| max - min)
+    };
+    let rows = span(cells.iter().map(|(row, _)| *row).collect());
+    let columns = span(cells.iter().map(|(_, column)| *column).collect());
+
+    rows > columns || (rows == columns && vertical)
+}
+
+// sorteios da frota inteira antes de random_fleet desistir
+const FLEET_ATTEMPTS: usize = 1000;
+
+// frota aleatória válida para as regras, usada pelos bots. Err se a frota não couber
+pub fn random_fleet(rules: &Rules, rng: &mut impl Rng) -> Result<Vec<ShipPlacement>, String> {
+    for _ in 0..FLEET_ATTEMPTS {
+        // ilhas e minas contam como ocupadas desde o começo
+        let mut occupied: HashSet<(usize, usize)> = rules
+            .terrain
+            .obstacles()
+            .map(|(row, column, _)| (row, column))
+            .collect();
+        let mut placements = Vec::new();
+
+        for ship in &rules.fleet {
+            let orientations = ship.shape.orientations();
+
+            for _ in 0..100 {
+                let shape = &orientations[rng.gen_range(0..orientations.len())];
+                let (rows, columns) = shape.size();
+                if rows > rules.rows || columns > rules.columns {
+                    continue;
+                }
+
+                let placement = ShipPlacement::from_shape(
+                    &ship.name,
+                    rng.gen_range(0..=rules.rows - rows),
+                    rng.gen_range(0..=rules.columns - columns),
+                    shape,
+                );
+
+                let cells = placement.cells();
+                if cells.iter().all(|cell| !occupied.contains(cell)) {
+                    occupied.extend(cells);
+                    placements.push(placement);
+                    break;
+                }
+            }
+        }
+
+        if placements.len() == rules.fleet.len() {
+            return Ok(placements);
+        }
+    }
+
+    Err(format!("frota não coube no tabuleiro em {FLEET_ATTEMPTS} tentativas"))
+}
@@ -1 +1,9 @@
+pub mod ability;
+pub mod bots;
 pub mod cell;
+pub mod commitment;
+pub mod engine;
+pub mod shape;
+pub mod shot;
+pub mod terrain;
+pub mod tournament;
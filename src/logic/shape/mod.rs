@@ -0,0 +1,182 @@
+use std::fmt;
+
+/**
+ * formato de um navio como poliominó: células em (linha, coluna) relativas
+ * ao canto do menor retângulo que contém o navio. o navio reto de N casas
+ * é só o caso da linha; L, T e formatos livres vêm do arquivo de regras
+ */
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shape {
+    cells: Vec<(usize, usize)>, // sempre normalizadas e ordenadas
+}
+
+impl Shape {
+    pub fn new(cells: &[(usize, usize)]) -> Result<Shape, String> {
+        if cells.is_empty() {
+            return Err("formato sem células".to_string());
+        }
+
+        let shape = Shape::normalized(cells.iter().map(|(row, column)| (*row as i64, *column as i64)));
+
+        if shape.cells.len() != cells.len() {
+            return Err("formato com células repetidas".to_string());
+        }
+        if !shape.is_connected() {
+            return Err("formato com células soltas".to_string());
+        }
+
+        Ok(shape)
+    }
+
+    // navio reto, deitado
+    pub fn line(length: usize) -> Shape {
+        Shape {
+            cells: (0..length).map(|column| (0, column)).collect(),
+        }
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // (linhas, colunas) ocupadas
+    pub fn size(&self) -> (usize, usize) {
+        let rows = self.cells.iter().map(|(row, _)| row + 1).max().unwrap_or(0);
+        let columns = self.cells.iter().map(|(_, column)| column + 1).max().unwrap_or(0);
+        (rows, columns)
+    }
+
+    pub fn is_line(&self) -> bool {
+        let (rows, columns) = self.size();
+        rows == 1 || columns == 1
+    }
+
+    // um quarto de volta no sentido horário
+    pub fn rotated(&self) -> Shape {
+        Shape::normalized(
+            self.cells
+                .iter()
+                .map(|(row, column)| (*column as i64, -(*row as i64))),
+        )
+    }
+
+    // espelhado da esquerda para a direita
+    pub fn mirrored(&self) -> Shape {
+        Shape::normalized(
+            self.cells
+                .iter()
+                .map(|(row, column)| (*row as i64, -(*column as i64))),
+        )
+    }
+
+    // `turns` quartos de volta, espelhando antes se pedido
+    pub fn oriented(&self, turns: usize, mirrored: bool) -> Shape {
+        let mut shape = if mirrored { self.mirrored() } else { self.clone() };
+        for _ in 0..turns % 4 {
+            shape = shape.rotated();
+        }
+        shape
+    }
+
+    // todas as posições distintas do navio: até 4 giros, com e sem espelho
+    pub fn orientations(&self) -> Vec<Shape> {
+        let mut shapes: Vec<Shape> = Vec::new();
+
+        for mirrored in [false, true] {
+            for turns in 0..4 {
+                let shape = self.oriented(turns, mirrored);
+                if !shapes.contains(&shape) {
+                    shapes.push(shape);
+                }
+            }
+        }
+
+        shapes
+    }
+
+    // o mesmo navio, em qualquer giro ou espelho
+    pub fn matches(&self, other: &Shape) -> bool {
+        self.orientations().contains(other)
+    }
+
+    // células no tabuleiro com o canto do formato em (linha, coluna)
+    pub fn at(&self, row: usize, column: usize) -> Vec<(usize, usize)> {
+        self.cells
+            .iter()
+            .map(|(cell_row, cell_column)| (row + cell_row, column + cell_column))
+            .collect()
+    }
+
+    // "linha,coluna;linha,coluna;...", o mesmo jeito de escrever células do compromisso de frota
+    pub fn parse(value: &str) -> Result<Shape, String> {
+        let invalid = || format!("formato inválido: {value}");
+
+        let cells = value
+            .split(';')
+            .map(|cell| {
+                let (row, column) = cell.split_once(',').ok_or_else(invalid)?;
+                Ok((
+                    row.trim().parse().map_err(|_| invalid())?,
+                    column.trim().parse().map_err(|_| invalid())?,
+                ))
+            })
+            .collect::<Result<Vec<(usize, usize)>, String>>()?;
+
+        Shape::new(&cells)
+    }
+
+    fn normalized(cells: impl Iterator<Item = (i64, i64)>) -> Shape {
+        let cells: Vec<(i64, i64)> = cells.collect();
+        let min_row = cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+        let min_column = cells.iter().map(|(_, column)| *column).min().unwrap_or(0);
+
+        let mut cells: Vec<(usize, usize)> = cells
+            .iter()
+            .map(|(row, column)| ((row - min_row) as usize, (column - min_column) as usize))
+            .collect();
+        cells.sort();
+        cells.dedup();
+
+        Shape { cells }
+    }
+
+    // toda célula alcança as outras andando só por vizinhas
+    fn is_connected(&self) -> bool {
+        let mut reached = vec![self.cells[0]];
+        let mut index = 0;
+
+        while index < reached.len() {
+            let (row, column) = reached[index];
+            index += 1;
+
+            for cell in &self.cells {
+                let neighbour = row.abs_diff(cell.0) + column.abs_diff(cell.1) == 1;
+                if neighbour && !reached.contains(cell) {
+                    reached.push(*cell);
+                }
+            }
+        }
+
+        reached.len() == self.cells.len()
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells: Vec<String> = self
+            .cells
+            .iter()
+            .map(|(row, column)| format!("{row},{column}"))
+            .collect();
+
+        write!(f, "{}", cells.join(";"))
+    }
+}
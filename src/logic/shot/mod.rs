@@ -0,0 +1,7 @@
+// resultado de um disparo, do jeito que é anunciado pelo lado que recebeu o tiro
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ShotOutcome {
+    Miss,
+    Hit,
+    Sunk,
+}
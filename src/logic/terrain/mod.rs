@@ -0,0 +1,110 @@
+use std::fmt;
+
+// o que há numa célula além de água
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Terrain {
+    #[default]
+    Water,
+    Island, // não recebe navio nem tiro
+    Mine,   // não recebe navio; quem atira nela perde a próxima vez
+}
+
+impl Terrain {
+    fn symbol(self) -> char {
+        match self {
+            Terrain::Water => '.',
+            Terrain::Island => 'o',
+            Terrain::Mine => 'x',
+        }
+    }
+
+    fn from_symbol(symbol: char) -> Option<Terrain> {
+        [Terrain::Water, Terrain::Island, Terrain::Mine]
+            .into_iter()
+            .find(|terrain| terrain.symbol() == symbol)
+    }
+}
+
+/**
+ * mapa de um lado do tabuleiro, igual para os dois lados. fora do mapa é
+ * tudo água, então um mapa menor que o tabuleiro ocupa só o canto das
+ * primeiras linhas e colunas e o mapa vazio é o jogo clássico
+ */
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TerrainMap {
+    rows: Vec<Vec<Terrain>>, // linha 0 primeiro, como no resto do motor
+}
+
+impl TerrainMap {
+    pub fn get(&self, row: usize, column: usize) -> Terrain {
+        self.rows
+            .get(row)
+            .and_then(|cells| cells.get(column))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn is_water(&self, row: usize, column: usize) -> bool {
+        self.get(row, column) == Terrain::Water
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().flatten().all(|terrain| *terrain == Terrain::Water)
+    }
+
+    // células que não são água, com o tipo de cada uma
+    pub fn obstacles(&self) -> impl Iterator<Item = (usize, usize, Terrain)> + '_ {
+        self.rows.iter().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .filter(|(_, terrain)| **terrain != Terrain::Water)
+                .map(move |(column, terrain)| (row, column, *terrain))
+        })
+    }
+
+    // uma linha do mapa: "." água, "o" ilha, "x" mina
+    pub fn parse_row(value: &str) -> Result<Vec<Terrain>, String> {
+        value
+            .chars()
+            .map(|symbol| Terrain::from_symbol(symbol).ok_or_else(|| format!("terreno inválido: {symbol}")))
+            .collect()
+    }
+
+    pub fn push_row(&mut self, cells: Vec<Terrain>) {
+        self.rows.push(cells);
+    }
+
+    /**
+     * arquivo de mapa: uma linha "map <células>" por linha do lado, da linha
+     * 0 em diante, no mesmo formato aceito pelo arquivo de regras. linhas
+     * vazias e começando com # são ignoradas
+     */
+    pub fn parse(text: &str) -> Result<TerrainMap, String> {
+        let mut map = TerrainMap::default();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                ["map", cells] => map.push_row(TerrainMap::parse_row(cells)?),
+                _ => return Err(format!("linha inválida: {line}")),
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+// as linhas "map" do arquivo, sem o resto das regras
+impl fmt::Display for TerrainMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for cells in &self.rows {
+            let cells: String = cells.iter().map(|terrain| terrain.symbol()).collect();
+            writeln!(f, "map {cells}")?;
+        }
+        Ok(())
+    }
+}
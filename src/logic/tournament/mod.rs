@@ -0,0 +1,219 @@
+use super::{
+    ability::{Ability, AbilityOutcome},
+    bots::BotPlayer,
+    engine::{Match, Rules, ShipPlacement},
+    shot::ShotOutcome,
+};
+
+// como terminou uma partida entre dois bots
+#[derive(Clone, Debug)]
+pub struct GameReport {
+    pub winner: usize,
+    pub shots: [usize; 2],
+    pub forfeit: Option<(usize, String)>, // lado que errou (tempo, jogada inválida) e o motivo
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Standing {
+    pub name: String,
+    pub wins: usize,
+    pub losses: usize,
+    pub forfeits: usize,
+    pub shots_in_wins: usize,
+}
+
+impl Standing {
+    pub fn average_shots_to_win(&self) -> Option<f32> {
+        (self.wins > 0).then(|| self.shots_in_wins as f32 / self.wins as f32)
+    }
+}
+
+/**
+ * acompanha uma partida enquanto ela acontece, para assistir ao torneio
+ * ao vivo (api::server::RoomFeed repassa tudo a espectadores)
+ */
+pub trait MatchObserver {
+    fn new_game(&mut self, _names: [String; 2], _rules: &Rules) {}
+    fn fleet(&mut self, _seat: usize, _placements: &[ShipPlacement]) {}
+    fn shot(&mut self, _seat: usize, _row: usize, _column: usize, _outcome: ShotOutcome) {}
+    // habilidade usada no lugar do tiro, com os tiros dela ou a resposta do sonar
+    fn ability(&mut self, _seat: usize, _ability: Ability, _row: usize, _column: usize, _outcome: &AbilityOutcome) {}
+    // navio `ship` de `seat`, na ordem do posicionamento, andou no lugar do tiro
+    fn moved(&mut self, _seat: usize, _ship: usize, _forward: bool) {}
+    fn game_over(&mut self, _report: &GameReport) {}
+}
+
+// torneio sem ninguém assistindo
+pub struct NoObserver;
+
+impl MatchObserver for NoObserver {}
+
+fn forfeit(game: &mut Match, shots: [usize; 2], seat: usize, reason: String) -> GameReport {
+    game.forfeit(seat);
+
+    GameReport {
+        winner: 1 - seat,
+        shots,
+        forfeit: Some((seat, reason)),
+    }
+}
+
+// resultado do tiro para quem atirou, com o tamanho do navio se afundou
+fn report_shot(
+    game: &Match,
+    player: &mut dyn BotPlayer,
+    seat: usize,
+    row: usize,
+    column: usize,
+    outcome: ShotOutcome,
+) {
+    let sunk = (outcome == ShotOutcome::Sunk)
+        .then(|| game.ship_at(1 - seat, row, column).map(|(_, length)| length))
+        .flatten();
+    player.result(row, column, outcome, sunk);
+}
+
+/**
+ * joga uma partida completa com o motor de regras; qualquer erro de um
+ * lado (tempo esgotado, frota ou tiro inválido) dá a vitória ao outro
+ */
+pub fn play_game(
+    mut players: [&mut dyn BotPlayer; 2],
+    rules: &Rules,
+    first: usize,
+    observer: &mut dyn MatchObserver,
+) -> GameReport {
+    let mut game = Match::new(rules.clone());
+    game.turn = first;
+    let mut shots = [0, 0];
+
+    observer.new_game([players[0].name(), players[1].name()], rules);
+
+    let report = 'game: {
+        for (seat, player) in players.iter_mut().enumerate() {
+            let placed = player
+                .new_game(rules)
+                .and_then(|_| player.place(rules))
+                .and_then(|fleet| {
+                    game.place_fleet(seat, &fleet)
+                        .map_err(|error| error.to_string())?;
+                    observer.fleet(seat, &fleet);
+                    Ok(())
+                });
+
+            if let Err(reason) = placed {
+                break 'game forfeit(&mut game, shots, seat, reason);
+            }
+        }
+
+        while game.winner.is_none() {
+            let seat = game.turn;
+
+            // com navios em movimento o bot pode andar com um navio no lugar do tiro
+            let moves = if rules.moving { game.moves(seat) } else { Vec::new() };
+            if let Some((ship, forward)) = (!moves.is_empty()).then(|| players[seat].maneuver(&moves)).flatten() {
+                if let Err(error) = game.move_ship(seat, ship, forward) {
+                    break 'game forfeit(&mut game, shots, seat, error.to_string());
+                }
+                observer.moved(seat, ship, forward);
+                players[1 - seat].opponent_moved();
+                continue;
+            }
+
+            // nas regras avançadas o bot pode trocar o tiro por uma habilidade
+            let available = game.abilities(seat);
+            let chosen = if available.is_empty() {
+                None
+            } else {
+                players[seat].ability(&available)
+            };
+
+            if let Some((ability, row, column)) = chosen {
+                let outcome = match game.use_ability(seat, ability, row, column) {
+                    Ok(outcome) => outcome,
+                    Err(error) => break 'game forfeit(&mut game, shots, seat, error.to_string()),
+                };
+
+                observer.ability(seat, ability, row, column, &outcome);
+                match outcome {
+                    AbilityOutcome::Sonar(found) => players[seat].sonar(row, column, found),
+                    AbilityOutcome::Shots(strikes) => {
+                        for (row, column, outcome) in strikes {
+                            shots[seat] += 1;
+                            report_shot(&game, players[seat], seat, row, column, outcome);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            let (row, column) = match players[seat].fire() {
+                Ok(target) => target,
+                Err(reason) => break 'game forfeit(&mut game, shots, seat, reason),
+            };
+
+            let outcome = match game.fire(seat, row, column) {
+                Ok(outcome) => outcome,
+                Err(error) => break 'game forfeit(&mut game, shots, seat, error.to_string()),
+            };
+
+            shots[seat] += 1;
+            observer.shot(seat, row, column, outcome);
+            report_shot(&game, players[seat], seat, row, column, outcome);
+        }
+
+        GameReport {
+            winner: game.winner.unwrap_or(0),
+            shots,
+            forfeit: None,
+        }
+    };
+
+    observer.game_over(&report);
+
+    for (seat, player) in players.into_iter().enumerate() {
+        player.game_over(report.winner == seat);
+    }
+
+    report
+}
+
+// todos contra todos, `games` partidas por par, alternando quem começa
+pub fn round_robin(
+    players: &mut [Box<dyn BotPlayer>],
+    rules: &Rules,
+    games: usize,
+    observer: &mut dyn MatchObserver,
+) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = players
+        .iter()
+        .map(|player| Standing {
+            name: player.name(),
+            ..Default::default()
+        })
+        .collect();
+
+    for a in 0..players.len() {
+        for b in (a + 1)..players.len() {
+            let (left, right) = players.split_at_mut(b);
+
+            for round in 0..games {
+                let pair: [&mut dyn BotPlayer; 2] = [left[a].as_mut(), right[0].as_mut()];
+                let report = play_game(pair, rules, round % 2, observer);
+                let (winner, loser) = if report.winner == 0 { (a, b) } else { (b, a) };
+
+                standings[winner].wins += 1;
+                standings[winner].shots_in_wins += report.shots[report.winner];
+                standings[loser].losses += 1;
+
+                if let Some((_, reason)) = &report.forfeit {
+                    standings[loser].forfeits += 1;
+                    log::warn!("{} perdeu por W.O.: {reason}", standings[loser].name);
+                }
+            }
+        }
+    }
+
+    standings.sort_by_key(|standing| std::cmp::Reverse(standing.wins));
+    standings
+}
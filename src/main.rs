@@ -1,11 +1,24 @@
-mod logic;
-mod ui;
-
 use bevy::prelude::*;
-use ui::components::{
-    board::Board,
+use naval_battle_game_interface::ui::components::{
+    abilities::AbilityBarPlugin,
+    achievements::AchievementsPlugin,
+    board::{render_board, Board},
+    effects::EffectsPlugin,
+    external_bot::ExternalBotPlugin,
+    fleet_commitment::FleetCommitmentPlugin,
+    hotseat::HotseatPlugin,
+    layout::LayoutPlugin,
+    localization::LocalizationPlugin,
+    main_menu::{AppState, MainMenuPlugin},
+    network_match::NetworkMatchPlugin,
+    notices::NoticesPlugin,
+    pause_menu::PauseMenuPlugin,
+    replay::ReplayPlugin,
+    save_game::replay_saved_shots,
     ship_selection_panel::ShipSelectionPanel,
-    ships::{check_sunk_change, debug_spawn_submarine},
+    ships::{check_sunk_change, spawn_fleets, ShipSprites},
+    sound::SoundPlugin,
+    spectator::SpectatorPlugin,
 };
 
 fn main() {
@@ -13,15 +26,28 @@ fn main() {
 
     App::new()
         .add_plugins(DefaultPlugins)
+        .add_plugins(LayoutPlugin) // câmera e escala da interface
+        .add_plugins(LocalizationPlugin)
+        .add_plugins(MainMenuPlugin)
         .add_plugins(Board) // adicionando plugin de tabuleiro
+        .add_plugins(ShipSprites)
         .add_plugins(ShipSelectionPanel)
-        .add_systems(Startup, setup)
-        .add_systems(PostStartup, debug_spawn_submarine)
+        .add_plugins(FleetCommitmentPlugin)
+        .add_plugins(EffectsPlugin)
+        .add_plugins(NetworkMatchPlugin) // só ativo com --connect <endereço>
+        .add_plugins(ExternalBotPlugin) // só ativo com --bot-exe "<comando>"
+        .add_plugins(SpectatorPlugin) // só ativo com --spectate <endereço>
+        .add_plugins(HotseatPlugin) // só ativo na partida de dois jogadores do menu
+        .add_plugins(ReplayPlugin) // só ativo no replay do jogo salvo
+        .add_plugins(PauseMenuPlugin)
+        .add_plugins(AbilityBarPlugin) // só aparece nas regras avançadas
+        .add_plugins(NoticesPlugin)
+        .add_plugins(AchievementsPlugin)
+        .add_plugins(SoundPlugin) // sem dispositivo de áudio o jogo segue mudo
+        .add_systems(
+            OnEnter(AppState::InGame),
+            (spawn_fleets, replay_saved_shots).chain().after(render_board),
+        )
         .add_systems(Update, check_sunk_change)
         .run();
 }
-
-//função setup serve para inicializar o sistema e configurações gerais do ambiente gráfico
-fn setup(mut commands: Commands) {
-    commands.spawn(Camera2d);
-}
@@ -0,0 +1,258 @@
+use bevy::{input::mouse::MouseButtonInput, prelude::*};
+
+use crate::logic::{ability::Ability, cell::Side};
+
+use super::{
+    board::{BoardIndex, GameState, ShotGrid},
+    hotseat::Hotseat,
+    localization::{Locale, Localized},
+    main_menu::AppState,
+    network_match::NetworkSession,
+    pause_menu::PauseState,
+    replay::ReplaySession,
+    settings::{GameSettings, RuleVariant},
+    ship_selection_panel::SelectedShip,
+    ships::{Ship, ShipCatalog},
+    spectator::SpectatorSession,
+    theme::Theme,
+};
+
+pub struct AbilityBarPlugin;
+
+// habilidade escolhida na barra; o próximo clique no lado inimigo é o alvo dela
+#[derive(Resource, Default, Debug)]
+pub struct ArmedAbility(pub Option<Ability>);
+
+// resposta do último sonar do jogador e as células inimigas que ele varreu
+#[derive(Resource, Default, Debug)]
+pub struct SonarReport {
+    pub found: Option<bool>,
+    pub area: Vec<Entity>,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+struct AbilityButton(Ability);
+
+#[derive(Component)]
+struct AbilityStatus;
+
+const BAR_BUTTON_WIDTH: f32 = 160.0;
+const BAR_BUTTON_HEIGHT: f32 = 40.0;
+
+/**
+ * barra de habilidades das regras avançadas, embaixo da tela. cada botão
+ * fica ativo enquanto algum navio do jogador com aquela habilidade flutua
+ * e ainda não a usou; clicar arma a habilidade para o próximo clique no
+ * tabuleiro inimigo (clicar de novo desarma). com dois jogadores a barra
+ * é de quem está com a vez
+ */
+impl Plugin for AbilityBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ArmedAbility>()
+            .init_resource::<SonarReport>()
+            .add_systems(
+                OnEnter(AppState::InGame),
+                (reset_abilities, spawn_ability_bar).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_ability_buttons.run_if(in_state(PauseState::Running)),
+                    update_ability_bar,
+                    show_sonar_report.run_if(resource_changed::<SonarReport>),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+fn ability_key(ability: Ability) -> &'static str {
+    match ability {
+        Ability::Airstrike => "ability-airstrike",
+        Ability::Sonar => "ability-sonar",
+        Ability::Torpedo => "ability-torpedo",
+    }
+}
+
+fn reset_abilities(mut armed: ResMut<ArmedAbility>, mut sonar: ResMut<SonarReport>) {
+    *armed = ArmedAbility::default();
+    *sonar = SonarReport::default();
+}
+
+// só nas partidas locais com as regras avançadas; em rede o cliente joga as regras clássicas
+fn spawn_ability_bar(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    network: Option<Res<NetworkSession>>,
+    spectator: Option<Res<SpectatorSession>>,
+    replay: Option<Res<ReplaySession>>,
+) {
+    if settings.variant != RuleVariant::Advanced || network.is_some() || spectator.is_some() || replay.is_some() {
+        return;
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(12.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                ..Default::default()
+            },
+            StateScoped(AppState::InGame),
+        ))
+        .with_children(|parent| {
+            for ability in Ability::ALL {
+                parent
+                    .spawn((
+                        Button,
+                        AbilityButton(ability),
+                        Node {
+                            width: Val::Px(BAR_BUTTON_WIDTH),
+                            height: Val::Px(BAR_BUTTON_HEIGHT),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..Default::default()
+                        },
+                        BorderRadius::all(Val::Px(8.0)),
+                        BorderColor(theme.palette().panel_border),
+                        BackgroundColor(theme.palette().panel_background),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::default(),
+                            Localized(ability_key(ability)),
+                            TextFont {
+                                font_size: 18.0,
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+
+            parent.spawn((
+                Text::default(),
+                AbilityStatus,
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+// lado dono da barra: o jogador, ou quem está com a vez numa partida de dois
+fn bar_side(game_state: &GameState, hotseat: Option<&Hotseat>) -> Side {
+    match hotseat {
+        Some(_) => game_state.shooter(),
+        None => Side::Player,
+    }
+}
+
+fn side_has(
+    ability: Ability,
+    side: Side,
+    ships_query: &Query<&Ship, Without<SelectedShip>>,
+    catalog: &ShipCatalog,
+) -> bool {
+    ships_query
+        .iter()
+        .any(|ship| ship.side == side && ship.ability(catalog) == Some(ability))
+}
+
+fn handle_ability_buttons(
+    buttons_query: Query<(&Interaction, &AbilityButton), Changed<Interaction>>,
+    ships_query: Query<&Ship, Without<SelectedShip>>,
+    mut armed: ResMut<ArmedAbility>,
+    mut mouse_events: ResMut<Events<MouseButtonInput>>,
+    catalog: Res<ShipCatalog>,
+    game_state: Res<GameState>,
+    hotseat: Option<Res<Hotseat>>,
+) {
+    let side = bar_side(&game_state, hotseat.as_deref());
+
+    for (interaction, button) in buttons_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        // o clique no botão não pode virar tiro no tabuleiro
+        mouse_events.clear();
+
+        armed.0 = if armed.0 == Some(button.0) || !side_has(button.0, side, &ships_query, &catalog) {
+            None
+        } else {
+            Some(button.0)
+        };
+    }
+}
+
+// cor de cada botão: armado, disponível ou já gasto
+fn update_ability_bar(
+    mut buttons_query: Query<(&AbilityButton, &mut BackgroundColor, &mut BorderColor)>,
+    ships_query: Query<&Ship, Without<SelectedShip>>,
+    armed: Res<ArmedAbility>,
+    theme: Res<Theme>,
+    catalog: Res<ShipCatalog>,
+    (game_state, hotseat): (Res<GameState>, Option<Res<Hotseat>>),
+) {
+    let palette = theme.palette();
+    let side = bar_side(&game_state, hotseat.as_deref());
+
+    for (button, mut background, mut border) in buttons_query.iter_mut() {
+        let available = side_has(button.0, side, &ships_query, &catalog);
+        let color = if armed.0 == Some(button.0) {
+            palette.highlight
+        } else if available {
+            palette.panel_background
+        } else {
+            palette.miss
+        };
+        let border_color = if available { palette.panel_border } else { palette.miss };
+
+        if background.0 != color {
+            background.0 = color;
+        }
+        if border.0 != border_color {
+            border.0 = border_color;
+        }
+    }
+}
+
+// o sonar não atira: a resposta vai para a barra e, se achou navio, a área fica destacada
+fn show_sonar_report(
+    sonar: Res<SonarReport>,
+    mut status_query: Query<&mut Text, With<AbilityStatus>>,
+    mut cells_query: Query<&mut Sprite>,
+    shot_grid: Res<ShotGrid>,
+    index: Res<BoardIndex>,
+    locale: Res<Locale>,
+    theme: Res<Theme>,
+) {
+    let Some(found) = sonar.found else {
+        return;
+    };
+
+    if let Ok(mut text) = status_query.get_single_mut() {
+        text.0 = locale.get(if found { "ability-sonar-found" } else { "ability-sonar-empty" });
+    }
+
+    if !found {
+        return;
+    }
+
+    for entity in &sonar.area {
+        let unknown = index
+            .position(*entity)
+            .is_some_and(|(side, row, column)| !shot_grid.is_known(&side, row, column));
+
+        if let (true, Ok(mut sprite)) = (unknown, cells_query.get_mut(*entity)) {
+            sprite.color = theme.palette().highlight;
+        }
+    }
+}
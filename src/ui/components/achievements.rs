@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+
+use crate::logic::{cell::Side, shot::ShotOutcome};
+
+use super::{
+    board::{BoardIndex, MatchEnded, ShotResolved},
+    localization::Locale,
+    main_menu::AppState,
+    notices::spawn_toast,
+    profiles::{MatchMode, Profiles},
+    settings::BotDifficulty,
+    ships::{HiddenFleet, Ship, ShipType},
+};
+
+pub struct AchievementsPlugin;
+
+const TOAST_COLOR: Color = Color::srgb(0.85, 0.65, 0.13);
+
+// o que precisa acontecer para a conquista ser desbloqueada
+#[derive(Clone, Debug)]
+pub enum AchievementCondition {
+    WinWithoutLosingShip,
+    SinkWithoutMissing(ShipType), // do primeiro acerto no navio até afundá-lo, todos os tiros nele
+    WinUnderShots(u32),
+    BeatBot(BotDifficulty),
+}
+
+pub struct Achievement {
+    pub id: &'static str, // gravado no perfil, não pode mudar
+    pub condition: AchievementCondition,
+}
+
+impl Achievement {
+    // o título fica em assets/locales, em "achievement-<id>"
+    pub fn title_key(&self) -> String {
+        format!("achievement-{}", self.id)
+    }
+}
+
+/**
+ * tabela das conquistas. uma nova conquista é só uma nova linha aqui e o
+ * título nos arquivos de idioma, desde que a condição dela já exista em
+ * AchievementCondition
+ */
+pub const ACHIEVEMENTS: [Achievement; 4] = [
+    Achievement {
+        id: "intacto",
+        condition: AchievementCondition::WinWithoutLosingShip,
+    },
+    Achievement {
+        id: "porta-avioes-certeiro",
+        condition: AchievementCondition::SinkWithoutMissing(ShipType::AircraftCarrier),
+    },
+    Achievement {
+        id: "relampago",
+        condition: AchievementCondition::WinUnderShots(30),
+    },
+    Achievement {
+        id: "mestre-do-bot",
+        condition: AchievementCondition::BeatBot(BotDifficulty::Hard),
+    },
+];
+
+// o que a partida atual já fez de relevante para as conquistas
+#[derive(Resource, Default)]
+struct AchievementTracker {
+    ships_lost: u32,
+    // navios inimigos já atingidos e ainda à tona, e se todos os tiros desde o primeiro acerto foram nele
+    runs: Vec<(Entity, bool)>,
+    sunk_without_missing: Vec<ShipType>,
+}
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AchievementTracker::default())
+            .add_systems(OnEnter(AppState::InGame), |mut tracker: ResMut<AchievementTracker>| {
+                *tracker = AchievementTracker::default();
+            })
+            .add_systems(
+                Update,
+                (track_shots, unlock_achievements)
+                    .chain()
+                    .run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+fn track_shots(
+    mut shots: EventReader<ShotResolved>,
+    mut tracker: ResMut<AchievementTracker>,
+    ships_query: Query<(Entity, &Ship)>,
+    hidden: Res<HiddenFleet>,
+    index: Res<BoardIndex>,
+) {
+    for shot in shots.read() {
+        if shot.shooter != Side::Player {
+            if shot.outcome == ShotOutcome::Sunk {
+                tracker.ships_lost += 1;
+            }
+            continue;
+        }
+
+        // em rede os navios inimigos não existem aqui, então só o erro conta.
+        // o navio inimigo atingido continua na frota escondida até afundar
+        let target = index.ship_at(shot.cell).and_then(|ship| {
+            ships_query
+                .get(ship)
+                .ok()
+                .or_else(|| hidden.get(ship).map(|hidden| (ship, hidden)))
+        });
+
+        for (ship, clean) in tracker.runs.iter_mut() {
+            if target.is_none_or(|(entity, _)| entity != *ship) {
+                *clean = false;
+            }
+        }
+
+        let Some((entity, ship)) = target else {
+            continue;
+        };
+
+        if !tracker.runs.iter().any(|(run, _)| *run == entity) {
+            tracker.runs.push((entity, true));
+        }
+
+        if shot.outcome == ShotOutcome::Sunk {
+            let clean = tracker
+                .runs
+                .iter()
+                .any(|(run, clean)| *run == entity && *clean);
+            tracker.runs.retain(|(run, _)| *run != entity);
+
+            if clean {
+                tracker.sunk_without_missing.push(ship.r#type.clone());
+            }
+        }
+    }
+}
+
+fn unlock_achievements(
+    mut commands: Commands,
+    mut match_ended: EventReader<MatchEnded>,
+    tracker: Res<AchievementTracker>,
+    mut profiles: ResMut<Profiles>,
+    locale: Res<Locale>,
+) {
+    let ended = match_ended.read().last().map(|event| event.summary.clone());
+
+    let mut unlocked = Vec::new();
+
+    for achievement in &ACHIEVEMENTS {
+        if profiles
+            .active()
+            .achievements
+            .iter()
+            .any(|id| id == achievement.id)
+        {
+            continue;
+        }
+
+        let won = ended.as_ref().filter(|summary| summary.won);
+
+        let done = match &achievement.condition {
+            AchievementCondition::SinkWithoutMissing(ship_type) => {
+                tracker.sunk_without_missing.contains(ship_type)
+            }
+            AchievementCondition::WinWithoutLosingShip => won.is_some() && tracker.ships_lost == 0,
+            AchievementCondition::WinUnderShots(shots) => won.is_some_and(|summary| summary.shots < *shots),
+            AchievementCondition::BeatBot(difficulty) => won.is_some_and(|summary| {
+                summary.mode == MatchMode::Bot && summary.difficulty == Some(*difficulty)
+            }),
+        };
+
+        if done {
+            unlocked.push(achievement);
+        }
+    }
+
+    if unlocked.is_empty() {
+        return;
+    }
+
+    for achievement in unlocked {
+        profiles
+            .active_mut()
+            .achievements
+            .push(achievement.id.to_string());
+        spawn_toast(
+            &mut commands,
+            &locale.format("achievement-unlocked", &[("title", &locale.get(&achievement.title_key()))]),
+            TOAST_COLOR,
+        );
+    }
+    profiles.save();
+}
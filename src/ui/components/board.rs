@@ -1,39 +1,455 @@
-use crate::logic::cell::{Cell, CellSide};
+use crate::logic::{
+    cell::{Cell, Side},
+    shot::ShotOutcome,
+    terrain::{Terrain, TerrainMap},
+};
 use bevy::{input::{mouse::MouseButtonInput, ButtonState}, text::TextBounds};
 
-use crate::ui::components::ships::Ship;
+use crate::{
+    api::protocol::ClientMessage,
+    logic::{
+        ability::{Ability, AbilityOutcome},
+        bots::{BotPlayer, HunterBot, RandomBot},
+        engine::{moved_cells, moves_vertically, Rules},
+    },
+    ui::components::{
+        abilities::{ArmedAbility, SonarReport},
+        effects::{animations_finished, explosions_finished},
+        external_bot::ExternalOpponent,
+        hotseat::Hotseat,
+        layout::{board_extent, cursor_world_position, SIDE_MARGIN},
+        main_menu::AppState,
+        network_match::NetworkSession,
+        notices::Notice,
+        pause_menu::PauseState,
+        profiles::{MatchMode, MatchSummary, Profiles},
+        replay::ReplaySession,
+        settings::{BotDifficulty, GameSettings, RuleVariant},
+        ships::{Fleet, Ship, ShipCatalog, ShipDirection},
+        spectator::SpectatorSession,
+        localization::Locale,
+        theme::{Palette, Theme},
+    },
+};
 pub use bevy::prelude::*;
-use rand::prelude::SliceRandom;
+use rand::seq::SliceRandom;
+use std::{
+    collections::HashMap,
+    time::Instant,
+};
 
 pub struct Board;
 
-//struct que vai representar o total de cliques do jogo globalmente
-#[derive(Default, Resource)]
-pub struct ClickedCells {
-    pub cells: Vec<Entity>,
+// o que se sabe de uma célula depois dos tiros
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ShotState {
+    #[default]
+    Unknown,
+    Miss,
+    Hit,
+    Sunk, // todas as células do navio afundado, não só a do último tiro
+}
+
+impl From<ShotOutcome> for ShotState {
+    fn from(outcome: ShotOutcome) -> Self {
+        match outcome {
+            ShotOutcome::Miss => ShotState::Miss,
+            ShotOutcome::Hit => ShotState::Hit,
+            ShotOutcome::Sunk => ShotState::Sunk,
+        }
+    }
+}
+
+/**
+ * tiros que caíram em cada lado do tabuleiro, com as linhas contadas a partir
+ * do começo do lado. o lado inimigo é o que o jogador sabe do adversário e o
+ * lado do jogador é o que o adversário sabe dele. é a única fonte de quais
+ * células já levaram tiro: bot, renderização e jogo salvo leem daqui
+ */
+#[derive(Default, Resource, Debug)]
+pub struct ShotGrid {
+    rows: usize,
+    columns: usize,
+    player: Vec<ShotState>,
+    enemy: Vec<ShotState>,
+}
+
+impl ShotGrid {
+    // linhas e colunas de um lado só
+    pub fn new(rows: usize, columns: usize) -> Self {
+        ShotGrid {
+            rows,
+            columns,
+            player: vec![ShotState::Unknown; rows * columns],
+            enemy: vec![ShotState::Unknown; rows * columns],
+        }
+    }
+
+    fn side(&self, side: &Side) -> &Vec<ShotState> {
+        match side {
+            Side::Player => &self.player,
+            Side::Enemy => &self.enemy,
+        }
+    }
+
+    pub fn get(&self, side: &Side, row: usize, column: usize) -> ShotState {
+        if row >= self.rows || column >= self.columns {
+            return ShotState::Unknown;
+        }
+        self.side(side)[row * self.columns + column]
+    }
+
+    pub fn set(&mut self, side: &Side, row: usize, column: usize, state: ShotState) {
+        if row >= self.rows || column >= self.columns {
+            return;
+        }
+        let columns = self.columns;
+        let cells = match side {
+            Side::Player => &mut self.player,
+            Side::Enemy => &mut self.enemy,
+        };
+        cells[row * columns + column] = state;
+    }
+
+    pub fn is_known(&self, side: &Side, row: usize, column: usize) -> bool {
+        self.get(side, row, column) != ShotState::Unknown
+    }
+
+    // células de um lado que ainda não levaram tiro
+    pub fn unknown<'a>(&'a self, side: &'a Side) -> impl Iterator<Item = (usize, usize)> + 'a {
+        (0..self.rows)
+            .flat_map(move |row| (0..self.columns).map(move |column| (row, column)))
+            .filter(move |(row, column)| !self.is_known(side, *row, *column))
+    }
+
+    pub fn count(&self, side: &Side, state: ShotState) -> usize {
+        self.side(side).iter().filter(|cell| **cell == state).count()
+    }
+
+    // (linhas, colunas) de um lado
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows, self.columns)
+    }
+}
+
+/**
+ * entidade de cada célula pela posição e posição de cada entidade, montado
+ * no render_board. as linhas aqui são as do tabuleiro inteiro (o jogador
+ * embaixo, de 0 até a metade); para um lado só use cell() e position().
+ * também guarda o navio em cada célula, mantido por index_ships e move_ship
+ */
+#[derive(Default, Resource, Debug)]
+pub struct BoardIndex {
+    rows: usize,
+    columns: usize,
+    cells: Vec<Entity>, // linha por linha
+    positions: HashMap<Entity, (usize, usize)>,
+    ships: HashMap<Entity, Entity>, // célula -> navio
+}
+
+impl BoardIndex {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        BoardIndex {
+            rows,
+            columns,
+            cells: Vec::with_capacity(rows * columns),
+            positions: HashMap::with_capacity(rows * columns),
+            ships: HashMap::new(),
+        }
+    }
+
+    // as células precisam chegar na ordem das linhas e colunas
+    pub fn push(&mut self, entity: Entity) {
+        let index = self.cells.len();
+        self.positions.insert(entity, (index / self.columns, index % self.columns));
+        self.cells.push(entity);
+    }
+
+    pub fn at(&self, row: usize, column: usize) -> Option<Entity> {
+        if row >= self.rows || column >= self.columns {
+            return None;
+        }
+        self.cells.get(row * self.columns + column).copied()
+    }
+
+    // linha contada a partir do começo do lado
+    pub fn cell(&self, side: &Side, row: usize, column: usize) -> Option<Entity> {
+        let half = self.rows / 2;
+        match side {
+            Side::Player if row < half => self.at(row, column),
+            Side::Enemy if row < half => self.at(row + half, column),
+            _ => None,
+        }
+    }
+
+    // navio que ocupa a célula, dos dois lados
+    pub fn ship_at(&self, cell: Entity) -> Option<Entity> {
+        self.ships.get(&cell).copied()
+    }
+
+    // o navio sai das células antigas e passa a ocupar `cells`
+    pub fn place_ship(&mut self, ship: Entity, cells: &[Entity]) {
+        self.ships.retain(|_, placed| *placed != ship);
+        self.ships.extend(cells.iter().map(|cell| (*cell, ship)));
+    }
+
+    pub fn board_position(&self, entity: Entity) -> Option<(usize, usize)> {
+        self.positions.get(&entity).copied()
+    }
+
+    pub fn position(&self, entity: Entity) -> Option<(Side, usize, usize)> {
+        let (row, column) = self.board_position(entity)?;
+        let half = self.rows / 2;
+        Some(if row < half {
+            (Side::Player, row, column)
+        } else {
+            (Side::Enemy, row - half, column)
+        })
+    }
+
+    // célula sob um ponto do mundo, direto pela grade; o espaço entre as células não conta
+    pub fn cell_at_point(&self, point: Vec2) -> Option<Entity> {
+        let (row, column) = self.nearest(point)?;
+
+        if (point - self.center(row, column)).abs().max_element() > SLOT_SIZE / 2.0 {
+            return None;
+        }
+
+        self.at(row, column)
+    }
+
+    // centro no mundo da célula (linha, coluna) do tabuleiro inteiro
+    pub fn center(&self, row: usize, column: usize) -> Vec2 {
+        let step = SLOT_SIZE + SLOT_SPACE_BETWEEN;
+        (Vec2::new(column as f32, row as f32) - Vec2::new(self.columns as f32, self.rows as f32) / 2.0) * step
+    }
+
+    // (linha, coluna) da célula mais perto de um ponto do mundo, mesmo no espaço entre elas
+    pub fn nearest(&self, point: Vec2) -> Option<(usize, usize)> {
+        let step = SLOT_SIZE + SLOT_SPACE_BETWEEN;
+        // o centro da célula (0, 0) fica em -tamanho/2 do tabuleiro
+        let offset = point + Vec2::new(self.columns as f32, self.rows as f32) * step / 2.0;
+        let column = (offset.x / step).round();
+        let row = (offset.y / step).round();
+
+        if column < 0.0 || row < 0.0 || column >= self.columns as f32 || row >= self.rows as f32 {
+            return None;
+        }
+
+        Some((row as usize, column as usize))
+    }
+}
+
+// navios novos, posicionados ou removidos atualizam o mapa de células do BoardIndex
+pub fn index_ships(
+    mut index: ResMut<BoardIndex>,
+    ships_query: Query<(Entity, &Ship), Changed<Ship>>,
+    mut removed: RemovedComponents<Ship>,
+) {
+    for ship in removed.read() {
+        index.place_ship(ship, &[]);
+    }
+    for (entity, ship) in ships_query.iter() {
+        index.place_ship(entity, &ship.cells);
+    }
+}
+
+// uma jogada registrada, na ordem em que aconteceu (base para o replay da partida)
+#[derive(Clone, Debug)]
+pub struct ShotRecord {
+    pub shooter: Side,
+    pub row: usize,
+    pub column: usize,
+    pub reported: ShotOutcome,
+    pub lie: bool, // marcado na auditoria do fim de jogo se a resposta não bate com a frota revelada
+}
+
+// habilidade usada; `after` é quantos tiros o registro tinha, para o jogo salvo refazer na ordem
+#[derive(Clone, Debug)]
+pub struct AbilityRecord {
+    pub after: usize,
+    pub shooter: Side,
+    pub ability: Ability,
+}
+
+// resposta de um sonar, na linha do tabuleiro inteiro como os tiros; a auditoria confere como confere os tiros
+#[derive(Clone, Debug)]
+pub struct SonarRecord {
+    pub after: usize,
+    pub shooter: Side,
+    pub row: usize,
+    pub column: usize,
+    pub found: bool,
+    pub lie: bool,
+}
+
+/**
+ * navio que andou uma célula no lugar do tiro. (linha, coluna) é a primeira
+ * célula dele antes de andar, no tabuleiro inteiro, e `step` o quanto ela andou
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct MoveRecord {
+    pub after: usize,
+    pub side: Side,
+    pub row: usize,
+    pub column: usize,
+    pub step: (isize, isize),
+}
+
+#[derive(Default, Resource, Debug)]
+pub struct ShotLog {
+    pub records: Vec<ShotRecord>,
+    pub abilities: Vec<AbilityRecord>, // inclusive o sonar, que não deixa tiro no registro
+    pub sonars: Vec<SonarRecord>,
+    pub moves: Vec<MoveRecord>,
+}
+
+impl ShotLog {
+    pub fn record(&mut self, shooter: Side, cell: &Cell, reported: ShotOutcome) {
+        self.records.push(ShotRecord {
+            shooter,
+            row: cell.row,
+            column: cell.column,
+            reported,
+            lie: false,
+        });
+    }
+
+    pub fn record_move(&mut self, side: Side, (row, column): (usize, usize), step: (isize, isize)) {
+        self.moves.push(MoveRecord {
+            after: self.records.len(),
+            side,
+            row,
+            column,
+            step,
+        });
+    }
+
+    pub fn record_sonar(&mut self, shooter: Side, (row, column): (usize, usize), found: bool) {
+        self.sonars.push(SonarRecord {
+            after: self.records.len(),
+            shooter,
+            row,
+            column,
+            found,
+            lie: false,
+        });
+    }
+
+    pub fn record_ability(&mut self, shooter: Side, ability: Ability) {
+        self.abilities.push(AbilityRecord {
+            after: self.records.len(),
+            shooter,
+            ability,
+        });
+    }
+}
+
+// tiro resolvido no tabuleiro, do jogador ou do adversário
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ShotResolved {
+    pub shooter: Side,
+    pub cell: Entity,
+    pub outcome: ShotOutcome,
+}
+
+// partida encerrada, disparado uma vez pela tela de fim de jogo
+#[derive(Event, Clone, Debug)]
+pub struct MatchEnded {
+    pub summary: MatchSummary,
 }
 
 #[derive(Default, Resource, Debug)]
 pub struct GameState {
     pub is_player_turn: bool,
-    pub total_ships_bot: i32,    //quantidade de navios ativos no jogo
-    pub total_ships_player: i32, //quantidade de navios ativos no jogo
-    pub bot_score: i32,
-    pub player_score: i32,
-    pub winner: Option<i32>,
+    pub ships_sunk: [usize; 2], //navios de cada lado já afundados, pelo índice do Side
+    pub winner: Option<Side>,
+    pub variant: RuleVariant,
+    pub resigned: bool, // o jogador desistiu pelo menu de pausa
+    pub started_at: Option<Instant>,
+    pub terrain: TerrainMap, // ilhas e minas, iguais nos dois lados
+    pub penalty: Option<Side>, // caiu numa mina e perde a próxima vez
+    pub ship_moved: Option<Side>, // andou com um navio e o bot adversário ainda não soube
 }
 
 impl GameState {
     pub fn new() -> Self {
         GameState {
             is_player_turn: true,
-            bot_score: 0,
-            player_score: 0,
-            total_ships_bot: 0,
-            total_ships_player: 0,
+            ships_sunk: [0; 2],
             winner: None,
+            variant: RuleVariant::Classic,
+            resigned: false,
+            started_at: None,
+            terrain: TerrainMap::default(),
+            penalty: None,
+            ship_moved: None,
+        }
+    }
+
+    // lado que atira agora
+    pub fn shooter(&self) -> Side {
+        if self.is_player_turn {
+            Side::Player
+        } else {
+            Side::Enemy
+        }
+    }
+
+    // navios do adversário que o lado já afundou
+    pub fn score(&self, side: Side) -> usize {
+        self.ships_sunk[side.opponent().index()]
+    }
+
+    pub fn record_sunk(&mut self, side: Side) {
+        self.ships_sunk[side.index()] += 1;
+    }
+
+    // célula do lado que ainda aceita tiro; com navios em movimento o tiro na água pode ter ficado velho
+    pub fn can_fire(&self, grid: &ShotGrid, side: &Side, row: usize, column: usize) -> bool {
+        let (rows, columns) = grid.size();
+        if row >= rows || column >= columns {
+            return false;
+        }
+
+        match grid.get(side, row, column) {
+            ShotState::Unknown => true,
+            ShotState::Miss => self.variant == RuleVariant::Moving,
+            ShotState::Hit | ShotState::Sunk => false,
         }
     }
+
+    // passa a vez, a menos que o adversário esteja pagando por uma mina
+    pub fn pass_turn(&mut self) {
+        let next = self.shooter().opponent();
+        if self.penalty == Some(next) {
+            self.penalty = None;
+        } else {
+            self.is_player_turn = !self.is_player_turn;
+        }
+    }
+}
+
+// adversário local quando não há motor externo, escolhido pela dificuldade
+#[derive(Resource)]
+pub struct LocalBot {
+    pub bot: Box<dyn BotPlayer + Send + Sync>,
+}
+
+impl LocalBot {
+    pub fn new(difficulty: BotDifficulty, rules: &Rules) -> Self {
+        let mut bot: Box<dyn BotPlayer + Send + Sync> = match difficulty {
+            BotDifficulty::Easy => Box::<RandomBot>::default(),
+            BotDifficulty::Normal => Box::<HunterBot>::default(),
+            BotDifficulty::Hard => Box::new(HunterBot::with_parity()),
+        };
+
+        if let Err(error) = bot.new_game(rules) {
+            warn!("bot local: {error}");
+        }
+
+        LocalBot { bot }
+    }
 }
 
 /**
@@ -43,12 +459,39 @@ impl GameState {
  */
 impl Plugin for Board {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, render_board);
-        app.add_systems(Update, handle_click);
-        app.add_systems(Update, bot_turn);
-        app.add_systems(Update, show_victory_screen);
+        app.add_systems(OnEnter(AppState::InGame), (start_match, render_board).chain());
+        app.add_systems(Update, index_ships.before(handle_click).before(bot_turn));
+        app.add_systems(
+            Update,
+            handle_click
+                .run_if(in_state(PauseState::Running))
+                .run_if(animations_finished) // a vez só passa depois que o tiro cai
+                .run_if(not(resource_exists::<ReplaySession>)),
+        );
+        app.add_systems(
+            Update,
+            bot_turn
+                .run_if(in_state(PauseState::Running))
+                .run_if(animations_finished)
+                .run_if(not(resource_exists::<NetworkSession>)) // em rede o adversário é remoto
+                .run_if(not(resource_exists::<SpectatorSession>))
+                .run_if(not(resource_exists::<Hotseat>)) // com dois jogadores o outro lado também clica
+                .run_if(not(resource_exists::<ReplaySession>)),
+        );
+        app.add_systems(
+            Update,
+            show_victory_screen
+                .run_if(in_state(AppState::InGame))
+                .run_if(animations_finished)
+                .run_if(explosions_finished),
+        );
+
+        app.add_event::<ShotResolved>();
+        app.add_event::<MatchEnded>();
 
-        app.insert_resource(ClickedCells::default()); //adicionando struct como recurso global do bevy
+        app.insert_resource(ShotGrid::default()); //adicionando struct como recurso global do bevy
+        app.insert_resource(BoardIndex::default());
+        app.insert_resource(ShotLog::default());
         app.insert_resource(GameState::new());
     }
 }
@@ -57,99 +500,473 @@ impl Plugin for Board {
 pub const SLOT_SIZE: f32 = 60.0;
 pub const SLOT_SPACE_BETWEEN: f32 = 4.0;
 
-pub const ROWS: usize = 10;
-pub const COLUMNS: usize = 10;
-pub const ENEMY_CELL_COLOR: Color = Color::srgb(0.0, 0.2, 0.4);
-pub const PLAYER_CELL_COLOR: Color = Color::srgb(0.4, 0.7, 1.0);
+// cada partida começa do zero, com as configurações escolhidas no menu
+pub fn start_match(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    mut game_state: ResMut<GameState>,
+    mut shot_grid: ResMut<ShotGrid>,
+    mut shot_log: ResMut<ShotLog>,
+    catalog: Res<ShipCatalog>,
+) {
+    *game_state = GameState::new();
+    game_state.variant = settings.variant;
+    game_state.started_at = Some(Instant::now());
+    game_state.terrain = settings.map.terrain();
+    *shot_grid = ShotGrid::new(settings.rows() / 2, settings.columns());
+    *shot_log = ShotLog::default();
+
+    commands.insert_resource(LocalBot::new(settings.difficulty, &settings.rules(&catalog)));
+}
 
-fn render_board(mut commands: Commands) {
-    for row in 0..ROWS {
-        for column in 0..COLUMNS {
+pub fn render_board(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    game_state: Res<GameState>,
+    theme: Res<Theme>,
+    mut index: ResMut<BoardIndex>,
+) {
+    let rows = settings.rows();
+    let columns = settings.columns();
+    *index = BoardIndex::new(rows, columns);
+
+    for row in 0..rows {
+        for column in 0..columns {
             let x = (column as f32) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                - (COLUMNS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0);
+                - (columns as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0);
             let y = (row as f32) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                - (ROWS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0);
-
-            commands.spawn((
-                Sprite {
-                    color: if row < ROWS / 2 {
-                        PLAYER_CELL_COLOR
-                    } else {
-                        ENEMY_CELL_COLOR
+                - (rows as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0);
+
+            let (side, side_row) = if row < rows / 2 {
+                (Side::Player, row)
+            } else {
+                (Side::Enemy, row - rows / 2)
+            };
+            let cell = Cell {
+                column,
+                row,
+                side,
+                terrain: game_state.terrain.get(side_row, column),
+            };
+
+            let entity = commands
+                .spawn((
+                    Sprite {
+                        color: theme.palette().ground(&cell),
+                        custom_size: Some(Vec2::new(SLOT_SIZE, SLOT_SIZE)),
+                        ..Default::default()
+                    },
+                    Transform {
+                        translation: Vec3::new(x, y, Vec3::default().z - 1.0),
+                        ..Default::default()
                     },
-                    custom_size: Some(Vec2::new(SLOT_SIZE, SLOT_SIZE)),
-                    ..Default::default()
-                },
-                Transform {
-                    translation: Vec3::new(x, y, Vec3::default().z - 1.0),
-                    ..Default::default()
-                },
-                Cell {
-                    column,
-                    row,
-                    marked: false,
-                },
-                if row < ROWS / 2 {
-                    CellSide::Player
-                } else {
-                    CellSide::Enemy
-                },
-            ));
+                    cell,
+                    StateScoped(AppState::InGame),
+                ))
+                .id();
+            index.push(entity);
         }
     }
 }
 
-fn bot_turn(
+/**
+ * habilidade de `shooter` com o alvo (linha, coluna) no lado adversário. os
+ * tiros são resolvidos célula a célula como tiros comuns, com registro e
+ * evento cada um, e a vez passa uma vez só no fim. None se o lado não tem
+ * a habilidade ou se ela não atingiria nada
+ */
+pub fn use_ability(
+    ability: Ability,
+    shooter: Side,
+    (row, column): (usize, usize),
+    query: &mut Query<(Entity, &mut Sprite, &Cell)>,
+    fleet: &mut Fleet,
+    shot_grid: &mut ShotGrid,
+    index: &BoardIndex,
+    game_state: &mut ResMut<GameState>,
+    shot_log: &mut ShotLog,
+    shots: &mut EventWriter<ShotResolved>,
+    palette: &Palette,
+    catalog: &ShipCatalog,
+) -> Option<AbilityOutcome> {
+    let target = shooter.opponent();
+    let ship_entity = fleet
+        .iter()
+        .find(|(_, ship)| ship.side == shooter && ship.ability(catalog) == Some(ability))
+        .map(|(entity, _)| entity)?;
+
+    let (rows, columns) = shot_grid.size();
+    let area = ability.area(row, column, rows, columns);
+    // ilhas contam como já atacadas: não levam tiro e nunca escondem navio
+    let fired = |cell: &(usize, usize)| {
+        shot_grid.is_known(&target, cell.0, cell.1) || game_state.terrain.get(cell.0, cell.1) == Terrain::Island
+    };
+    let occupied = |cell: &(usize, usize)| {
+        index
+            .cell(&target, cell.0, cell.1)
+            .is_some_and(|entity| index.ship_at(entity).is_some())
+    };
+
+    let outcome = if ability == Ability::Sonar {
+        let found = Ability::detects(&area, fired, occupied);
+        if let Some(position) = index.cell(&target, row, column).and_then(|cell| index.board_position(cell)) {
+            shot_log.record_sonar(shooter, position, found);
+        }
+        AbilityOutcome::Sonar(found)
+    } else {
+        let strikes = ability.strikes(&area, fired, occupied);
+        if strikes.is_empty() {
+            return None;
+        }
+
+        // a penalidade pendente do adversário só vale na passagem de vez do fim
+        let pending = game_state.penalty.take();
+        let mut results = Vec::new();
+        for (row, column) in strikes {
+            let Some(entity) = index.cell(&target, row, column) else {
+                continue;
+            };
+            let Ok((_, mut sprite, cell)) = query.get_mut(entity) else {
+                continue;
+            };
+
+            // mark pinta com a cor de quem está na vez e pode passá-la a cada tiro
+            game_state.is_player_turn = shooter == Side::Player;
+            let outcome = cell.mark(&mut sprite, fleet, entity, shot_grid, index, game_state, palette);
+            shot_log.record(shooter, cell, outcome);
+            shots.send(ShotResolved {
+                shooter,
+                cell: entity,
+                outcome,
+            });
+            results.push((row, column, outcome));
+        }
+
+        // se o atirador também caiu numa mina, as duas penalidades se anulam
+        game_state.penalty = match game_state.penalty {
+            Some(_) if pending.is_some() => None,
+            mined => mined.or(pending),
+        };
+        AbilityOutcome::Shots(results)
+    };
+
+    if let Some(ship) = fleet.get_mut(ship_entity) {
+        ship.ability_used = true;
+    }
+    shot_log.record_ability(shooter, ability);
+    game_state.is_player_turn = shooter == Side::Player;
+    game_state.pass_turn();
+
+    Some(outcome)
+}
+
+/**
+ * navios em movimento: células para onde o navio anda uma célula no eixo
+ * dele e o deslocamento do sprite. None se o navio já levou tiro ou se o
+ * destino sai do lado, cai em ilha ou mina ou em cima de outro navio
+ */
+pub fn planned_move(
+    ship_entity: Entity,
+    forward: bool,
+    fleet: &Fleet,
+    shot_grid: &ShotGrid,
+    index: &BoardIndex,
+    terrain: &TerrainMap,
+) -> Option<(Vec<Entity>, Vec2)> {
+    let ship = fleet.get(ship_entity)?;
+    let direction = fleet.direction(ship_entity)?;
+
+    let positions: Vec<(usize, usize)> = ship
+        .cells
+        .iter()
+        .filter_map(|cell| index.position(*cell))
+        .map(|(_, row, column)| (row, column))
+        .collect();
+    let damaged = positions
+        .iter()
+        .any(|(row, column)| matches!(shot_grid.get(&ship.side, *row, *column), ShotState::Hit | ShotState::Sunk));
+    if positions.is_empty() || damaged {
+        return None;
+    }
+
+    let cells = moved_cells(&positions, *direction == ShipDirection::Vertical, forward)?
+        .into_iter()
+        .map(|(row, column)| {
+            terrain
+                .is_water(row, column)
+                .then(|| index.cell(&ship.side, row, column))
+                .flatten()
+        })
+        .collect::<Option<Vec<Entity>>>()?;
+
+    let blocked = cells
+        .iter()
+        .any(|cell| index.ship_at(*cell).is_some_and(|other| other != ship_entity));
+    if blocked {
+        return None;
+    }
+
+    let center = |cell: Entity| index.board_position(cell).map(|(row, column)| index.center(row, column));
+    let offset = center(cells[0])? - center(ship.cells[0])?;
+
+    Some((cells, offset))
+}
+
+// anda com o navio já planejado por planned_move, no lugar do tiro da vez
+pub fn move_ship(
+    ship_entity: Entity,
+    (cells, offset): (Vec<Entity>, Vec2),
+    fleet: &mut Fleet,
+    motion_query: &mut Query<(&mut Transform, &ShipDirection), With<Ship>>,
+    game_state: &mut GameState,
+    index: &mut BoardIndex,
+    shot_log: &mut ShotLog,
+) {
+    if let Some(ship) = fleet.get_mut(ship_entity) {
+        // registrado com os tiros, para o jogo salvo andar com o navio na mesma ordem
+        let first = |cells: &[Entity]| cells.first().and_then(|cell| index.board_position(*cell));
+        if let (Some(from), Some(to)) = (first(&ship.cells), first(&cells)) {
+            let step = (to.0 as isize - from.0 as isize, to.1 as isize - from.1 as isize);
+            shot_log.record_move(ship.side, from, step);
+        }
+
+        // na hora, para o tiro seguinte no mesmo frame já achar o navio no lugar novo
+        index.place_ship(ship_entity, &cells);
+        ship.cells = cells;
+        game_state.ship_moved = Some(ship.side);
+    }
+    if let Ok((mut transform, _)) = motion_query.get_mut(ship_entity) {
+        transform.translation += offset.extend(0.0);
+    }
+
+    game_state.pass_turn();
+}
+
+// tamanho do navio afundado no tiro, para o bot saber quantos acertos descartar
+fn sunk_length(
+    outcome: ShotOutcome,
+    entity: Entity,
+    fleet: &Fleet,
+    index: &BoardIndex,
+) -> Option<usize> {
+    (outcome == ShotOutcome::Sunk)
+        .then(|| index.ship_at(entity))
+        .flatten()
+        .and_then(|ship| fleet.get(ship))
+        .map(|ship| ship.cells.len())
+}
+
+pub fn bot_turn(
     mut game_state: ResMut<GameState>,
-    mut query: Query<(Entity, &mut Cell, &mut Sprite)>,
-    mut ships_query: Query<(Entity, &mut Ship)>,
-    mut clicked_cells: ResMut<ClickedCells>,
+    mut query: Query<(Entity, &mut Sprite, &Cell)>,
+    mut fleet: Fleet,
+    mut shot_grid: ResMut<ShotGrid>,
+    mut shot_log: ResMut<ShotLog>,
+    mut external: Option<ResMut<ExternalOpponent>>,
+    mut local: Option<ResMut<LocalBot>>,
+    mut shots: EventWriter<ShotResolved>,
+    mut motion_query: Query<(&mut Transform, &ShipDirection), With<Ship>>,
+    theme: Res<Theme>,
+    mut index: ResMut<BoardIndex>,
+    catalog: Res<ShipCatalog>,
 ) {
-    if !game_state.is_player_turn {
-        let mut available_cells: Vec<Entity> = query
-            .iter_mut()
-            .filter(|(_, cell, _)| cell.row < (ROWS / 2) && !cell.marked) // Lado do jogador (linha maior que ROWS / 2)
-            .filter_map(|(entity, cell, _)| {
-                if !cell.marked && !clicked_cells.cells.contains(&entity) {
-                    //fazendo com que o bot não escolha celulas já clicadas
-                    Some(entity)
-                } else {
-                    None
-                }
-            })
-            .collect();
+    if !game_state.is_player_turn && game_state.winner.is_none() {
+        // o motor externo tem prioridade sobre o bot local
+        let mut bot: Option<&mut dyn BotPlayer> = match (external.as_mut(), local.as_mut()) {
+            (Some(opponent), _) => Some(&mut opponent.bot),
+            (None, Some(local)) => Some(local.bot.as_mut()),
+            (None, None) => None,
+        };
 
-        let mut rng = rand::thread_rng();
-        available_cells.shuffle(&mut rng);
+        // motor externo ainda pensando no tiro pedido num frame anterior: só espera a resposta
+        let thinking = bot.as_ref().is_some_and(|bot| bot.is_thinking());
 
-        if let Some(target_entity) = available_cells.first() {
-            for (entity, mut cell, mut sprite) in query.iter_mut() {
-                if entity == *target_entity {
-                    cell.mark(
-                        &mut sprite,
-                        &mut ships_query,
+        // o jogador andou com um navio: os tiros na água do bot podem ter ficado velhos
+        if game_state.ship_moved.take() == Some(Side::Player) {
+            if let Some(bot) = bot.as_mut() {
+                bot.opponent_moved();
+            }
+        }
+
+        // com navios em movimento o bot pode andar com um navio no lugar do tiro
+        if game_state.variant == RuleVariant::Moving && !thinking {
+            let enemy: Vec<Entity> = fleet
+                .iter()
+                .filter(|(_, ship)| ship.side == Side::Enemy)
+                .map(|(entity, _)| entity)
+                .collect();
+            let mut plans = Vec::new();
+            for (ship, entity) in enemy.into_iter().enumerate() {
+                for forward in [true, false] {
+                    let plan = planned_move(
                         entity,
-                        &mut clicked_cells.cells,
-                        &mut game_state,
+                        forward,
+                        &fleet,
+                        &shot_grid,
+                        &index,
+                        &game_state.terrain,
                     );
+                    if let Some(plan) = plan {
+                        plans.push(((ship, forward), entity, plan));
+                    }
+                }
+            }
+
+            let moves: Vec<(usize, bool)> = plans.iter().map(|(chosen, _, _)| *chosen).collect();
+            let chosen = bot
+                .as_mut()
+                .filter(|_| !moves.is_empty())
+                .and_then(|bot| bot.maneuver(&moves));
+
+            if let Some((_, entity, plan)) = plans.into_iter().find(|(valid, _, _)| Some(*valid) == chosen) {
+                move_ship(
+                    entity,
+                    plan,
+                    &mut fleet,
+                    &mut motion_query,
+                    &mut game_state,
+                    &mut index,
+                    &mut shot_log,
+                );
+                return;
+            }
+        }
+
+        // nas regras avançadas o bot pode trocar o tiro por uma habilidade dos navios dele
+        let available: Vec<Ability> = fleet
+            .iter()
+            .filter(|(_, ship)| ship.side == Side::Enemy)
+            .filter_map(|(_, ship)| ship.ability(&catalog))
+            .collect();
+        let may_use_ability = game_state.variant == RuleVariant::Advanced && !available.is_empty() && !thinking;
 
-                    break;
+        if let Some(bot) = bot.as_mut().filter(|_| may_use_ability) {
+            if let Some((ability, row, column)) = bot.ability(&available) {
+                let outcome = use_ability(
+                    ability,
+                    Side::Enemy,
+                    (row, column),
+                    &mut query,
+                    &mut fleet,
+                    &mut shot_grid,
+                    &index,
+                    &mut game_state,
+                    &mut shot_log,
+                    &mut shots,
+                    theme.palette(),
+                    &catalog,
+                );
+
+                match outcome {
+                    Some(AbilityOutcome::Sonar(found)) => bot.sonar(row, column, found),
+                    Some(AbilityOutcome::Shots(results)) => {
+                        for (row, column, outcome) in results {
+                            let entity = index.cell(&Side::Player, row, column);
+                            let sunk = entity.and_then(|entity| sunk_length(outcome, entity, &fleet, &index));
+                            bot.result(row, column, outcome, sunk);
+                        }
+                    }
+                    // habilidade inválida: o bot atira normalmente
+                    None => warn!("habilidade inválida do bot {}: {ability:?}", bot.name()),
+                }
+
+                if game_state.is_player_turn || game_state.winner.is_some() {
+                    return;
                 }
             }
         }
+
+        // o motor externo responde nos próximos frames, sem travar o jogo enquanto pensa
+        let reply = match bot.as_mut().map(|bot| bot.poll_fire()) {
+            Some(None) => return,
+            Some(Some(reply)) => Some(reply),
+            None => None,
+        };
+
+        // tiro inválido ou fora do tempo cai no alvo aleatório
+        let chosen_cell = bot.as_mut().zip(reply).and_then(|(bot, reply)| match reply {
+            Ok((row, column)) => index
+                .cell(&Side::Player, row, column)
+                .filter(|_| game_state.can_fire(&shot_grid, &Side::Player, row, column))
+                .filter(|_| game_state.terrain.get(row, column) != Terrain::Island)
+                .or_else(|| {
+                    warn!("tiro inválido do bot {}: {row} {column}", bot.name());
+                    None
+                }),
+            Err(error) => {
+                warn!("bot {}: {error}", bot.name());
+                None
+            }
+        });
+
+        let target_entity = chosen_cell.or_else(|| {
+            //fazendo com que o bot não escolha celulas já clicadas, nem ilhas e minas
+            let available_cells: Vec<Entity> = shot_grid
+                .unknown(&Side::Player)
+                .filter(|(row, column)| game_state.terrain.is_water(*row, *column))
+                .filter_map(|(row, column)| index.cell(&Side::Player, row, column))
+                .collect();
+
+            available_cells.choose(&mut rand::thread_rng()).copied()
+        });
+
+        let Some(entity) = target_entity else {
+            return;
+        };
+        let Ok((_, mut sprite, cell)) = query.get_mut(entity) else {
+            return;
+        };
+
+        let outcome = cell.mark(
+            &mut sprite,
+            &mut fleet,
+            entity,
+            &mut shot_grid,
+            &index,
+            &mut game_state,
+            theme.palette(),
+        );
+        shot_log.record(Side::Enemy, cell, outcome);
+        shots.send(ShotResolved {
+            shooter: Side::Enemy,
+            cell: entity,
+            outcome,
+        });
+
+        if let Some(bot) = bot.as_mut() {
+            let sunk = sunk_length(outcome, entity, &fleet, &index);
+            bot.result(cell.row, cell.column, outcome, sunk);
+        }
     }
 }
 
-fn handle_click(
-    mut query: Query<(Entity, &mut Sprite, &mut Cell, &Transform)>,
+pub fn handle_click(
+    mut query: Query<(Entity, &mut Sprite, &Cell)>,
     mut mouse_button_input: EventReader<MouseButtonInput>,
     camera_query: Single<(&Camera, &GlobalTransform)>,
     windows: Query<&Window>,
-    mut ships_query: Query<(Entity, &mut Ship)>,
-    mut clicked_cells: ResMut<ClickedCells>,
+    mut fleet: Fleet,
+    mut shot_grid: ResMut<ShotGrid>,
     mut game_state: ResMut<GameState>,
+    mut shot_log: ResMut<ShotLog>,
+    mut network: Option<ResMut<NetworkSession>>,
+    mut shots: EventWriter<ShotResolved>,
+    (mut armed, mut sonar): (ResMut<ArmedAbility>, ResMut<SonarReport>),
+    mut motion_query: Query<(&mut Transform, &ShipDirection), With<Ship>>,
+    mut notices: EventWriter<Notice>,
+    (theme, catalog): (Res<Theme>, Res<ShipCatalog>),
+    mut index: ResMut<BoardIndex>,
+    hotseat: Option<Res<Hotseat>>,
 ) {
-    if game_state.is_player_turn {
+    // com dois jogadores no mesmo computador os dois lados clicam, cada um na sua vez
+    let shooter = game_state.shooter();
+    let target = shooter.opponent();
+    let playing = match hotseat.as_ref() {
+        Some(hotseat) => hotseat.shown == Some(shooter), // a tela de troca ainda cobre o tabuleiro
+        None => game_state.is_player_turn,
+    };
+
+    if playing && game_state.winner.is_none() {
         for event in mouse_button_input.read() {
             if event.button == MouseButton::Left && event.state == ButtonState::Pressed {
                 let (camera, camera_transform) = *camera_query;
@@ -158,93 +975,284 @@ fn handle_click(
                     return;
                 };
 
-                let Some(cursor_position) = window.cursor_position() else {
+                let Some(point) = cursor_world_position(window, camera, camera_transform) else {
                     return;
                 };
 
-                let Ok(point) = camera.viewport_to_world_2d(camera_transform, cursor_position)
-                else {
-                    return;
+                // a célula sai direto da grade, sem percorrer o tabuleiro
+                let Some(entity) = index.cell_at_point(point) else {
+                    continue;
+                };
+                let Some((side, row, column)) = index.position(entity) else {
+                    continue;
                 };
 
-                for (entity, mut sprite, mut cell, _) in query.iter_mut() {
-                    let column = cell.column;
-                    let row = cell.row;
-
-                    // Cálculo da posição da célula no tabuleiro
-                    let x = (column as f32) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                        - (COLUMNS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0);
-                    let y = (row as f32) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                        - (ROWS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0);
-
-                    // area da celula (precisa refinar)
-                    let cell_area = Rect {
-                        min: Vec2::new(x - SLOT_SIZE / 2.0, y - SLOT_SIZE / 2.0),
-                        max: Vec2::new(x + SLOT_SIZE / 2.0, y + SLOT_SIZE / 2.0),
-                    };
-
-                    if cell_area.contains(point.xy()) {
-                        if cell.row > (ROWS / 2) - 1 && !cell.marked {
-                            cell.mark(
-                                &mut sprite,
-                                &mut ships_query,
-                                entity,
-                                &mut clicked_cells.cells,
+                // habilidade armada na barra: o clique no lado inimigo é o alvo dela
+                if side == target && network.is_none() {
+                    if let Some(ability) = armed.0.take() {
+                        let outcome = use_ability(
+                            ability,
+                            shooter,
+                            (row, column),
+                            &mut query,
+                            &mut fleet,
+                            &mut shot_grid,
+                            &index,
+                            &mut game_state,
+                            &mut shot_log,
+                            &mut shots,
+                            theme.palette(),
+                            &catalog,
+                        );
+
+                        if let Some(AbilityOutcome::Sonar(found)) = outcome {
+                            let (rows, columns) = shot_grid.size();
+                            *sonar = SonarReport {
+                                found: Some(found),
+                                area: ability
+                                    .area(row, column, rows, columns)
+                                    .into_iter()
+                                    .filter_map(|(row, column)| index.cell(&target, row, column))
+                                    .collect(),
+                            };
+                        }
+                        continue;
+                    }
+                }
+
+                // navios em movimento: clicar num navio próprio anda com ele para o lado do clique
+                if side == shooter && game_state.variant == RuleVariant::Moving && network.is_none() {
+                    let ship = index
+                        .ship_at(entity)
+                        .and_then(|ship_entity| fleet.get(ship_entity).map(|ship| (ship_entity, ship.cells.clone())));
+
+                    if let Some((ship_entity, cells)) = ship {
+                        let positions: Vec<(usize, usize)> =
+                            cells.iter().filter_map(|cell| index.board_position(*cell)).collect();
+                        let middle = positions
+                            .iter()
+                            .map(|(row, column)| index.center(*row, *column))
+                            .sum::<Vec2>()
+                            / positions.len().max(1) as f32;
+                        let vertical = fleet.direction(ship_entity) == Some(&ShipDirection::Vertical);
+                        let forward = if moves_vertically(&positions, vertical) {
+                            point.y >= middle.y
+                        } else {
+                            point.x >= middle.x
+                        };
+
+                        match planned_move(
+                            ship_entity,
+                            forward,
+                            &fleet,
+                            &shot_grid,
+                            &index,
+                            &game_state.terrain,
+                        ) {
+                            Some(plan) => move_ship(
+                                ship_entity,
+                                plan,
+                                &mut fleet,
+                                &mut motion_query,
                                 &mut game_state,
-                            );
-                        } else if cell.marked {
-                            println!("celula já marcada posicao");
+                                &mut index,
+                                &mut shot_log,
+                            ),
+                            None => {
+                                notices.send(Notice::new("notice-ship-blocked"));
+                            }
+                        }
+                        continue;
+                    }
+                }
+
+                let Ok((_, mut sprite, cell)) = query.get_mut(entity) else {
+                    continue;
+                };
+                let known = !game_state.can_fire(&shot_grid, &side, row, column);
+
+                if cell.terrain == Terrain::Island {
+                    notices.send(Notice::new("notice-island"));
+                    continue;
+                }
+
+                if side == target && !known {
+                    // em rede quem resolve o tiro é o servidor, que responde com SHOT
+                    if let Some(session) = network.as_mut() {
+                        if session.started {
+                            session.send(&ClientMessage::Fire {
+                                row,
+                                column,
+                            });
+                            game_state.is_player_turn = false;
                         } else {
-                            // println!("não é possivel selecionar essa celula");
+                            notices.send(Notice::new("notice-waiting-start"));
                         }
+                        continue;
                     }
+
+                    let outcome = cell.mark(
+                        &mut sprite,
+                        &mut fleet,
+                        entity,
+                        &mut shot_grid,
+                        &index,
+                        &mut game_state,
+                        theme.palette(),
+                    );
+                    shot_log.record(shooter, cell, outcome);
+                    shots.send(ShotResolved {
+                        shooter,
+                        cell: entity,
+                        outcome,
+                    });
+                } else if known {
+                    notices.send(Notice::new("notice-already-marked"));
                 }
             }
         }
     }
 }
 
-fn show_victory_screen(
+// caixa do resultado; enquanto ela existe a partida já foi encerrada e contabilizada
+#[derive(Component)]
+pub struct VictoryBox;
+
+// texto do resultado sem a auditoria da frota, que pode chegar depois pela rede
+#[derive(Component)]
+pub struct VictoryText {
+    pub lines: Vec<String>,
+}
+
+// estatísticas da partida do ponto de vista do jogador, a partir do registro de tiros
+fn match_summary(
+    game_state: &GameState,
+    shot_log: &ShotLog,
+    mode: MatchMode,
+    difficulty: Option<BotDifficulty>,
+    fleet: &Fleet,
+    index: &BoardIndex,
+) -> MatchSummary {
+    let player_shots: Vec<&ShotRecord> = shot_log.records.iter().filter(|record| record.shooter == Side::Player).collect();
+
+    let mut streak = 0;
+    let mut longest_streak = 0;
+    for record in &player_shots {
+        if record.reported == ShotOutcome::Miss {
+            streak = 0;
+        } else {
+            streak += 1;
+            longest_streak = longest_streak.max(streak);
+        }
+    }
+
+    // o tipo do navio afundado vem do navio que ocupa a célula do tiro (em rede ele não existe aqui)
+    let sunk = player_shots
+        .iter()
+        .filter(|record| record.reported == ShotOutcome::Sunk)
+        .filter_map(|record| {
+            let entity = index.at(record.row, record.column)?;
+            fleet.get(index.ship_at(entity)?).map(|ship| ship.r#type.clone())
+        })
+        .collect();
+
+    MatchSummary {
+        mode,
+        difficulty,
+        won: game_state.winner == Some(Side::Player),
+        resigned: game_state.resigned,
+        shots: player_shots.len() as u32,
+        hits: player_shots
+            .iter()
+            .filter(|record| record.reported != ShotOutcome::Miss)
+            .count() as u32,
+        longest_streak,
+        seconds: game_state
+            .started_at
+            .map_or(0, |started_at| started_at.elapsed().as_secs()),
+        sunk,
+    }
+}
+
+pub fn show_victory_screen(
     game_state: ResMut<GameState>,
     mut commands: Commands,
-    ships_query: Query<(Entity, &mut Ship)>,
-    cells_query: Query<(Entity, &mut Cell)>,
+    fleet: Fleet,
+    shot_log: Res<ShotLog>,
+    settings: Res<GameSettings>,
+    mut profiles: ResMut<Profiles>,
+    network: Option<Res<NetworkSession>>,
+    external: Option<Res<ExternalOpponent>>,
+    victory_query: Query<(), With<VictoryBox>>,
+    mut match_ended: EventWriter<MatchEnded>,
+    locale: Res<Locale>,
+    index: Res<BoardIndex>,
+    (hotseat, replay): (Option<Res<Hotseat>>, Option<Res<ReplaySession>>),
 ) {
-    if game_state.winner.is_some() {
-        let winner = game_state.winner.unwrap();
+    if !victory_query.is_empty() {
+        return;
+    }
 
-        for (entity, _ship) in ships_query.iter() {
-            commands.entity(entity).despawn();
-        }
+    if let Some(winner) = game_state.winner {
+        let (mode, difficulty) = if network.is_some() {
+            (MatchMode::Network, None)
+        } else if external.is_some() {
+            (MatchMode::External, None)
+        } else {
+            (MatchMode::Bot, Some(settings.difficulty))
+        };
 
-        for (entity, _ship) in cells_query.iter() {
-            commands.entity(entity).despawn();
+        // o perfil é de um jogador só: a partida de dois e o replay não contam
+        if hotseat.is_none() && replay.is_none() {
+            let summary = match_summary(&game_state, &shot_log, mode, difficulty, &fleet, &index);
+            profiles.active_mut().record(&summary);
+            profiles.save();
+            match_ended.send(MatchEnded { summary });
         }
 
+        // o tabuleiro fica: reveal_ships mostra a frota inimiga que sobrou, com destaque
+        let missed = fleet
+            .iter()
+            .filter(|(_, ship)| ship.side == Side::Enemy && !ship.sunk)
+            .count();
 
-        let alvo = if winner == 0 {
-            "jogador"
-        } else {
-            "Bot"
+        let alvo = match (winner, hotseat.is_some()) {
+            (side, true) => locale.get(Hotseat::player_key(side)),
+            (Side::Player, false) => locale.get("victory-player"),
+            (Side::Enemy, false) => locale.get("victory-bot"),
         };
 
-        let textofinal  = format!("vencedor: {alvo}");
+        let mut linhas = vec![locale.format("victory-winner", &[("winner", &alvo)])];
+
+        if game_state.resigned {
+            linhas.push(locale.get("victory-resigned"));
+        }
+        // com dois jogadores não há um lado "inimigo" para contar
+        if missed > 0 && hotseat.is_none() {
+            linhas.push(locale.format("victory-missed", &[("count", &missed)]));
+        }
+
+        let textofinal = linhas.join("\n");
 
-        let box_size = Vec2::new(400.0, 100.0);
-        let box_position = Vec2::new(0.0, 100.0);
+        // na margem à direita do tabuleiro, para não cobrir a frota revelada
+        let box_size = Vec2::new(SIDE_MARGIN - 20.0, 240.0);
+        let box_position = Vec2::new((board_extent(&settings).x + SIDE_MARGIN) / 2.0, 0.0);
         let slightly_smaller_text_font = TextFont {
-            font_size: 25.0,
+            font_size: 20.0,
             ..default()
         };
 
         commands
             .spawn((
                 Sprite::from_color(Color::srgb(0.25, 0.25, 0.75), box_size),
-                Transform::from_translation(box_position.extend(0.0)),
+                Transform::from_translation(box_position.extend(10.0)), // acima do fogo e da fumaça
+                VictoryBox,
+                StateScoped(AppState::InGame),
             ))
             .with_children(|builder| {
                 builder.spawn((
                     Text2d::new(textofinal),
+                    VictoryText { lines: linhas },
                     slightly_smaller_text_font.clone(),
                     TextLayout::new(JustifyText::Left, LineBreak::WordBoundary),
                     // Wrap text in the rectangle
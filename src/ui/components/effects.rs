@@ -0,0 +1,362 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::logic::{
+    cell::{Cell, Side},
+    shot::ShotOutcome,
+};
+
+use super::{
+    board::{BoardIndex, ShotGrid, ShotResolved, ShotState, SLOT_SIZE, SLOT_SPACE_BETWEEN},
+    main_menu::AppState,
+    settings::GameSettings,
+    theme::Theme,
+};
+
+pub struct EffectsPlugin;
+
+const PROJECTILE_SECONDS: f32 = 0.45;
+const SINKING_SECONDS: f32 = 1.4;
+const PROJECTILE_COLOR: Color = Color::srgb(0.1, 0.1, 0.1);
+
+/**
+ * tiro em voo do atirador até a célula. a célula só mostra o resultado
+ * quando ele chega, e enquanto houver projétil no ar (ou navio afundando)
+ * a vez não passa: veja animations_finished
+ */
+#[derive(Component)]
+pub struct Projectile {
+    pub target: Entity,
+    from: Vec3,
+    to: Vec3,
+    timer: Timer,
+    outcome: ShotOutcome,
+    shooter: Side,
+}
+
+// projétil chegou e o resultado do tiro apareceu na célula
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ShotLanded {
+    pub shooter: Side,
+    pub outcome: ShotOutcome,
+}
+
+// X ou ponto por cima da célula, filho dela
+#[derive(Component)]
+struct ShotMarker(ShotState);
+
+#[derive(Component)]
+pub struct Particle {
+    velocity: Vec2,
+    timer: Timer,
+    size: f32,
+    grows: bool, // fumaça cresce enquanto some, faísca e água só diminuem
+}
+
+// fogo que fica na célula atingida
+#[derive(Component)]
+struct Fire {
+    phase: f32,
+}
+
+// navio afundando: espera o projétil chegar, depois aparece e desce um pouco (os segmentos escurecem sozinhos)
+#[derive(Component)]
+pub struct Sinking {
+    delay: Timer,
+    timer: Timer,
+    translation: Vec3,
+    rotation: Quat,
+    smoke: Timer,
+}
+
+impl Sinking {
+    pub fn new(transform: &Transform) -> Self {
+        Sinking {
+            delay: Timer::from_seconds(PROJECTILE_SECONDS, TimerMode::Once),
+            timer: Timer::from_seconds(SINKING_SECONDS, TimerMode::Once),
+            translation: transform.translation,
+            rotation: transform.rotation,
+            smoke: Timer::from_seconds(0.2, TimerMode::Repeating),
+        }
+    }
+
+    // 0 antes de começar a afundar, 1 quando já é destroço
+    pub fn progress(&self) -> f32 {
+        self.timer.fraction()
+    }
+}
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ShotLanded>().add_systems(
+            Update,
+            (
+                launch_projectiles,
+                fly_projectiles,
+                draw_markers,
+                sink_ships,
+                animate_particles,
+                flicker_fires,
+            )
+                .chain()
+                .run_if(in_state(AppState::InGame)),
+        );
+    }
+}
+
+// condição de execução para quem precisa esperar as animações (troca de vez, fim de jogo)
+pub fn animations_finished(
+    projectiles_query: Query<(), With<Projectile>>,
+    sinking_query: Query<&Sinking>,
+) -> bool {
+    // o afundar só começa depois do atraso, então os dois timers precisam ter terminado
+    projectiles_query.is_empty()
+        && sinking_query
+            .iter()
+            .all(|sinking| sinking.delay.finished() && sinking.timer.finished())
+}
+
+// para o fim de jogo: a última explosão também precisa sumir (a fumaça pode continuar)
+pub fn explosions_finished(particles_query: Query<&Particle>) -> bool {
+    particles_query.iter().all(|particle| particle.grows)
+}
+
+fn launch_projectiles(
+    mut commands: Commands,
+    mut shots: EventReader<ShotResolved>,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    mut cells_query: Query<(&Transform, &mut Sprite, &Cell)>,
+) {
+    // o jogador atira de baixo do tabuleiro, o adversário de cima
+    let edge = (settings.rows() as f32 / 2.0 + 1.0) * (SLOT_SIZE + SLOT_SPACE_BETWEEN);
+
+    for shot in shots.read() {
+        let Ok((transform, mut sprite, cell)) = cells_query.get_mut(shot.cell) else {
+            continue;
+        };
+
+        // o resultado fica escondido até o projétil chegar
+        sprite.color = theme.palette().ground(cell);
+
+        let from = Vec3::new(
+            0.0,
+            match shot.shooter {
+                Side::Player => -edge,
+                Side::Enemy => edge,
+            },
+            5.0,
+        );
+        let to = transform.translation.with_z(5.0);
+
+        commands.spawn((
+            Sprite::from_color(PROJECTILE_COLOR, Vec2::splat(12.0)),
+            Transform::from_translation(from),
+            Projectile {
+                target: shot.cell,
+                from,
+                to,
+                timer: Timer::from_seconds(PROJECTILE_SECONDS, TimerMode::Once),
+                outcome: shot.outcome,
+                shooter: shot.shooter,
+            },
+            StateScoped(AppState::InGame),
+        ));
+    }
+}
+
+fn fly_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectiles_query: Query<(Entity, &mut Projectile, &mut Transform)>,
+    mut cells_query: Query<&mut Sprite, With<Cell>>,
+    mut landed: EventWriter<ShotLanded>,
+    theme: Res<Theme>,
+) {
+    for (entity, mut projectile, mut transform) in projectiles_query.iter_mut() {
+        projectile.timer.tick(time.delta());
+        let progress = projectile.timer.fraction();
+
+        // a escala faz o papel da altura do arco
+        transform.translation = projectile.from.lerp(projectile.to, progress);
+        transform.scale = Vec3::splat(1.0 + (progress * PI).sin() * 1.5);
+
+        if !projectile.timer.finished() {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        landed.send(ShotLanded {
+            shooter: projectile.shooter,
+            outcome: projectile.outcome,
+        });
+
+        if let Ok(mut sprite) = cells_query.get_mut(projectile.target) {
+            sprite.color = theme.palette().outcome(projectile.outcome, projectile.shooter);
+        }
+
+        let position = projectile.to.truncate();
+        match projectile.outcome {
+            ShotOutcome::Miss => spawn_burst(&mut commands, position, Color::srgb(0.75, 0.9, 1.0), 14, 90.0, false),
+            ShotOutcome::Hit | ShotOutcome::Sunk => {
+                spawn_burst(&mut commands, position, Color::srgb(1.0, 0.55, 0.1), 20, 140.0, false);
+                commands.spawn((
+                    Sprite::from_color(Color::srgb(1.0, 0.4, 0.0), Vec2::splat(SLOT_SIZE * 0.35)),
+                    Transform::from_translation(position.extend(4.0)),
+                    Fire {
+                        phase: rand::thread_rng().gen_range(0.0..PI * 2.0),
+                    },
+                    StateScoped(AppState::InGame),
+                ));
+            }
+        }
+    }
+}
+
+/**
+ * símbolo do resultado em cada célula com tiro, para quem não distingue as cores.
+ * espera o projétil chegar, e some quando a célula volta a ficar sem tiro
+ * (o espectador redesenha o tabuleiro ao trocar de visão)
+ */
+fn draw_markers(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    shot_grid: Res<ShotGrid>,
+    index: Res<BoardIndex>,
+    cells_query: Query<(Entity, Option<&Children>), With<Cell>>,
+    markers_query: Query<&ShotMarker>,
+    projectiles_query: Query<&Projectile>,
+) {
+    for (entity, children) in cells_query.iter() {
+        let state = index
+            .position(entity)
+            .map_or(ShotState::Unknown, |(side, row, column)| shot_grid.get(&side, row, column));
+
+        let marker = children.and_then(|children| {
+            children
+                .iter()
+                .find_map(|child| markers_query.get(*child).ok().map(|marker| (*child, marker.0)))
+        });
+
+        if marker.map_or(ShotState::Unknown, |(_, marked)| marked) == state {
+            continue;
+        }
+
+        if let Some((child, _)) = marker {
+            commands.entity(child).despawn_recursive();
+        }
+
+        let Some((symbol, color)) = theme.palette().marker(state) else {
+            continue;
+        };
+
+        if projectiles_query.iter().any(|projectile| projectile.target == entity) {
+            continue;
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                Text2d::new(symbol),
+                TextFont {
+                    font_size: SLOT_SIZE * 0.6,
+                    ..default()
+                },
+                TextColor(color),
+                Transform::from_xyz(0.0, 0.0, 6.0), // acima do navio e do fogo
+                ShotMarker(state),
+            ));
+        });
+    }
+}
+
+// respingo, explosão ou fumaça: partículas que se espalham a partir de um ponto e somem
+fn spawn_burst(commands: &mut Commands, position: Vec2, color: Color, count: usize, speed: f32, grows: bool) {
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..count {
+        let angle = rng.gen_range(0.0..PI * 2.0);
+        let velocity = Vec2::from_angle(angle) * rng.gen_range(speed * 0.3..speed);
+        let size = rng.gen_range(4.0..9.0);
+
+        commands.spawn((
+            Sprite::from_color(color, Vec2::splat(size)),
+            Transform::from_translation(position.extend(6.0)),
+            Particle {
+                velocity: if grows {
+                    velocity * 0.3 + Vec2::Y * 30.0 // fumaça sobe
+                } else {
+                    velocity
+                },
+                timer: Timer::from_seconds(rng.gen_range(0.4..0.9), TimerMode::Once),
+                size,
+                grows,
+            },
+            StateScoped(AppState::InGame),
+        ));
+    }
+}
+
+fn sink_ships(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut ships_query: Query<(&mut Sinking, &mut Transform)>,
+) {
+    for (mut sinking, mut transform) in ships_query.iter_mut() {
+        if sinking.timer.finished() || !sinking.delay.tick(time.delta()).finished() {
+            continue;
+        }
+
+        sinking.timer.tick(time.delta());
+        let progress = sinking.timer.fraction();
+
+        transform.translation = sinking.translation - Vec3::Y * 6.0 * progress;
+        transform.rotation = sinking.rotation * Quat::from_rotation_z(0.08 * progress);
+
+        if sinking.smoke.tick(time.delta()).just_finished() {
+            spawn_burst(
+                &mut commands,
+                sinking.translation.truncate(),
+                Color::srgba(0.3, 0.3, 0.3, 0.8),
+                4,
+                60.0,
+                true,
+            );
+        }
+    }
+}
+
+fn animate_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles_query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut particle, mut transform, mut sprite) in particles_query.iter_mut() {
+        if particle.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let remaining = 1.0 - particle.timer.fraction();
+        transform.translation += (particle.velocity * time.delta_secs()).extend(0.0);
+
+        let size = if particle.grows {
+            particle.size * (2.0 - remaining)
+        } else {
+            particle.size * remaining
+        };
+        sprite.custom_size = Some(Vec2::splat(size));
+        sprite.color.set_alpha(remaining);
+    }
+}
+
+fn flicker_fires(time: Res<Time>, mut fires_query: Query<(&Fire, &mut Transform, &mut Sprite)>) {
+    let elapsed = time.elapsed_secs();
+
+    for (fire, mut transform, mut sprite) in fires_query.iter_mut() {
+        let flicker = ((elapsed * 12.0 + fire.phase).sin() + 1.0) / 2.0;
+        transform.scale = Vec3::splat(0.8 + flicker * 0.4);
+        sprite.color = Color::srgb(1.0, 0.3 + flicker * 0.4, 0.0);
+    }
+}
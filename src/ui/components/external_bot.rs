@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    api::external_bot::{ExternalBot, DEFAULT_TIME_LIMIT},
+    arg_value,
+    logic::{bots::BotPlayer, cell::Side},
+};
+
+use super::board::GameState;
+
+pub struct ExternalBotPlugin;
+
+// oponente externo escolhido com --bot-exe "<comando>" [--bot-time-limit-ms 2000]
+#[derive(Resource)]
+pub struct ExternalOpponent {
+    pub bot: ExternalBot,
+    pub game_over_sent: bool,
+}
+
+impl Plugin for ExternalBotPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(command) = arg_value("--bot-exe") else {
+            return;
+        };
+
+        let time_limit = arg_value("--bot-time-limit-ms")
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_TIME_LIMIT);
+
+        match ExternalBot::spawn(&command, time_limit) {
+            Ok(bot) => {
+                app.insert_resource(ExternalOpponent {
+                    bot,
+                    game_over_sent: false,
+                })
+                .add_systems(Update, notify_game_over);
+            }
+            Err(error) => warn!("não foi possível iniciar o motor '{command}': {error}"),
+        }
+    }
+}
+
+fn notify_game_over(game_state: Res<GameState>, mut opponent: ResMut<ExternalOpponent>) {
+    let Some(winner) = game_state.winner else {
+        return;
+    };
+
+    if !opponent.game_over_sent {
+        opponent.bot.game_over(winner == Side::Enemy);
+        opponent.game_over_sent = true;
+    }
+}
@@ -0,0 +1,294 @@
+use bevy::prelude::*;
+
+use crate::logic::{
+    cell::Side,
+    commitment::{
+        audit, random_salt, AuditReport, FleetCommitment, FleetLayout, FleetReveal, PlacedShip,
+        ReportedShot, ReportedSonar, SALT_SIZE,
+    },
+};
+
+use super::{
+    board::{bot_turn, handle_click, show_victory_screen, BoardIndex, GameState, ShotLog, VictoryText},
+    localization::Locale,
+    main_menu::AppState,
+    settings::{GameSettings, RuleVariant},
+    ship_selection_panel::SelectedShip,
+    ships::{HiddenFleet, Ship, ShipCatalog},
+};
+
+pub struct FleetCommitmentPlugin;
+
+/**
+ * compromissos de frota da partida: cada lado manda o hash da sua frota
+ * antes do primeiro tiro e revela frota + salt quando o jogo termina
+ */
+#[derive(Resource, Debug)]
+pub struct FleetCommitments {
+    pub player_salt: [u8; SALT_SIZE],
+    pub player_layout: FleetLayout,
+    pub player: Option<FleetCommitment>,
+    pub opponent: Option<FleetCommitment>,
+    pub opponent_reveal: Option<FleetReveal>,
+    pub audit: Option<AuditReport>,
+}
+
+impl Default for FleetCommitments {
+    fn default() -> Self {
+        FleetCommitments {
+            player_salt: random_salt(),
+            player_layout: FleetLayout::default(),
+            player: None,
+            opponent: None,
+            opponent_reveal: None,
+            audit: None,
+        }
+    }
+}
+
+impl FleetCommitments {
+    // o que o jogador revela ao adversário no fim da partida
+    pub fn player_reveal(&self) -> FleetReveal {
+        FleetReveal {
+            layout: self.player_layout.clone(),
+            salt: self.player_salt,
+        }
+    }
+}
+
+// segredo do bot local; numa partida em rede ele fica só do lado do adversário
+#[derive(Resource, Default, Debug)]
+pub struct LocalBotFleetSecret(pub Option<FleetReveal>);
+
+impl Plugin for FleetCommitmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FleetCommitments::default())
+            .insert_resource(LocalBotFleetSecret::default())
+            .add_systems(OnEnter(AppState::InGame), reset_commitments)
+            .add_systems(
+                Update,
+                (
+                    commit_fleets.before(handle_click).before(bot_turn),
+                    reveal_and_audit
+                        .after(handle_click)
+                        .after(bot_turn)
+                        .before(show_victory_screen),
+                    show_audit.after(show_victory_screen),
+                ),
+            );
+    }
+}
+
+// salt e compromissos novos a cada partida
+fn reset_commitments(
+    mut commitments: ResMut<FleetCommitments>,
+    mut bot_secret: ResMut<LocalBotFleetSecret>,
+) {
+    *commitments = FleetCommitments::default();
+    *bot_secret = LocalBotFleetSecret::default();
+}
+
+// frota em linhas contadas a partir do começo do lado, como no motor de regras e no servidor
+pub fn fleet_layout<'a>(ships: impl Iterator<Item = &'a Ship>, index: &BoardIndex, side: Side) -> FleetLayout {
+    let mut layout = FleetLayout::default();
+
+    for ship in ships.filter(|ship| ship.side == side) {
+        layout.ships.push(PlacedShip {
+            name: ship.r#type.name().to_string(),
+            cells: ship
+                .cells
+                .iter()
+                .filter_map(|entity| index.position(*entity))
+                .map(|(_, row, column)| (row, column))
+                .collect(),
+        });
+    }
+
+    layout
+}
+
+// enquanto nenhum tiro foi dado o compromisso do jogador acompanha o posicionamento
+fn commit_fleets(
+    mut commitments: ResMut<FleetCommitments>,
+    mut bot_secret: ResMut<LocalBotFleetSecret>,
+    shot_log: Res<ShotLog>,
+    ships_query: Query<&Ship, Without<SelectedShip>>,
+    hidden: Res<HiddenFleet>,
+    index: Res<BoardIndex>,
+) {
+    if !shot_log.records.is_empty() {
+        return;
+    }
+
+    let player_layout = fleet_layout(ships_query.iter(), &index, Side::Player);
+
+    if commitments.player.is_none() || commitments.player_layout != player_layout {
+        commitments.player = Some(FleetCommitment::new(
+            &player_layout,
+            &commitments.player_salt,
+        ));
+        commitments.player_layout = player_layout;
+    }
+
+    if bot_secret.0.is_none() {
+        let bot_layout = fleet_layout(hidden.ships(), &index, Side::Enemy);
+
+        if !bot_layout.ships.is_empty() {
+            let reveal = FleetReveal {
+                layout: bot_layout,
+                salt: random_salt(),
+            };
+
+            commitments.opponent = Some(FleetCommitment::new(&reveal.layout, &reveal.salt));
+            bot_secret.0 = Some(reveal);
+        }
+    }
+}
+
+/**
+ * no fim do jogo o adversário revela a frota e cada resposta dada ao jogador
+ * é conferida. o bot local revela na hora; em rede a revelação chega pelo
+ * servidor e a auditoria espera por ela
+ */
+fn reveal_and_audit(
+    game_state: Res<GameState>,
+    settings: Res<GameSettings>,
+    mut commitments: ResMut<FleetCommitments>,
+    bot_secret: Res<LocalBotFleetSecret>,
+    mut shot_log: ResMut<ShotLog>,
+    index: Res<BoardIndex>,
+    catalog: Res<ShipCatalog>,
+) {
+    if game_state.winner.is_none() || commitments.audit.is_some() {
+        return;
+    }
+
+    if let Some(secret) = &bot_secret.0 {
+        commitments.opponent_reveal = Some(secret.clone());
+    }
+
+    let (Some(commitment), Some(reveal)) = (commitments.opponent, &commitments.opponent_reveal)
+    else {
+        return;
+    };
+
+    // com navios em movimento a frota revelada é a do começo e não explica os tiros seguintes:
+    // só o compromisso é conferido
+    let moving = game_state.variant == RuleVariant::Moving;
+    let (indexes, shots): (Vec<usize>, Vec<ReportedShot>) = shot_log
+        .records
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| record.shooter == Side::Player && !moving)
+        .filter_map(|(position, record)| {
+            // o registro guarda a linha do tabuleiro inteiro, a frota revelada a do lado
+            let (_, row, column) = index.position(index.at(record.row, record.column)?)?;
+
+            Some((
+                position,
+                ReportedShot {
+                    row,
+                    column,
+                    reported: record.reported,
+                },
+            ))
+        })
+        .unzip();
+
+    // o sonar é refeito com os tiros do jogador que vieram antes dele
+    let (sonar_indexes, sonars): (Vec<usize>, Vec<ReportedSonar>) = shot_log
+        .sonars
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| record.shooter == Side::Player && !moving)
+        .filter_map(|(position, record)| {
+            let (_, row, column) = index.position(index.at(record.row, record.column)?)?;
+
+            Some((
+                position,
+                ReportedSonar {
+                    after: indexes.iter().filter(|shot| **shot < record.after).count(),
+                    row,
+                    column,
+                    found: record.found,
+                },
+            ))
+        })
+        .unzip();
+
+    let report = audit(&settings.rules(&catalog), &commitment, reveal, &shots, &sonars);
+
+    if !report.commitment_valid {
+        warn!("frota revelada pelo adversário não confere com o compromisso");
+    }
+    if !report.fleet_valid {
+        warn!("frota revelada pelo adversário não obedece às regras da partida");
+    }
+
+    for lie in &report.lies {
+        let record = &mut shot_log.records[indexes[lie.shot_index]];
+        record.lie = true;
+        warn!(
+            "resposta falsa em ({}, {}): anunciado {:?}, real {:?}",
+            record.row, record.column, lie.reported, lie.actual
+        );
+    }
+    for lie in &report.sonar_lies {
+        let record = &mut shot_log.sonars[sonar_indexes[*lie]];
+        record.lie = true;
+        warn!(
+            "sonar falso em ({}, {}): anunciado {}",
+            record.row, record.column, record.found
+        );
+    }
+
+    commitments.audit = Some(report);
+}
+
+// resultado da auditoria no texto do fim de jogo, inclusive as células onde o adversário mentiu
+fn show_audit(
+    commitments: Res<FleetCommitments>,
+    shot_log: Res<ShotLog>,
+    locale: Res<Locale>,
+    mut text_query: Query<(Ref<VictoryText>, &mut Text2d)>,
+) {
+    for (victory, mut text) in text_query.iter_mut() {
+        if !victory.is_added() && !commitments.is_changed() {
+            continue;
+        }
+
+        let Some(report) = &commitments.audit else {
+            continue;
+        };
+
+        let mut lines = victory.lines.clone();
+
+        if !report.commitment_valid {
+            lines.push(locale.get("victory-commitment-mismatch"));
+        }
+        if !report.fleet_valid {
+            lines.push(locale.get("victory-fleet-invalid"));
+        }
+        if !report.lies.is_empty() || !report.sonar_lies.is_empty() {
+            let shots = shot_log
+                .records
+                .iter()
+                .filter(|record| record.lie)
+                .map(|record| format!("({}, {})", record.row, record.column));
+            let sonars = shot_log
+                .sonars
+                .iter()
+                .filter(|record| record.lie)
+                .map(|record| locale.format("victory-lie-sonar", &[("row", &record.row), ("column", &record.column)]));
+            let cells: Vec<String> = shots.chain(sonars).collect();
+
+            lines.push(locale.format("victory-lies", &[("count", &cells.len())]));
+            lines.push(locale.format("victory-lie-cells", &[("cells", &cells.join(" "))]));
+        }
+        if report.is_honest() {
+            lines.push(locale.get("victory-verified"));
+        }
+
+        text.0 = lines.join("\n");
+    }
+}
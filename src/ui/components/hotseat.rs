@@ -0,0 +1,150 @@
+use bevy::{input::mouse::MouseButtonInput, prelude::*};
+
+use crate::logic::cell::Side;
+
+use super::{
+    abilities::ArmedAbility,
+    board::GameState,
+    effects::animations_finished,
+    localization::Locale,
+    main_menu::{screen_node, spawn_button, AppState},
+    pause_menu::PauseState,
+    ships::Ship,
+};
+
+pub struct HotseatPlugin;
+
+/**
+ * partida de dois jogadores no mesmo computador: o jogador 1 é o lado de
+ * baixo e o jogador 2 o de cima. entre um turno e outro a tela fica coberta
+ * até quem vai jogar confirmar, e só a frota de quem está jogando aparece
+ */
+#[derive(Resource, Default, Debug)]
+pub struct Hotseat {
+    pub shown: Option<Side>, // lado cuja frota está à mostra; nenhum enquanto a tela está coberta
+}
+
+impl Hotseat {
+    pub fn player_key(side: Side) -> &'static str {
+        match side {
+            Side::Player => "hotseat-player-1",
+            Side::Enemy => "hotseat-player-2",
+        }
+    }
+}
+
+// cobre o tabuleiro enquanto o computador troca de mãos
+#[derive(Component)]
+struct PassScreen;
+
+#[derive(Component, Clone, Copy)]
+struct PassReady;
+
+const PASS_SCREEN_COLOR: Color = Color::srgb(0.05, 0.08, 0.15);
+
+impl Plugin for HotseatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::InGame),
+            (|mut hotseat: ResMut<Hotseat>| hotseat.shown = None).run_if(resource_exists::<Hotseat>),
+        )
+        .add_systems(
+            Update,
+            (
+                cover_between_turns.run_if(animations_finished),
+                dismiss_pass_screen.run_if(in_state(PauseState::Running)),
+                show_current_fleet,
+            )
+                .chain()
+                .run_if(in_state(AppState::InGame))
+                .run_if(resource_exists::<Hotseat>),
+        );
+    }
+}
+
+// a vez mudou de dono: a tela é coberta antes que o próximo veja a frota do outro
+fn cover_between_turns(
+    mut commands: Commands,
+    mut hotseat: ResMut<Hotseat>,
+    game_state: Res<GameState>,
+    pass_query: Query<(), With<PassScreen>>,
+    locale: Res<Locale>,
+) {
+    let shooter = game_state.shooter();
+    if game_state.winner.is_some() || hotseat.shown == Some(shooter) || !pass_query.is_empty() {
+        return;
+    }
+    hotseat.shown = None;
+
+    let name = locale.get(Hotseat::player_key(shooter));
+
+    commands
+        .spawn((
+            screen_node(),
+            BackgroundColor(PASS_SCREEN_COLOR),
+            GlobalZIndex(1), // acima da barra de habilidades e dos avisos
+            PassScreen,
+            StateScoped(AppState::InGame),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(locale.format("hotseat-pass", &[("name", &name)])),
+                TextFont {
+                    font_size: 36.0,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new(locale.get("hotseat-hint")),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+            ));
+            spawn_button(parent, "hotseat-ready", Some(PassReady));
+        });
+}
+
+// o botão ou Enter descobre a tela para quem está com a vez
+fn dismiss_pass_screen(
+    mut commands: Commands,
+    mut hotseat: ResMut<Hotseat>,
+    game_state: Res<GameState>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    buttons_query: Query<&Interaction, (Changed<Interaction>, With<PassReady>)>,
+    pass_query: Query<Entity, With<PassScreen>>,
+    mut armed: ResMut<ArmedAbility>,
+    mut mouse_events: ResMut<Events<MouseButtonInput>>,
+) {
+    let Ok(screen) = pass_query.get_single() else {
+        return;
+    };
+
+    let pressed = buttons_query.iter().any(|interaction| *interaction == Interaction::Pressed);
+    if !pressed && !keyboard.just_pressed(KeyCode::Enter) {
+        return;
+    }
+
+    // o clique no botão não pode virar tiro, nem a habilidade armada pelo outro jogador
+    mouse_events.clear();
+    armed.0 = None;
+
+    commands.entity(screen).despawn_recursive();
+    hotseat.shown = Some(game_state.shooter());
+}
+
+// navio afundado fica à mostra para os dois; no fim da partida as duas frotas aparecem
+fn show_current_fleet(
+    hotseat: Res<Hotseat>,
+    game_state: Res<GameState>,
+    mut ships_query: Query<(&Ship, &mut Visibility)>,
+) {
+    for (ship, mut visibility) in ships_query.iter_mut() {
+        let shown = ship.sunk || game_state.winner.is_some() || hotseat.shown == Some(ship.side);
+        let wanted = if shown { Visibility::Inherited } else { Visibility::Hidden };
+
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
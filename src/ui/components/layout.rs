@@ -0,0 +1,78 @@
+use bevy::{prelude::*, render::camera::ScalingMode, window::PrimaryWindow};
+
+use super::{
+    board::{SLOT_SIZE, SLOT_SPACE_BETWEEN},
+    settings::GameSettings,
+};
+
+pub struct LayoutPlugin;
+
+// espaço em volta do tabuleiro, em unidades do mundo. à esquerda fica o painel de navios,
+// e a direita tem o mesmo tamanho para o tabuleiro continuar no centro
+pub const SIDE_MARGIN: f32 = 260.0;
+const VERTICAL_MARGIN: f32 = SLOT_SIZE;
+
+#[derive(Component)]
+pub struct MainCamera;
+
+/**
+ * o tabuleiro é desenhado em unidades fixas em volta da origem e a câmera
+ * escala o mundo para caber na janela, mantendo a proporção. a interface
+ * (painel de navios, menus) acompanha pela UiScale, então tudo cresce e
+ * diminui junto ao redimensionar a janela ou mudar de monitor
+ */
+impl Plugin for LayoutPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_camera)
+            .add_systems(Update, fit_board_to_window);
+    }
+}
+
+// largura e altura do tabuleiro inteiro (os dois lados), em unidades do mundo
+pub fn board_extent(settings: &GameSettings) -> Vec2 {
+    Vec2::new(settings.columns() as f32, settings.rows() as f32) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
+}
+
+// área do mundo que sempre precisa aparecer na janela
+fn visible_area(settings: &GameSettings) -> Vec2 {
+    board_extent(settings) + Vec2::new(SIDE_MARGIN, VERTICAL_MARGIN) * 2.0
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn((Camera2d, MainCamera));
+}
+
+fn fit_board_to_window(
+    settings: Res<GameSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut projection_query: Query<&mut OrthographicProjection, With<MainCamera>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let area = visible_area(&settings);
+
+    // o tamanho do tabuleiro só muda nas configurações
+    if settings.is_changed() {
+        for mut projection in projection_query.iter_mut() {
+            projection.scaling_mode = ScalingMode::AutoMin {
+                min_width: area.x,
+                min_height: area.y,
+            };
+        }
+    }
+
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    // pixels lógicos da janela por unidade do mundo, o mesmo fator que a câmera usa
+    let scale = (window.width() / area.x).min(window.height() / area.y);
+    if scale > 0.0 && ui_scale.0 != scale {
+        ui_scale.0 = scale;
+    }
+}
+
+// posição do cursor no mundo, passando pela câmera
+pub fn cursor_world_position(window: &Window, camera: &Camera, camera_transform: &GlobalTransform) -> Option<Vec2> {
+    let cursor_position = window.cursor_position()?;
+    camera.viewport_to_world_2d(camera_transform, cursor_position).ok()
+}
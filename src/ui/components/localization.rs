@@ -0,0 +1,124 @@
+use std::{collections::HashMap, fmt::Display, fs};
+
+use bevy::prelude::*;
+
+use super::settings::{GameSettings, Language};
+
+pub const LOCALES_DIR: &str = "assets/locales";
+
+// cópias embutidas, usadas quando o arquivo não existe ou não tem a chave
+const BUILTIN_PT_BR: &str = include_str!("../../../assets/locales/pt-BR.ftl");
+const BUILTIN_EN_US: &str = include_str!("../../../assets/locales/en-US.ftl");
+
+pub struct LocalizationPlugin;
+
+/**
+ * textos do idioma escolhido nas configurações, lidos de assets/locales/<idioma>.ftl.
+ * só o básico do fluent: "chave = texto" numa linha e { $nome } para os valores.
+ * chave que falta no idioma cai no português, e depois na própria chave
+ */
+#[derive(Resource, Debug)]
+pub struct Locale {
+    pub language: Language,
+    messages: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+// texto fixo da interface, traduzido de novo sempre que o idioma muda
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Localized(pub &'static str);
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Locale::new(GameSettings::default().language))
+            .add_systems(
+                Update,
+                (
+                    apply_language.run_if(resource_changed::<GameSettings>),
+                    translate_texts,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn builtin(language: Language) -> &'static str {
+    match language {
+        Language::PtBr => BUILTIN_PT_BR,
+        Language::EnUs => BUILTIN_EN_US,
+    }
+}
+
+fn load_messages(language: Language) -> HashMap<String, String> {
+    let path = format!("{LOCALES_DIR}/{}.ftl", language.code());
+    let text = fs::read_to_string(&path).unwrap_or_else(|_| builtin(language).to_string());
+
+    let mut messages = parse_messages(builtin(language));
+    messages.extend(parse_messages(&text));
+    messages
+}
+
+pub fn parse_messages(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+impl Locale {
+    pub fn new(language: Language) -> Self {
+        Locale {
+            language,
+            messages: load_messages(language),
+            fallback: parse_messages(BUILTIN_PT_BR),
+        }
+    }
+
+    pub fn try_get(&self, key: &str) -> Option<&str> {
+        self.messages
+            .get(key)
+            .or_else(|| self.fallback.get(key))
+            .map(String::as_str)
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        self.try_get(key).unwrap_or(key).to_string()
+    }
+
+    // troca cada { $nome } pelo valor com o mesmo nome
+    pub fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = self.get(key);
+
+        for (name, value) in args {
+            text = text.replace(&format!("{{ ${name} }}"), &value.to_string());
+        }
+
+        text
+    }
+}
+
+fn apply_language(settings: Res<GameSettings>, mut locale: ResMut<Locale>) {
+    if locale.language != settings.language {
+        *locale = Locale::new(settings.language);
+    }
+}
+
+fn translate_texts(
+    locale: Res<Locale>,
+    mut texts_query: Query<(Ref<Localized>, Option<&mut Text>, Option<&mut Text2d>)>,
+) {
+    for (localized, text, text_2d) in texts_query.iter_mut() {
+        if !locale.is_changed() && !localized.is_added() {
+            continue;
+        }
+
+        let translated = locale.get(localized.0);
+        if let Some(mut text) = text {
+            text.0 = translated;
+        } else if let Some(mut text) = text_2d {
+            text.0 = translated;
+        }
+    }
+}
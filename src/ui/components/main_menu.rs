@@ -0,0 +1,671 @@
+use std::net::SocketAddr;
+
+use bevy::{
+    app::AppExit,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use crate::{
+    api::server::{GameServer, ServerConfig},
+    arg_value,
+};
+
+use super::{
+    hotseat::Hotseat,
+    network_match::{NetworkSession, NetworkSettings},
+    profiles::Profiles,
+    replay::ReplaySession,
+    save_game::{load_game, PendingLoad, SAVE_PATH},
+    localization::{Locale, Localized},
+    settings::GameSettings,
+    theme::Theme,
+};
+
+pub struct MainMenuPlugin;
+
+/**
+ * telas do jogo. as opções de linha de comando que já dizem o que jogar
+ * (--connect, --spectate) pulam o menu e começam direto na partida
+ */
+#[derive(States, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Settings,
+    JoinLan,
+    Profiles,
+    InGame,
+    Restarting, // só de passagem, para reiniciar a partida
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+enum MenuAction {
+    VsBot,
+    Hotseat,
+    LoadGame,
+    Replays,
+    HostLan,
+    JoinLan,
+    Connect,
+    Settings,
+    Profiles,
+    NextProfile,
+    Back,
+    Quit,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+enum SettingField {
+    BoardSize,
+    Fleet,
+    Variant,
+    Map,
+    Difficulty,
+    Volume,
+    MusicVolume,
+    SfxVolume,
+    Mute,
+    Theme,
+    Language,
+}
+
+impl SettingField {
+    const ALL: [SettingField; 11] = [
+        SettingField::BoardSize,
+        SettingField::Fleet,
+        SettingField::Variant,
+        SettingField::Map,
+        SettingField::Difficulty,
+        SettingField::Volume,
+        SettingField::MusicVolume,
+        SettingField::SfxVolume,
+        SettingField::Mute,
+        SettingField::Theme,
+        SettingField::Language,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            SettingField::BoardSize => "setting-board-size",
+            SettingField::Fleet => "setting-fleet",
+            SettingField::Variant => "setting-variant",
+            SettingField::Map => "setting-map",
+            SettingField::Difficulty => "setting-difficulty",
+            SettingField::Volume => "setting-volume",
+            SettingField::MusicVolume => "setting-music-volume",
+            SettingField::SfxVolume => "setting-sfx-volume",
+            SettingField::Mute => "setting-mute",
+            SettingField::Theme => "setting-theme",
+            SettingField::Language => "setting-language",
+        }
+    }
+
+    fn value(self, settings: &GameSettings, theme: &Theme, locale: &Locale) -> String {
+        match self {
+            SettingField::BoardSize => settings.board_size.label(),
+            SettingField::Fleet => locale.get(settings.fleet.key()),
+            SettingField::Variant => locale.get(settings.variant.key()),
+            SettingField::Map => locale.get(settings.map.key()),
+            SettingField::Difficulty => locale.get(settings.difficulty.key()),
+            SettingField::Volume => format!("{}%", settings.volume),
+            SettingField::MusicVolume => format!("{}%", settings.music_volume),
+            SettingField::SfxVolume => format!("{}%", settings.sfx_volume),
+            SettingField::Mute => locale.get(if settings.muted { "sound-muted" } else { "sound-on" }),
+            SettingField::Theme => locale
+                .try_get(&format!("theme-{}", settings.theme))
+                .map_or_else(|| theme.label(&settings.theme), str::to_string),
+            SettingField::Language => settings.language.label().to_string(),
+        }
+    }
+
+    // cada clique avança para a próxima opção
+    fn cycle(self, settings: &mut GameSettings, theme: &Theme) {
+        match self {
+            SettingField::BoardSize => settings.board_size = settings.board_size.next(),
+            SettingField::Fleet => settings.fleet = settings.fleet.next(),
+            SettingField::Variant => settings.variant = settings.variant.next(),
+            SettingField::Map => settings.map = settings.map.next(),
+            SettingField::Difficulty => settings.difficulty = settings.difficulty.next(),
+            SettingField::Volume => settings.volume = (settings.volume + 10) % 110,
+            SettingField::MusicVolume => settings.music_volume = (settings.music_volume + 10) % 110,
+            SettingField::SfxVolume => settings.sfx_volume = (settings.sfx_volume + 10) % 110,
+            SettingField::Mute => settings.muted = !settings.muted,
+            SettingField::Theme => settings.theme = theme.next_name(&settings.theme),
+            SettingField::Language => settings.language = settings.language.next(),
+        }
+    }
+}
+
+// texto que mostra o valor atual de uma configuração
+#[derive(Component)]
+struct SettingValue(SettingField);
+
+// texto do endereço sendo digitado na tela de rede local
+#[derive(Component)]
+struct LanAddressText;
+
+// estatísticas do perfil ativo na tela de perfis
+#[derive(Component)]
+struct ProfileSummaryText;
+
+// nome do perfil novo sendo digitado
+#[derive(Component)]
+struct NewProfileText;
+
+#[derive(Component)]
+struct MenuButton;
+
+// servidor aberto pelo "hospedar", reaproveitado se o jogador hospedar de novo
+#[derive(Resource)]
+struct LocalServer(SocketAddr);
+
+const BUTTON_COLOR: Color = Color::srgb(0.224, 0.451, 0.678);
+const BUTTON_HOVER_COLOR: Color = Color::srgb(0.3, 0.55, 0.8);
+const BUTTON_DISABLED_COLOR: Color = Color::srgb(0.3, 0.3, 0.35);
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        let initial = if arg_value("--connect").is_some() || arg_value("--spectate").is_some() {
+            AppState::InGame
+        } else {
+            AppState::MainMenu
+        };
+
+        app.insert_state(initial)
+            .enable_state_scoped_entities::<AppState>()
+            .insert_resource(GameSettings::default())
+            .insert_resource(Profiles::load())
+            .insert_resource(Theme::load())
+            .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+            .add_systems(OnEnter(AppState::Settings), spawn_settings_screen)
+            .add_systems(OnEnter(AppState::JoinLan), spawn_join_lan_screen)
+            .add_systems(OnEnter(AppState::Profiles), spawn_profiles_screen)
+            .add_systems(
+                Update,
+                (
+                    highlight_buttons,
+                    handle_menu_buttons,
+                    apply_theme.run_if(resource_changed::<GameSettings>),
+                ),
+            )
+            .add_systems(
+                Update,
+                (handle_setting_buttons, refresh_setting_values)
+                    .chain()
+                    .run_if(in_state(AppState::Settings)),
+            )
+            .add_systems(Update, type_lan_address.run_if(in_state(AppState::JoinLan)))
+            .add_systems(
+                Update,
+                (type_profile_name, refresh_profile_summary)
+                    .chain()
+                    .run_if(in_state(AppState::Profiles)),
+            );
+    }
+}
+
+// tela inteira com o conteúdo centralizado em coluna
+pub fn screen_node() -> Node {
+    Node {
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        row_gap: Val::Px(12.0),
+        ..Default::default()
+    }
+}
+
+fn screen_root(state: AppState) -> impl Bundle {
+    (screen_node(), StateScoped(state))
+}
+
+// título e botões recebem a chave do texto, traduzido por Localized
+pub fn title(key: &'static str) -> impl Bundle {
+    (
+        Text::default(),
+        Localized(key),
+        TextFont {
+            font_size: 48.0,
+            ..default()
+        },
+        Node {
+            margin: UiRect::bottom(Val::Px(24.0)),
+            ..Default::default()
+        },
+    )
+}
+
+fn button_node() -> Node {
+    Node {
+        width: Val::Px(320.0),
+        height: Val::Px(48.0),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..Default::default()
+    }
+}
+
+// sem ação o botão aparece desabilitado
+pub fn spawn_button(parent: &mut ChildBuilder, key: &'static str, action: Option<impl Component>) {
+    let mut button = parent.spawn((
+        Button,
+        button_node(),
+        BorderRadius::all(Val::Px(8.0)),
+        BackgroundColor(if action.is_some() {
+            BUTTON_COLOR
+        } else {
+            BUTTON_DISABLED_COLOR
+        }),
+    ));
+
+    if let Some(action) = action {
+        button.insert((action, MenuButton));
+    }
+
+    button.with_children(|parent| {
+        parent.spawn((
+            Text::default(),
+            Localized(key),
+            TextFont {
+                font_size: 22.0,
+                ..default()
+            },
+        ));
+    });
+}
+
+fn spawn_main_menu(mut commands: Commands) {
+    commands
+        .spawn(screen_root(AppState::MainMenu))
+        .with_children(|parent| {
+            parent.spawn(title("menu-title"));
+            spawn_button(parent, "menu-vs-bot", Some(MenuAction::VsBot));
+            spawn_button(parent, "menu-hotseat", Some(MenuAction::Hotseat));
+            spawn_button(parent, "menu-host-lan", Some(MenuAction::HostLan));
+            spawn_button(parent, "menu-join-lan", Some(MenuAction::JoinLan));
+            spawn_button(parent, "menu-load-game", Some(MenuAction::LoadGame));
+            spawn_button(parent, "menu-replays", Some(MenuAction::Replays));
+            spawn_button(parent, "menu-profiles", Some(MenuAction::Profiles));
+            spawn_button(parent, "menu-settings", Some(MenuAction::Settings));
+            spawn_button(parent, "menu-quit", Some(MenuAction::Quit));
+        });
+}
+
+fn spawn_settings_screen(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+) {
+    commands
+        .spawn(screen_root(AppState::Settings))
+        .with_children(|parent| {
+            parent.spawn(title("menu-settings"));
+
+            for field in SettingField::ALL {
+                parent
+                    .spawn(Node {
+                        width: Val::Px(560.0),
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    })
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::default(),
+                            Localized(field.key()),
+                            TextFont {
+                                font_size: 22.0,
+                                ..default()
+                            },
+                        ));
+                        row.spawn((
+                            Button,
+                            button_node(),
+                            BorderRadius::all(Val::Px(8.0)),
+                            BackgroundColor(BUTTON_COLOR),
+                            field,
+                            MenuButton,
+                        ))
+                        .with_children(|button| {
+                            button.spawn((
+                                Text::new(field.value(&settings, &theme, &locale)),
+                                TextFont {
+                                    font_size: 22.0,
+                                    ..default()
+                                },
+                                SettingValue(field),
+                            ));
+                        });
+                    });
+            }
+
+            spawn_button(parent, "menu-back", Some(MenuAction::Back));
+        });
+}
+
+fn spawn_join_lan_screen(mut commands: Commands, settings: Res<GameSettings>) {
+    commands
+        .spawn(screen_root(AppState::JoinLan))
+        .with_children(|parent| {
+            parent.spawn(title("menu-join-lan"));
+            parent.spawn((
+                Text::default(),
+                Localized("join-lan-hint"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new(format!("{}_", settings.lan_address)),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                LanAddressText,
+            ));
+            spawn_button(parent, "join-lan-connect", Some(MenuAction::Connect));
+            spawn_button(parent, "menu-back", Some(MenuAction::Back));
+        });
+}
+
+fn profile_text(profiles: &Profiles, locale: &Locale) -> String {
+    let profile = profiles.active();
+    format!(
+        "{}\n\n{}",
+        locale.format("profile-header", &[("name", &profile.name)]),
+        profile.summary(locale)
+    )
+}
+
+fn spawn_profiles_screen(mut commands: Commands, profiles: Res<Profiles>, locale: Res<Locale>) {
+    commands
+        .spawn(screen_root(AppState::Profiles))
+        .with_children(|parent| {
+            parent.spawn(title("menu-profiles"));
+            parent.spawn((
+                Text::new(profile_text(&profiles, &locale)),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                ProfileSummaryText,
+            ));
+            spawn_button(parent, "profiles-next", Some(MenuAction::NextProfile));
+            parent.spawn((
+                Text::default(),
+                Localized("profiles-hint"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new("_"),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                NewProfileText,
+            ));
+            spawn_button(parent, "menu-back", Some(MenuAction::Back));
+        });
+}
+
+fn highlight_buttons(
+    mut buttons_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<MenuButton>)>,
+) {
+    for (interaction, mut background) in buttons_query.iter_mut() {
+        background.0 = match interaction {
+            Interaction::Hovered | Interaction::Pressed => BUTTON_HOVER_COLOR,
+            Interaction::None => BUTTON_COLOR,
+        };
+    }
+}
+
+fn handle_menu_buttons(
+    mut commands: Commands,
+    buttons_query: Query<(&Interaction, &MenuAction), Changed<Interaction>>,
+    mut settings: ResMut<GameSettings>,
+    mut next_state: ResMut<NextState<AppState>>,
+    local_server: Option<Res<LocalServer>>,
+    mut profiles: ResMut<Profiles>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for (interaction, action) in buttons_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match action {
+            MenuAction::VsBot => {
+                leave_match_modes(&mut commands);
+                next_state.set(AppState::InGame);
+            }
+            MenuAction::Hotseat => {
+                leave_match_modes(&mut commands);
+                commands.insert_resource(Hotseat::default());
+                next_state.set(AppState::InGame);
+            }
+            MenuAction::LoadGame => match load_game() {
+                Ok(saved) if saved.winner.is_some() => warn!("o jogo salvo em {SAVE_PATH} já terminou: só abre no replay"),
+                Ok(saved) => {
+                    leave_match_modes(&mut commands);
+                    saved.apply_settings(&mut settings);
+                    commands.insert_resource(PendingLoad(saved));
+                    next_state.set(AppState::InGame);
+                }
+                Err(error) => warn!("não foi possível carregar {SAVE_PATH}: {error}"),
+            },
+            MenuAction::Replays => match load_game() {
+                Ok(saved) => {
+                    leave_match_modes(&mut commands);
+                    saved.apply_settings(&mut settings);
+                    commands.insert_resource(ReplaySession { saved, shown: 0 });
+                    next_state.set(AppState::InGame);
+                }
+                Err(error) => warn!("não foi possível carregar {SAVE_PATH}: {error}"),
+            },
+            MenuAction::HostLan => {
+                let address = match local_server.as_ref() {
+                    Some(server) => Some(server.0),
+                    None => host_lan_server(&settings.lan_address),
+                };
+
+                let Some(address) = address else {
+                    continue;
+                };
+
+                commands.insert_resource(LocalServer(address));
+                start_network_match(&mut commands, &mut settings, format!("127.0.0.1:{}", address.port()));
+                next_state.set(AppState::InGame);
+            }
+            MenuAction::JoinLan => next_state.set(AppState::JoinLan),
+            MenuAction::Connect => {
+                let address = settings.lan_address.clone();
+                start_network_match(&mut commands, &mut settings, address);
+                next_state.set(AppState::InGame);
+            }
+            MenuAction::Settings => next_state.set(AppState::Settings),
+            MenuAction::Profiles => next_state.set(AppState::Profiles),
+            MenuAction::NextProfile => {
+                profiles.select_next();
+                profiles.save();
+            }
+            MenuAction::Back => next_state.set(AppState::MainMenu),
+            MenuAction::Quit => {
+                exit.send(AppExit::Success);
+            }
+        }
+    }
+}
+
+// servidor dedicado numa thread do próprio jogo, aberto para a rede local na porta do endereço configurado
+fn host_lan_server(lan_address: &str) -> Option<SocketAddr> {
+    let port = lan_address
+        .rsplit_once(':')
+        .and_then(|(_, port)| port.parse::<u16>().ok())
+        .unwrap_or(7878);
+
+    match GameServer::bind(("0.0.0.0", port), ServerConfig::default()).and_then(GameServer::spawn) {
+        Ok(address) => {
+            info!("servidor da rede local aberto em {address}");
+            Some(address)
+        }
+        Err(error) => {
+            warn!("não foi possível abrir o servidor na porta {port}: {error}");
+            None
+        }
+    }
+}
+
+// a partida nova não herda o modo da anterior (rede, dois jogadores ou replay)
+pub fn leave_match_modes(commands: &mut Commands) {
+    commands.remove_resource::<NetworkSession>();
+    commands.remove_resource::<NetworkSettings>();
+    commands.remove_resource::<Hotseat>();
+    commands.remove_resource::<ReplaySession>();
+}
+
+fn start_network_match(commands: &mut Commands, settings: &mut GameSettings, address: String) {
+    settings.reset_rules();
+    leave_match_modes(commands);
+
+    commands.insert_resource(NetworkSettings {
+        address,
+        room: arg_value("--room").unwrap_or_else(|| "principal".to_string()),
+        name: arg_value("--name").unwrap_or_else(|| "jogador".to_string()),
+    });
+    commands.insert_resource(NetworkSession::default());
+}
+
+fn handle_setting_buttons(
+    buttons_query: Query<(&Interaction, &SettingField), Changed<Interaction>>,
+    mut settings: ResMut<GameSettings>,
+    theme: Res<Theme>,
+) {
+    for (interaction, field) in buttons_query.iter() {
+        if *interaction == Interaction::Pressed {
+            field.cycle(&mut settings, &theme);
+        }
+    }
+}
+
+fn refresh_setting_values(
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    locale: Res<Locale>,
+    mut values_query: Query<(&mut Text, &SettingValue)>,
+) {
+    // o idioma novo só chega no quadro seguinte à troca
+    if !settings.is_changed() && !locale.is_changed() {
+        return;
+    }
+
+    for (mut text, value) in values_query.iter_mut() {
+        text.0 = value.0.value(&settings, &theme, &locale);
+    }
+}
+
+fn type_lan_address(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut settings: ResMut<GameSettings>,
+    mut address_query: Query<&mut Text, With<LanAddressText>>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(typed) => {
+                let accepted = typed
+                    .chars()
+                    .filter(|character| character.is_ascii_alphanumeric() || ".:-".contains(*character));
+                settings.lan_address.extend(accepted);
+            }
+            Key::Backspace => {
+                settings.lan_address.pop();
+            }
+            Key::Enter => {
+                let address = settings.lan_address.clone();
+                start_network_match(&mut commands, &mut settings, address);
+                next_state.set(AppState::InGame);
+            }
+            Key::Escape => next_state.set(AppState::MainMenu),
+            _ => {}
+        }
+    }
+
+    if let Ok(mut text) = address_query.get_single_mut() {
+        text.0 = format!("{}_", settings.lan_address);
+    }
+}
+
+// o nome vira uma palavra só no arquivo de perfis
+fn type_profile_name(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut typed_name: Local<String>,
+    mut profiles: ResMut<Profiles>,
+    mut name_query: Query<&mut Text, With<NewProfileText>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(typed) => {
+                let accepted = typed
+                    .chars()
+                    .filter(|character| character.is_alphanumeric() || "-_".contains(*character));
+                typed_name.extend(accepted);
+            }
+            // nomes podem ter espaços ("Ana Maria"), só não no começo nem repetidos
+            Key::Space if !typed_name.is_empty() && !typed_name.ends_with(' ') => typed_name.push(' '),
+            Key::Backspace => {
+                typed_name.pop();
+            }
+            Key::Enter if !typed_name.trim().is_empty() => {
+                profiles.select(typed_name.trim());
+                profiles.save();
+                typed_name.clear();
+            }
+            Key::Escape => next_state.set(AppState::MainMenu),
+            _ => {}
+        }
+    }
+
+    if let Ok(mut text) = name_query.get_single_mut() {
+        text.0 = format!("{}_", *typed_name);
+    }
+}
+
+fn refresh_profile_summary(
+    profiles: Res<Profiles>,
+    locale: Res<Locale>,
+    mut summary_query: Query<&mut Text, With<ProfileSummaryText>>,
+) {
+    if !profiles.is_changed() && !locale.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = summary_query.get_single_mut() {
+        text.0 = profile_text(&profiles, &locale);
+    }
+}
+
+fn apply_theme(settings: Res<GameSettings>, mut theme: ResMut<Theme>, mut clear_color: ResMut<ClearColor>) {
+    theme.select(&settings.theme);
+    clear_color.0 = theme.palette().background;
+}
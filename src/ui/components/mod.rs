@@ -1,3 +1,22 @@
+pub mod abilities;
+pub mod achievements;
 pub mod board;
+pub mod effects;
+pub mod external_bot;
+pub mod fleet_commitment;
+pub mod hotseat;
+pub mod layout;
+pub mod localization;
+pub mod main_menu;
+pub mod network_match;
+pub mod notices;
+pub mod pause_menu;
+pub mod profiles;
+pub mod replay;
+pub mod save_game;
+pub mod settings;
 pub mod ships;
+pub mod sound;
 pub mod ship_selection_panel;
+pub mod spectator;
+pub mod theme;
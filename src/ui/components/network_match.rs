@@ -0,0 +1,277 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+
+use crate::{
+    arg_value,
+    api::{
+        client::ServerConnection,
+        protocol::{ClientMessage, ServerMessage},
+    },
+    logic::{
+        cell::{Cell, Side},
+        shot::ShotOutcome,
+    },
+};
+
+use super::{
+    board::{BoardIndex, ShotGrid, GameState, ShotLog, ShotResolved},
+    fleet_commitment::FleetCommitments,
+    main_menu::AppState,
+    notices::Notice,
+    pause_menu::PauseState,
+    ship_selection_panel::SelectedShip,
+    ships::{placed_fleet, Ship, ShipDirection},
+    theme::Theme,
+};
+
+pub struct NetworkMatchPlugin;
+
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+// modo "conectar ao servidor", ativado por --connect <endereço> [--room <sala>] [--name <nome>]
+// ou pelas opções de rede local do menu
+#[derive(Resource, Clone, Debug)]
+pub struct NetworkSettings {
+    pub address: String,
+    pub room: String,
+    pub name: String,
+}
+
+impl NetworkSettings {
+    pub fn from_args() -> Option<Self> {
+        Some(NetworkSettings {
+            address: arg_value("--connect")?,
+            room: arg_value("--room").unwrap_or_else(|| "principal".to_string()),
+            name: arg_value("--name").unwrap_or_else(|| "jogador".to_string()),
+        })
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct NetworkSession {
+    pub connection: Option<ServerConnection>,
+    pub seat: Option<usize>,
+    pub token: Option<String>,
+    pub started: bool,
+    pub turn: Option<usize>,
+    pub fleet_sent: bool,
+    pub last_attempt: Option<Instant>,
+}
+
+impl NetworkSession {
+    pub fn send(&mut self, message: &ClientMessage) {
+        let Some(connection) = self.connection.as_mut() else {
+            return;
+        };
+
+        if connection.send(message).is_err() {
+            self.connection = None;
+        }
+    }
+}
+
+impl Plugin for NetworkMatchPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(settings) = NetworkSettings::from_args() {
+            app.insert_resource(settings)
+                .insert_resource(NetworkSession::default());
+        }
+
+        app.add_systems(
+            Update,
+            (
+                keep_connection,
+                receive_server_messages,
+                send_fleet_on_enter.run_if(in_state(PauseState::Running)),
+            )
+                .chain()
+                .run_if(in_state(AppState::InGame))
+                .run_if(resource_exists::<NetworkSession>),
+        );
+    }
+}
+
+// conecta (ou reconecta com o token) sempre que a conexão não existe
+fn keep_connection(
+    settings: Res<NetworkSettings>,
+    mut session: ResMut<NetworkSession>,
+    game_state: Res<GameState>,
+) {
+    // partida encerrada (inclusive por desistência): não há por que voltar
+    if session.connection.is_some() || game_state.winner.is_some() {
+        return;
+    }
+
+    if session
+        .last_attempt
+        .is_some_and(|attempt| attempt.elapsed() < RECONNECT_INTERVAL)
+    {
+        return;
+    }
+    session.last_attempt = Some(Instant::now());
+
+    let Ok(connection) = ServerConnection::connect(&settings.address) else {
+        warn!("não foi possível conectar em {}", settings.address);
+        return;
+    };
+
+    session.connection = Some(connection);
+
+    let message = match session.token.clone() {
+        Some(token) => ClientMessage::Reconnect { token },
+        None => ClientMessage::Join {
+            room: settings.room.clone(),
+            name: settings.name.clone(),
+        },
+    };
+    session.send(&message);
+}
+
+fn receive_server_messages(
+    mut session: ResMut<NetworkSession>,
+    mut game_state: ResMut<GameState>,
+    mut shot_grid: ResMut<ShotGrid>,
+    mut shot_log: ResMut<ShotLog>,
+    mut cells_query: Query<(Entity, &Cell, &mut Sprite)>,
+    mut ships_query: Query<&mut Ship>,
+    mut shots: EventWriter<ShotResolved>,
+    mut commitments: ResMut<FleetCommitments>,
+    mut notices: EventWriter<Notice>,
+    theme: Res<Theme>,
+    index: Res<BoardIndex>,
+) {
+    loop {
+        let message = match session.connection.as_ref().map(|connection| connection.try_recv()) {
+            Some(Ok(Some(message))) => message,
+            Some(Ok(None)) | None => return,
+            Some(Err(_)) => {
+                warn!("conexão com o servidor perdida");
+                session.connection = None;
+                return;
+            }
+        };
+
+        match message {
+            ServerMessage::Welcome { seat, token } => {
+                session.seat = Some(seat);
+                session.token = Some(token);
+            }
+            // mensagens de espectador, nunca enviadas a quem joga
+            ServerMessage::Spectating { .. }
+            | ServerMessage::Players { .. }
+            | ServerMessage::FleetOf { .. } => {}
+            ServerMessage::FleetAccepted | ServerMessage::YourFleet(_) => {
+                session.fleet_sent = true;
+            }
+            ServerMessage::Start { .. } => {
+                session.started = true;
+            }
+            ServerMessage::Turn { seat } => {
+                session.turn = Some(seat);
+                game_state.is_player_turn = session.seat == Some(seat);
+            }
+            ServerMessage::Shot {
+                seat,
+                row,
+                column,
+                outcome,
+            } => {
+                let shooter = if session.seat == Some(seat) { Side::Player } else { Side::Enemy };
+
+                // o tiro cai na metade do tabuleiro do outro lado
+                let side = shooter.opponent();
+
+                let Some((entity, cell, mut sprite)) = index
+                    .cell(&side, row, column)
+                    .and_then(|entity| cells_query.get_mut(entity).ok())
+                else {
+                    continue;
+                };
+
+                if shot_grid.is_known(&side, row, column) {
+                    continue; // evento repetido na reconexão
+                }
+
+                cell.show_outcome(&mut sprite, outcome, shooter, theme.palette());
+                shot_grid.set(&side, row, column, outcome.into());
+                shot_log.record(shooter, cell, outcome);
+                shots.send(ShotResolved {
+                    shooter,
+                    cell: entity,
+                    outcome,
+                });
+
+                // a frota inimiga não existe no tabuleiro e o fim de jogo vem do servidor,
+                // então só o placar é atualizado aqui
+                if outcome == ShotOutcome::Sunk {
+                    game_state.record_sunk(side);
+
+                    if side == Side::Player {
+                        if let Some(mut ship) = index
+                            .ship_at(entity)
+                            .and_then(|ship| ships_query.get_mut(ship).ok())
+                        {
+                            ship.sunk = true;
+                        }
+                    }
+                }
+            }
+            // o cliente gráfico joga em rede com as regras clássicas, sem habilidades nem navios
+            // em movimento; elas só chegariam de um servidor com outras regras, e os tiros vêm como SHOT
+            ServerMessage::Ability { .. } | ServerMessage::Sonar { .. } | ServerMessage::Moved { .. } => {}
+            ServerMessage::Timeout { seat } => {
+                if session.seat == Some(seat) {
+                    notices.send(Notice::new("notice-turn-timeout"));
+                }
+            }
+            ServerMessage::GameOver { winner } => {
+                game_state.winner = Some(if session.seat == Some(winner) { Side::Player } else { Side::Enemy });
+
+                // com a partida decidida o jogador mostra a frota que prometeu no COMMIT
+                let reveal = commitments.player_reveal();
+                session.send(&ClientMessage::Reveal(reveal));
+            }
+            ServerMessage::Commit { seat, commitment } => {
+                if session.seat != Some(seat) {
+                    commitments.opponent = Some(commitment);
+                }
+            }
+            ServerMessage::Reveal { seat, reveal } => {
+                if session.seat != Some(seat) {
+                    commitments.opponent_reveal = Some(reveal);
+                }
+            }
+            ServerMessage::OpponentDisconnected => {
+                notices.send(Notice::new("notice-opponent-disconnected"));
+            }
+            ServerMessage::OpponentReconnected => {
+                notices.send(Notice::new("notice-opponent-reconnected"));
+            }
+            ServerMessage::Error(error) => {
+                // tiro recusado: devolve a vez para quem ela é de fato
+                notices.send(Notice::new("notice-server-error").with("error", error));
+                game_state.is_player_turn = session.turn.is_some() && session.turn == session.seat;
+            }
+        }
+    }
+}
+
+// Enter envia o compromisso e a frota posicionada no tabuleiro para o servidor
+fn send_fleet_on_enter(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut session: ResMut<NetworkSession>,
+    commitments: Res<FleetCommitments>,
+    ships_query: Query<(&Ship, &ShipDirection), Without<SelectedShip>>,
+    cells_query: Query<&Cell>,
+) {
+    if !keyboard.just_pressed(KeyCode::Enter) || session.fleet_sent || session.seat.is_none() {
+        return;
+    }
+
+    let placements = placed_fleet(ships_query.iter().map(|(ship, _)| ship), &cells_query, Side::Player);
+    if let Some(commitment) = commitments.player {
+        session.send(&ClientMessage::Commit(commitment));
+    }
+    session.send(&ClientMessage::Fleet(placements));
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use super::localization::Locale;
+
+pub struct NoticesPlugin;
+
+const TOAST_SECONDS: f32 = 4.0;
+const NOTICE_COLOR: Color = Color::srgb(0.2, 0.25, 0.35);
+
+/**
+ * aviso de jogo (célula já marcada, adversário desconectou...) mostrado como
+ * toast. leva a chave do texto e os argumentos; quem mostra traduz
+ */
+#[derive(Event, Clone, Debug)]
+pub struct Notice {
+    pub key: &'static str,
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl Notice {
+    pub fn new(key: &'static str) -> Self {
+        Notice { key, args: Vec::new() }
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.args.push((name, value.to_string()));
+        self
+    }
+}
+
+#[derive(Component)]
+struct Toast {
+    timer: Timer,
+}
+
+impl Plugin for NoticesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Notice>()
+            .add_systems(Update, (show_notices, expire_toasts));
+    }
+}
+
+fn show_notices(mut commands: Commands, mut notices: EventReader<Notice>, locale: Res<Locale>) {
+    for notice in notices.read() {
+        let args: Vec<(&str, &dyn std::fmt::Display)> = notice
+            .args
+            .iter()
+            .map(|(name, value)| (*name, value as &dyn std::fmt::Display))
+            .collect();
+        spawn_toast(&mut commands, &locale.format(notice.key, &args), NOTICE_COLOR);
+    }
+}
+
+// aviso no canto da tela que some sozinho
+pub fn spawn_toast(commands: &mut Commands, text: &str, color: Color) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(16.0),
+                right: Val::Px(16.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..Default::default()
+            },
+            BackgroundColor(color),
+            BorderRadius::all(Val::Px(8.0)),
+            Toast {
+                timer: Timer::from_seconds(TOAST_SECONDS, TimerMode::Once),
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(text),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toasts_query: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in toasts_query.iter_mut() {
+        if toast.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
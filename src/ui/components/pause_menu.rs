@@ -0,0 +1,200 @@
+use bevy::{input::mouse::MouseButtonInput, prelude::*};
+
+use crate::{
+    api::protocol::ClientMessage,
+    logic::cell::{Cell, Side},
+};
+
+use super::{
+    board::{GameState, ShotLog},
+    fleet_commitment::FleetCommitments,
+    localization::Localized,
+    hotseat::Hotseat,
+    main_menu::{leave_match_modes, screen_node, spawn_button, title, AppState},
+    network_match::NetworkSession,
+    replay::ReplaySession,
+    save_game::{save_game, SAVE_PATH},
+    settings::GameSettings,
+    ship_selection_panel::SelectedShip,
+    ships::{HiddenFleet, Ship, ShipDirection},
+    spectator::SpectatorSession,
+};
+
+pub struct PauseMenuPlugin;
+
+/**
+ * pausa da partida, só existe dentro de AppState::InGame. fora de Running
+ * o bot, os cliques no tabuleiro e o arrasto de navios ficam parados;
+ * numa partida em rede as mensagens do servidor continuam chegando
+ */
+#[derive(SubStates, Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[source(AppState = AppState::InGame)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+    ConfirmResign,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+enum PauseAction {
+    Resume,
+    Restart,
+    Resign,
+    ConfirmResign,
+    CancelResign,
+    Save,
+    QuitToMenu,
+}
+
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_sub_state::<PauseState>()
+            .enable_state_scoped_entities::<PauseState>()
+            .add_systems(OnEnter(PauseState::Paused), spawn_pause_menu)
+            .add_systems(OnEnter(PauseState::ConfirmResign), spawn_resign_confirmation)
+            // reiniciar passa por um estado intermediário para que OnEnter(InGame) rode de novo
+            .add_systems(OnEnter(AppState::Restarting), |mut next_state: ResMut<NextState<AppState>>| {
+                next_state.set(AppState::InGame);
+            })
+            .add_systems(
+                Update,
+                (toggle_pause_on_escape, handle_pause_buttons).run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+fn overlay(state: PauseState) -> impl Bundle {
+    (screen_node(), BackgroundColor(OVERLAY_COLOR), StateScoped(state))
+}
+
+fn spawn_pause_menu(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    network: Option<Res<NetworkSession>>,
+    spectator: Option<Res<SpectatorSession>>,
+    (hotseat, replay): (Option<Res<Hotseat>>, Option<Res<ReplaySession>>),
+    commitments: Res<FleetCommitments>,
+) {
+    let local = network.is_none() && spectator.is_none() && replay.is_none();
+    let playing = spectator.is_none() && replay.is_none() && game_state.winner.is_none();
+    // o jogo salvo é contra o bot; a partida em rede só no fim, para o replay, com a frota do adversário revelada
+    let finished_online = network.is_some() && game_state.winner.is_some() && commitments.opponent_reveal.is_some();
+    let saves = (local && hotseat.is_none()) || finished_online;
+
+    // em rede não dá para reiniciar: a partida é do servidor. no replay ninguém joga
+    commands
+        .spawn(overlay(PauseState::Paused))
+        .with_children(|parent| {
+            parent.spawn(title("pause-title"));
+            spawn_button(parent, "pause-resume", Some(PauseAction::Resume));
+            spawn_button(parent, "pause-restart", local.then_some(PauseAction::Restart));
+            spawn_button(parent, "pause-resign", playing.then_some(PauseAction::Resign));
+            spawn_button(parent, "pause-save", saves.then_some(PauseAction::Save));
+            spawn_button(parent, "pause-quit-to-menu", Some(PauseAction::QuitToMenu));
+        });
+}
+
+fn spawn_resign_confirmation(mut commands: Commands) {
+    commands
+        .spawn(overlay(PauseState::ConfirmResign))
+        .with_children(|parent| {
+            parent.spawn(title("resign-title"));
+            parent.spawn((
+                Text::default(),
+                Localized("resign-hint"),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+            ));
+            spawn_button(parent, "resign-confirm", Some(PauseAction::ConfirmResign));
+            spawn_button(parent, "resign-cancel", Some(PauseAction::CancelResign));
+        });
+}
+
+fn toggle_pause_on_escape(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    pause_state: Res<State<PauseState>>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+) {
+    if !keyboard.just_pressed(KeyCode::Escape) {
+        return;
+    }
+
+    next_pause.set(match pause_state.get() {
+        PauseState::Running => PauseState::Paused,
+        PauseState::Paused => PauseState::Running,
+        PauseState::ConfirmResign => PauseState::Paused,
+    });
+}
+
+fn handle_pause_buttons(
+    mut commands: Commands,
+    buttons_query: Query<(&Interaction, &PauseAction), Changed<Interaction>>,
+    mut game_state: ResMut<GameState>,
+    mut next_pause: ResMut<NextState<PauseState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut mouse_events: ResMut<Events<MouseButtonInput>>,
+    mut network: Option<ResMut<NetworkSession>>,
+    settings: Res<GameSettings>,
+    shot_log: Res<ShotLog>,
+    ships_query: Query<(&Ship, &ShipDirection), Without<SelectedShip>>,
+    hidden: Res<HiddenFleet>,
+    cells_query: Query<&Cell>,
+    hotseat: Option<Res<Hotseat>>,
+    commitments: Res<FleetCommitments>,
+) {
+    for (interaction, action) in buttons_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        // o clique no botão não pode chegar ao tabuleiro quando a partida volta a rodar
+        mouse_events.clear();
+
+        match action {
+            PauseAction::Resume => next_pause.set(PauseState::Running),
+            PauseAction::Restart => next_state.set(AppState::Restarting),
+            PauseAction::Resign => next_pause.set(PauseState::ConfirmResign),
+            PauseAction::CancelResign => next_pause.set(PauseState::Paused),
+            // desistir é uma derrota comum: a tela de fim de jogo e as estatísticas seguem o fluxo normal
+            PauseAction::ConfirmResign => {
+                if let Some(session) = network.as_mut() {
+                    session.send(&ClientMessage::Leave);
+                }
+
+                // com dois jogadores quem desiste é quem está com a vez
+                game_state.resigned = true;
+                game_state.winner = Some(match hotseat {
+                    Some(_) => game_state.shooter().opponent(),
+                    None => Side::Enemy,
+                });
+                next_pause.set(PauseState::Running);
+            }
+            PauseAction::Save => {
+                let revealed = network
+                    .as_ref()
+                    .and(commitments.opponent_reveal.as_ref())
+                    .map(|reveal| &reveal.layout);
+
+                match save_game(&settings, &game_state, &shot_log, &ships_query, &hidden, &cells_query, revealed) {
+                    Ok(()) => info!("partida salva em {SAVE_PATH}"),
+                    Err(error) => warn!("não foi possível salvar a partida: {error}"),
+                }
+                next_pause.set(PauseState::Running);
+            }
+            PauseAction::QuitToMenu => {
+                if let Some(session) = network.as_mut() {
+                    session.send(&ClientMessage::Leave);
+                }
+
+                leave_match_modes(&mut commands);
+                commands.remove_resource::<SpectatorSession>();
+                next_state.set(AppState::MainMenu);
+            }
+        }
+    }
+}
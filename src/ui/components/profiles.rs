@@ -0,0 +1,487 @@
+use std::{fs, path::Path};
+
+use bevy::prelude::*;
+
+use super::{
+    achievements::ACHIEVEMENTS,
+    localization::Locale,
+    settings::{option_from_name, BotDifficulty},
+    ships::ShipType,
+};
+
+pub const PROFILES_PATH: &str = "saves/perfis.txt";
+// cópia do arquivo que não pôde ser lido, antes de o próximo save escrever por cima
+pub const PROFILES_BACKUP_PATH: &str = "saves/perfis.txt.bak";
+
+// contra quem a partida foi jogada
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchMode {
+    Bot,      // bot local, separado por dificuldade
+    External, // motor externo (--bot-exe)
+    Network,  // servidor dedicado ou rede local
+}
+
+impl MatchMode {
+    pub const ALL: [MatchMode; 3] = [MatchMode::Bot, MatchMode::External, MatchMode::Network];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            MatchMode::Bot => "mode-bot",
+            MatchMode::External => "mode-external",
+            MatchMode::Network => "mode-network",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ModeRecord {
+    pub mode: MatchMode,
+    pub difficulty: Option<BotDifficulty>, // só partidas contra o bot local
+    pub played: u32,
+    pub wins: u32,
+    pub losses: u32,
+}
+
+// resumo de uma partida terminada, montado na tela de fim de jogo
+#[derive(Clone, Debug)]
+pub struct MatchSummary {
+    pub mode: MatchMode,
+    pub difficulty: Option<BotDifficulty>,
+    pub won: bool,
+    pub resigned: bool,
+    pub shots: u32,
+    pub hits: u32,
+    pub longest_streak: u32,
+    pub seconds: u64,
+    pub sunk: Vec<ShipType>, // navios inimigos afundados pelo jogador
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Profile {
+    pub name: String,
+    pub records: Vec<ModeRecord>,
+    pub shots: u32,
+    pub hits: u32,
+    pub longest_streak: u32,
+    pub shots_in_wins: u32, // soma dos tiros das vitórias, para a média
+    pub fastest_win: Option<u64>, // em segundos
+    pub resignations: u32,
+    pub sunk: Vec<(ShipType, u32)>,
+    pub achievements: Vec<String>, // identificadores das conquistas desbloqueadas
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Self {
+        Profile {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn record_mut(&mut self, mode: MatchMode, difficulty: Option<BotDifficulty>) -> &mut ModeRecord {
+        let index = match self
+            .records
+            .iter()
+            .position(|record| record.mode == mode && record.difficulty == difficulty)
+        {
+            Some(index) => index,
+            None => {
+                self.records.push(ModeRecord {
+                    mode,
+                    difficulty,
+                    played: 0,
+                    wins: 0,
+                    losses: 0,
+                });
+                self.records.len() - 1
+            }
+        };
+
+        &mut self.records[index]
+    }
+
+    fn sunk_mut(&mut self, ship_type: &ShipType) -> &mut u32 {
+        let index = match self.sunk.iter().position(|(sunk, _)| sunk == ship_type) {
+            Some(index) => index,
+            None => {
+                self.sunk.push((ship_type.clone(), 0));
+                self.sunk.len() - 1
+            }
+        };
+
+        &mut self.sunk[index].1
+    }
+
+    pub fn record(&mut self, summary: &MatchSummary) {
+        let record = self.record_mut(summary.mode, summary.difficulty);
+        record.played += 1;
+        if summary.won {
+            record.wins += 1;
+        } else {
+            record.losses += 1;
+        }
+
+        self.shots += summary.shots;
+        self.hits += summary.hits;
+        self.longest_streak = self.longest_streak.max(summary.longest_streak);
+
+        if summary.won {
+            self.shots_in_wins += summary.shots;
+            self.fastest_win = Some(
+                self.fastest_win
+                    .map_or(summary.seconds, |fastest| fastest.min(summary.seconds)),
+            );
+        }
+
+        if summary.resigned {
+            self.resignations += 1;
+        }
+
+        for ship_type in &summary.sunk {
+            *self.sunk_mut(ship_type) += 1;
+        }
+    }
+
+    pub fn played(&self) -> u32 {
+        self.records.iter().map(|record| record.played).sum()
+    }
+
+    pub fn wins(&self) -> u32 {
+        self.records.iter().map(|record| record.wins).sum()
+    }
+
+    pub fn losses(&self) -> u32 {
+        self.records.iter().map(|record| record.losses).sum()
+    }
+
+    pub fn accuracy(&self) -> f32 {
+        if self.shots == 0 {
+            0.0
+        } else {
+            self.hits as f32 * 100.0 / self.shots as f32
+        }
+    }
+
+    pub fn average_shots_to_win(&self) -> Option<f32> {
+        let wins = self.wins();
+        (wins > 0).then(|| self.shots_in_wins as f32 / wins as f32)
+    }
+
+    // texto da tela de perfis
+    pub fn summary(&self, locale: &Locale) -> String {
+        let mut lines = vec![locale.format(
+            "profile-games",
+            &[
+                ("played", &self.played()),
+                ("wins", &self.wins()),
+                ("losses", &self.losses()),
+                ("resignations", &self.resignations),
+            ],
+        )];
+
+        for record in &self.records {
+            let mode = match record.difficulty {
+                Some(difficulty) => format!("{} {}", locale.get(record.mode.key()), locale.get(difficulty.key())),
+                None => locale.get(record.mode.key()),
+            };
+            lines.push(format!(
+                "  {}",
+                locale.format(
+                    "profile-mode",
+                    &[
+                        ("mode", &mode),
+                        ("played", &record.played),
+                        ("wins", &record.wins),
+                        ("losses", &record.losses),
+                    ],
+                )
+            ));
+        }
+
+        lines.push(locale.format(
+            "profile-accuracy",
+            &[
+                ("accuracy", &format!("{:.0}", self.accuracy())),
+                ("streak", &self.longest_streak),
+            ],
+        ));
+        lines.push(locale.format(
+            "profile-wins",
+            &[
+                (
+                    "average",
+                    &self
+                        .average_shots_to_win()
+                        .map_or("-".to_string(), |average| format!("{average:.1}")),
+                ),
+                (
+                    "fastest",
+                    &self
+                        .fastest_win
+                        .map_or("-".to_string(), |seconds| format!("{}:{:02}", seconds / 60, seconds % 60)),
+                ),
+            ],
+        ));
+
+        let sunk: Vec<String> = self
+            .sunk
+            .iter()
+            .map(|(ship_type, count)| format!("{} {count}", locale.get(ship_type.key())))
+            .collect();
+        lines.push(locale.format(
+            "profile-sunk",
+            &[("ships", &if sunk.is_empty() { "-".to_string() } else { sunk.join(", ") })],
+        ));
+
+        lines.push(locale.format(
+            "profile-achievements",
+            &[("unlocked", &self.achievements.len()), ("total", &ACHIEVEMENTS.len())],
+        ));
+
+        lines.join("\n")
+    }
+}
+
+/**
+ * perfis salvos no computador, em texto:
+ *
+ *   active <nome>
+ *   profile <nome>
+ *   record <modo> <dificuldade|-> <partidas> <vitórias> <derrotas>
+ *   shots <tiros> <acertos> <maior sequência>
+ *   wins <tiros nas vitórias> <vitória mais rápida em segundos|->
+ *   resigned <desistências>
+ *   sunk <navio> <quantidade>
+ *   achievement <conquista>
+ *
+ * as linhas depois de "profile" pertencem a ele até o próximo "profile".
+ * o nome vai até o fim da linha, então pode ter espaços
+ */
+#[derive(Resource, Debug)]
+pub struct Profiles {
+    pub active: usize,
+    pub profiles: Vec<Profile>,
+    pub writable: bool, // falso se o arquivo estragado não pôde ser guardado: save não escreve por cima
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Profiles {
+            active: 0,
+            profiles: vec![Profile::new("jogador")],
+            writable: true,
+        }
+    }
+}
+
+// nome depois do comando da linha, com os espaços do meio
+fn name_after<'a>(line: &'a str, command: &str) -> Result<&'a str, String> {
+    line.trim()
+        .strip_prefix(command)
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| format!("nome faltando: {line}"))
+}
+
+fn number<T: std::str::FromStr>(value: Option<&str>, line: &str) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("linha inválida: {line}"))
+}
+
+impl Profiles {
+    pub fn active(&self) -> &Profile {
+        &self.profiles[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Profile {
+        &mut self.profiles[self.active]
+    }
+
+    // seleciona o perfil, criando se ainda não existe
+    pub fn select(&mut self, name: &str) {
+        self.active = match self.profiles.iter().position(|profile| profile.name == name) {
+            Some(index) => index,
+            None => {
+                self.profiles.push(Profile::new(name));
+                self.profiles.len() - 1
+            }
+        };
+    }
+
+    pub fn select_next(&mut self) {
+        self.active = (self.active + 1) % self.profiles.len();
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![format!("active {}", self.active().name)];
+
+        for profile in &self.profiles {
+            lines.push(format!("profile {}", profile.name));
+
+            for record in &profile.records {
+                lines.push(format!(
+                    "record {:?} {} {} {} {}",
+                    record.mode,
+                    record
+                        .difficulty
+                        .map_or("-".to_string(), |difficulty| format!("{difficulty:?}")),
+                    record.played,
+                    record.wins,
+                    record.losses
+                ));
+            }
+
+            lines.push(format!(
+                "shots {} {} {}",
+                profile.shots, profile.hits, profile.longest_streak
+            ));
+            lines.push(format!(
+                "wins {} {}",
+                profile.shots_in_wins,
+                profile
+                    .fastest_win
+                    .map_or("-".to_string(), |seconds| seconds.to_string())
+            ));
+            lines.push(format!("resigned {}", profile.resignations));
+
+            for (ship_type, count) in &profile.sunk {
+                lines.push(format!("sunk {} {count}", ship_type.name()));
+            }
+
+            for achievement in &profile.achievements {
+                lines.push(format!("achievement {achievement}"));
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut active = None;
+        let mut profiles: Vec<Profile> = Vec::new();
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let mut words = line.split_whitespace();
+            let command = words.next();
+
+            if command == Some("active") {
+                active = Some(name_after(line, "active")?.to_string());
+                continue;
+            }
+            if command == Some("profile") {
+                profiles.push(Profile::new(name_after(line, "profile")?));
+                continue;
+            }
+
+            let profile = profiles
+                .last_mut()
+                .ok_or_else(|| format!("linha fora de um perfil: {line}"))?;
+
+            match command {
+                Some("record") => {
+                    let mode = words
+                        .next()
+                        .and_then(|mode| option_from_name(&MatchMode::ALL, mode))
+                        .ok_or_else(|| format!("modo inválido: {line}"))?;
+                    let difficulty = match words.next() {
+                        Some("-") => None,
+                        Some(name) => Some(
+                            option_from_name(&BotDifficulty::ALL, name)
+                                .ok_or_else(|| format!("dificuldade inválida: {line}"))?,
+                        ),
+                        None => return Err(format!("linha inválida: {line}")),
+                    };
+
+                    profile.records.push(ModeRecord {
+                        mode,
+                        difficulty,
+                        played: number(words.next(), line)?,
+                        wins: number(words.next(), line)?,
+                        losses: number(words.next(), line)?,
+                    });
+                }
+                Some("shots") => {
+                    profile.shots = number(words.next(), line)?;
+                    profile.hits = number(words.next(), line)?;
+                    profile.longest_streak = number(words.next(), line)?;
+                }
+                Some("wins") => {
+                    profile.shots_in_wins = number(words.next(), line)?;
+                    profile.fastest_win = match words.next() {
+                        Some("-") => None,
+                        value => Some(number(value, line)?),
+                    };
+                }
+                Some("resigned") => profile.resignations = number(words.next(), line)?,
+                Some("sunk") => {
+                    // navios do arquivo de regras entram só pelo nome
+                    let ship_type = words
+                        .next()
+                        .map(ShipType::named)
+                        .ok_or_else(|| format!("navio inválido: {line}"))?;
+                    profile.sunk.push((ship_type, number(words.next(), line)?));
+                }
+                Some("achievement") => {
+                    let id = words.next().ok_or_else(|| format!("linha inválida: {line}"))?;
+                    profile.achievements.push(id.to_string());
+                }
+                _ => return Err(format!("linha inválida: {line}")),
+            }
+        }
+
+        let mut loaded = Profiles {
+            active: 0,
+            profiles,
+            writable: true,
+        };
+
+        match active {
+            Some(name) => loaded.select(&name),
+            None if loaded.profiles.is_empty() => return Ok(Profiles::default()),
+            None => {}
+        }
+
+        Ok(loaded)
+    }
+
+    /**
+     * sem arquivo começa com o perfil padrão. com arquivo estragado também,
+     * mas antes o arquivo é copiado para PROFILES_BACKUP_PATH; se nem a cópia
+     * der certo os perfis ficam só na memória, para não perder o arquivo
+     */
+    pub fn load() -> Self {
+        let Ok(text) = fs::read_to_string(PROFILES_PATH) else {
+            return Profiles::default();
+        };
+
+        Profiles::parse(&text).unwrap_or_else(|error| {
+            warn!("perfis inválidos em {PROFILES_PATH}: {error}");
+
+            let mut profiles = Profiles::default();
+            match fs::copy(PROFILES_PATH, PROFILES_BACKUP_PATH) {
+                Ok(_) => warn!("arquivo antigo guardado em {PROFILES_BACKUP_PATH}"),
+                Err(error) => {
+                    warn!("não foi possível guardar {PROFILES_BACKUP_PATH} ({error}), os perfis não serão salvos");
+                    profiles.writable = false;
+                }
+            }
+            profiles
+        })
+    }
+
+    pub fn save(&self) {
+        if !self.writable {
+            return;
+        }
+
+        let result = Path::new(PROFILES_PATH)
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(PROFILES_PATH, self.to_text()));
+
+        if let Err(error) = result {
+            warn!("não foi possível salvar os perfis: {error}");
+        }
+    }
+}
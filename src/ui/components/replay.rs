@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+
+use crate::logic::cell::Side;
+
+use super::{
+    board::{start_match, BoardIndex, GameState, ShotLog, SLOT_SIZE, SLOT_SPACE_BETWEEN},
+    localization::Locale,
+    main_menu::AppState,
+    pause_menu::PauseState,
+    save_game::{replay_saved_shots, PendingLoad, SavedGame},
+    settings::GameSettings,
+    theme::Theme,
+};
+
+pub struct ReplayPlugin;
+
+/**
+ * replay do jogo salvo, com as duas frotas à mostra e ninguém jogando.
+ * cada passo remonta a partida até o tiro escolhido pelo mesmo caminho de
+ * um jogo carregado; as setas andam um tiro, Home e End vão às pontas.
+ * as respostas que a auditoria da partida achou falsas ficam destacadas
+ */
+#[derive(Resource, Debug)]
+pub struct ReplaySession {
+    pub saved: SavedGame,
+    pub shown: usize, // tiros já refeitos no tabuleiro
+}
+
+#[derive(Component)]
+struct ReplayHud;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::InGame),
+            (
+                queue_replay_step.before(start_match),
+                setup_replay_hud.after(start_match),
+                mark_lies.after(replay_saved_shots),
+            )
+                .run_if(resource_exists::<ReplaySession>),
+        )
+        .add_systems(
+            Update,
+            (step_replay.run_if(in_state(PauseState::Running)), update_replay_hud)
+                .chain()
+                .run_if(in_state(AppState::InGame))
+                .run_if(resource_exists::<ReplaySession>),
+        );
+    }
+}
+
+// o passo atual entra como um jogo carregado, que replay_saved_shots refaz
+fn queue_replay_step(mut commands: Commands, session: Res<ReplaySession>) {
+    commands.insert_resource(PendingLoad(session.saved.until(session.shown)));
+}
+
+fn setup_replay_hud(mut commands: Commands, settings: Res<GameSettings>) {
+    let top = settings.rows() as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0;
+
+    commands.spawn((
+        Text2d::new(""),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, top + 12.0, 1.0)),
+        ReplayHud,
+        StateScoped(AppState::InGame),
+    ));
+}
+
+// a cor de destaque por cima do resultado que o tiro teria de verdade
+fn mark_lies(
+    shot_log: Res<ShotLog>,
+    index: Res<BoardIndex>,
+    theme: Res<Theme>,
+    mut cells_query: Query<&mut Sprite>,
+) {
+    for record in shot_log.records.iter().filter(|record| record.lie) {
+        if let Some(mut sprite) = index.at(record.row, record.column).and_then(|cell| cells_query.get_mut(cell).ok()) {
+            sprite.color = theme.palette().highlight;
+        }
+    }
+}
+
+// trocar de passo reinicia a partida, como o "reiniciar" do menu de pausa
+fn step_replay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut session: ResMut<ReplaySession>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let total = session.saved.shots.len();
+    let shown = if keyboard.just_pressed(KeyCode::ArrowRight) {
+        (session.shown + 1).min(total)
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        session.shown.saturating_sub(1)
+    } else if keyboard.just_pressed(KeyCode::Home) {
+        0
+    } else if keyboard.just_pressed(KeyCode::End) {
+        total
+    } else {
+        return;
+    };
+
+    if shown != session.shown {
+        session.shown = shown;
+        next_state.set(AppState::Restarting);
+    }
+}
+
+fn update_replay_hud(
+    session: Res<ReplaySession>,
+    game_state: Res<GameState>,
+    shot_log: Res<ShotLog>,
+    mut hud_query: Query<&mut Text2d, With<ReplayHud>>,
+    locale: Res<Locale>,
+) {
+    let Ok(mut text) = hud_query.get_single_mut() else {
+        return;
+    };
+
+    let name = |side| locale.get(side_key(side));
+    let status = match game_state.winner {
+        Some(winner) => locale.format("spectator-winner", &[("name", &name(winner))]),
+        None => locale.format("spectator-turn", &[("name", &name(game_state.shooter()))]),
+    };
+    let mut lines = vec![
+        locale.format(
+            "replay-status",
+            &[("shown", &session.shown), ("total", &session.saved.shots.len()), ("status", &status)],
+        ),
+        locale.get("replay-hint"),
+    ];
+
+    let lies: Vec<String> = shot_log
+        .records
+        .iter()
+        .filter(|record| record.lie)
+        .map(|record| format!("({}, {})", record.row, record.column))
+        .collect();
+    if !lies.is_empty() {
+        lines.push(locale.format("victory-lie-cells", &[("cells", &lies.join(" "))]));
+    }
+
+    let hud = lines.join("\n");
+
+    if text.0 != hud {
+        text.0 = hud;
+    }
+}
+
+// os nomes do fim de jogo: o lado de baixo é o do jogador
+fn side_key(side: Side) -> &'static str {
+    match side {
+        Side::Player => "victory-player",
+        Side::Enemy => "victory-bot",
+    }
+}
@@ -0,0 +1,518 @@
+use std::{fs, path::Path};
+
+use bevy::prelude::*;
+
+use crate::{
+    api::protocol::{parse_placement, placement_to_str},
+    logic::{
+        ability::Ability,
+        cell::{Cell, Side},
+        commitment::FleetLayout,
+        engine::ShipPlacement,
+    },
+};
+
+use super::{
+    board::{move_ship, AbilityRecord, BoardIndex, GameState, MoveRecord, ShotGrid, ShotLog},
+    settings::{option_from_name, BoardSize, BotDifficulty, FleetPreset, GameSettings, MapPreset, RuleVariant},
+    ship_selection_panel::SelectedShip,
+    ships::{placed_fleet, Fleet, HiddenFleet, Ship, ShipCatalog, ShipDirection},
+    theme::Theme,
+};
+
+pub const SAVE_PATH: &str = "saves/partida.txt";
+
+/**
+ * partida contra o bot gravada em texto, uma informação por linha:
+ *
+ *   settings <tabuleiro> <frota> <regras> <dificuldade> [mapa]
+ *   player <navio:linha,coluna,tamanho,H|V> ...
+ *   enemy <navio:linha,coluna,tamanho,H|V> ...
+ *   shot P|B <linha> <coluna> [lie] (uma por tiro, na ordem; "lie" se a auditoria achou a resposta falsa)
+ *   ability P|B <habilidade>       (depois dos tiros que ela deu; o sonar não dá nenhum)
+ *   move P|B <linha> <coluna> up|down|left|right
+ *   penalty P|B                    (caiu numa mina e ainda vai perder a vez)
+ *   moved P                        (andou com um navio e o bot ainda não jogou depois)
+ *   winner P|B                     (partida terminada, que só abre no replay)
+ *   turn P|B
+ *
+ * as linhas dos navios, dos tiros e dos movimentos são do tabuleiro inteiro.
+ * as frotas são as do começo da partida: cada "move" anda uma célula com o
+ * navio que está em (linha, coluna) naquele ponto da sequência de tiros.
+ * uma partida em rede só é salva no fim, com a frota revelada pelo adversário
+ */
+#[derive(Clone, Debug)]
+pub struct SavedGame {
+    pub board_size: BoardSize,
+    pub fleet: FleetPreset,
+    pub variant: RuleVariant,
+    pub difficulty: BotDifficulty,
+    pub map: MapPreset,
+    pub player_fleet: Vec<ShipPlacement>,
+    pub enemy_fleet: Vec<ShipPlacement>,
+    pub shots: Vec<(Side, usize, usize)>, // quem atirou, linha, coluna
+    pub lies: Vec<usize>, // posição em `shots` das respostas que a auditoria achou falsas
+    pub abilities: Vec<AbilityRecord>,
+    pub moves: Vec<MoveRecord>,
+    pub penalty: Option<Side>,
+    pub ship_moved: Option<Side>,
+    pub winner: Option<Side>,
+    pub turn: Side,
+}
+
+// jogo lido do arquivo esperando a partida começar para ser montado no tabuleiro
+#[derive(Resource)]
+pub struct PendingLoad(pub SavedGame);
+
+fn shooter_to_str(shooter: Side) -> &'static str {
+    match shooter {
+        Side::Player => "P",
+        Side::Enemy => "B",
+    }
+}
+
+fn parse_shooter(value: &str) -> Result<Side, String> {
+    match value {
+        "P" => Ok(Side::Player),
+        "B" => Ok(Side::Enemy),
+        _ => Err(format!("atirador inválido: {value}")),
+    }
+}
+
+// "up" é a linha seguinte, "right" a coluna seguinte
+const STEPS: [((isize, isize), &str); 4] = [((1, 0), "up"), ((-1, 0), "down"), ((0, -1), "left"), ((0, 1), "right")];
+
+fn step_to_str(step: (isize, isize)) -> &'static str {
+    STEPS
+        .iter()
+        .find(|(known, _)| *known == step)
+        .map_or("?", |(_, name)| name)
+}
+
+fn parse_step(value: &str) -> Result<(isize, isize), String> {
+    STEPS
+        .iter()
+        .find(|(_, name)| *name == value)
+        .map(|(step, _)| *step)
+        .ok_or_else(|| format!("direção inválida: {value}"))
+}
+
+fn fleet_line(label: &str, fleet: &[ShipPlacement]) -> String {
+    let ships: Vec<String> = fleet.iter().map(placement_to_str).collect();
+    format!("{label} {}", ships.join(" ")).trim_end().to_string()
+}
+
+fn parse_option<T: Copy + std::fmt::Debug>(options: &[T], name: Option<&str>) -> Result<T, String> {
+    let name = name.ok_or("configuração faltando")?;
+    option_from_name(options, name).ok_or_else(|| format!("opção inválida: {name}"))
+}
+
+impl SavedGame {
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "settings {:?} {:?} {:?} {:?} {:?}",
+                self.board_size, self.fleet, self.variant, self.difficulty, self.map
+            ),
+            fleet_line("player", &self.player_fleet),
+            fleet_line("enemy", &self.enemy_fleet),
+        ];
+
+        for (index, (shooter, row, column)) in self.shots.iter().enumerate() {
+            lines.extend(self.play_lines(index));
+            let lie = if self.lies.contains(&index) { " lie" } else { "" };
+            lines.push(format!("shot {} {row} {column}{lie}", shooter_to_str(*shooter)));
+        }
+        lines.extend(self.play_lines(self.shots.len()));
+
+        if let Some(side) = self.penalty {
+            lines.push(format!("penalty {}", shooter_to_str(side)));
+        }
+        if let Some(side) = self.ship_moved {
+            lines.push(format!("moved {}", shooter_to_str(side)));
+        }
+        if let Some(side) = self.winner {
+            lines.push(format!("winner {}", shooter_to_str(side)));
+        }
+        lines.push(format!("turn {}", shooter_to_str(self.turn)));
+        lines.join("\n") + "\n"
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut saved = SavedGame {
+            board_size: BoardSize::Classic,
+            fleet: FleetPreset::Classic,
+            variant: RuleVariant::Classic,
+            difficulty: BotDifficulty::Normal,
+            map: MapPreset::Open,
+            player_fleet: Vec::new(),
+            enemy_fleet: Vec::new(),
+            shots: Vec::new(),
+            lies: Vec::new(),
+            abilities: Vec::new(),
+            moves: Vec::new(),
+            penalty: None,
+            ship_moved: None,
+            winner: None,
+            turn: Side::Player,
+        };
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let mut words = line.split_whitespace();
+
+            match words.next() {
+                Some("settings") => {
+                    saved.board_size = parse_option(&BoardSize::ALL, words.next())?;
+                    saved.fleet = parse_option(&FleetPreset::ALL, words.next())?;
+                    saved.variant = parse_option(&RuleVariant::ALL, words.next())?;
+                    saved.difficulty = parse_option(&BotDifficulty::ALL, words.next())?;
+                    // jogos salvos antes dos mapas não têm o quinto campo
+                    if let Some(map) = words.next() {
+                        saved.map = parse_option(&MapPreset::ALL, Some(map))?;
+                    }
+                }
+                Some("player") => {
+                    saved.player_fleet = words.map(parse_placement).collect::<Result<_, _>>()?;
+                }
+                Some("enemy") => {
+                    saved.enemy_fleet = words.map(parse_placement).collect::<Result<_, _>>()?;
+                }
+                Some("shot") => {
+                    let (Some(shooter), Some(row), Some(column)) = (words.next(), words.next(), words.next())
+                    else {
+                        return Err(format!("tiro inválido: {line}"));
+                    };
+
+                    match words.next() {
+                        Some("lie") => saved.lies.push(saved.shots.len()),
+                        Some(_) => return Err(format!("tiro inválido: {line}")),
+                        None => {}
+                    }
+                    saved.shots.push((
+                        parse_shooter(shooter)?,
+                        row.parse().map_err(|_| format!("tiro inválido: {line}"))?,
+                        column.parse().map_err(|_| format!("tiro inválido: {line}"))?,
+                    ));
+                }
+                Some("ability") => {
+                    let (Some(shooter), Some(ability)) = (words.next(), words.next()) else {
+                        return Err(format!("habilidade inválida: {line}"));
+                    };
+
+                    saved.abilities.push(AbilityRecord {
+                        after: saved.shots.len(),
+                        shooter: parse_shooter(shooter)?,
+                        ability: Ability::from_name(ability).ok_or_else(|| format!("habilidade inválida: {line}"))?,
+                    });
+                }
+                Some("move") => {
+                    let (Some(side), Some(row), Some(column), Some(step)) =
+                        (words.next(), words.next(), words.next(), words.next())
+                    else {
+                        return Err(format!("movimento inválido: {line}"));
+                    };
+
+                    saved.moves.push(MoveRecord {
+                        after: saved.shots.len(),
+                        side: parse_shooter(side)?,
+                        row: row.parse().map_err(|_| format!("movimento inválido: {line}"))?,
+                        column: column.parse().map_err(|_| format!("movimento inválido: {line}"))?,
+                        step: parse_step(step)?,
+                    });
+                }
+                Some("penalty") => saved.penalty = Some(parse_shooter(words.next().unwrap_or_default())?),
+                Some("moved") => saved.ship_moved = Some(parse_shooter(words.next().unwrap_or_default())?),
+                Some("winner") => saved.winner = Some(parse_shooter(words.next().unwrap_or_default())?),
+                Some("turn") => saved.turn = parse_shooter(words.next().unwrap_or_default())?,
+                _ => return Err(format!("linha inválida: {line}")),
+            }
+        }
+
+        Ok(saved)
+    }
+
+    // movimentos e habilidades depois de `shots` tiros
+    fn play_lines(&self, shots: usize) -> impl Iterator<Item = String> + '_ {
+        let moves = self
+            .moves
+            .iter()
+            .filter(move |record| record.after == shots)
+            .map(|record| {
+                format!(
+                    "move {} {} {} {}",
+                    shooter_to_str(record.side),
+                    record.row,
+                    record.column,
+                    step_to_str(record.step)
+                )
+            });
+        let abilities = self
+            .abilities
+            .iter()
+            .filter(move |record| record.after == shots)
+            .map(|record| format!("ability {} {}", shooter_to_str(record.shooter), record.ability.name()));
+
+        moves.chain(abilities)
+    }
+
+    /**
+     * o replay passa a vez tiro a tiro, sem saber quais tiros vieram juntos de
+     * uma habilidade; no fim valem a vez, a mina pendente e o movimento que o
+     * bot ainda não viu como estavam na hora de salvar. o vencedor gravado
+     * vale mesmo quando não sai dos tiros, como numa desistência
+     */
+    pub fn restore_turn(&self, game_state: &mut GameState) {
+        game_state.is_player_turn = self.turn == Side::Player;
+        game_state.penalty = self.penalty;
+        game_state.ship_moved = self.ship_moved;
+        if self.winner.is_some() {
+            game_state.winner = self.winner;
+        }
+    }
+
+    /**
+     * o jogo como estava depois dos primeiros `shots` tiros, para o replay.
+     * a vez é de quem deu o tiro seguinte; mina e movimento pendentes e o
+     * vencedor só valem no fim da partida salva
+     */
+    pub fn until(&self, shots: usize) -> SavedGame {
+        let mut saved = self.clone();
+        saved.shots.truncate(shots);
+        saved.lies.retain(|shot| *shot < shots);
+        saved.abilities.retain(|record| record.after <= shots);
+        saved.moves.retain(|record| record.after <= shots);
+
+        if let Some((shooter, _, _)) = self.shots.get(shots) {
+            saved.turn = *shooter;
+            saved.penalty = None;
+            saved.ship_moved = None;
+            saved.winner = None;
+        }
+
+        saved
+    }
+
+    pub fn apply_settings(&self, settings: &mut GameSettings) {
+        settings.board_size = self.board_size;
+        settings.fleet = self.fleet;
+        settings.variant = self.variant;
+        settings.difficulty = self.difficulty;
+        settings.map = self.map;
+    }
+}
+
+// frota do começo da partida: desfaz os movimentos do lado, do último para o primeiro
+fn starting_fleet(mut fleet: Vec<ShipPlacement>, side: Side, moves: &[MoveRecord]) -> Vec<ShipPlacement> {
+    for record in moves.iter().rev().filter(|record| record.side == side) {
+        let moved = (
+            record.row.checked_add_signed(record.step.0),
+            record.column.checked_add_signed(record.step.1),
+        );
+        let (Some(row), Some(column)) = moved else {
+            continue;
+        };
+
+        if let Some(placement) = fleet
+            .iter_mut()
+            .find(|placement| placement.cells().contains(&(row, column)))
+        {
+            placement.row = placement.row.wrapping_add_signed(-record.step.0);
+            placement.column = placement.column.wrapping_add_signed(-record.step.1);
+        }
+    }
+
+    fleet
+}
+
+// `revealed` é a frota do adversário em rede, que só chega na revelação do fim de jogo (em linhas do lado)
+pub fn save_game(
+    settings: &GameSettings,
+    game_state: &GameState,
+    shot_log: &ShotLog,
+    ships_query: &Query<(&Ship, &ShipDirection), Without<SelectedShip>>,
+    hidden: &HiddenFleet,
+    cells_query: &Query<&Cell>,
+    revealed: Option<&FleetLayout>,
+) -> std::io::Result<()> {
+    // a frota inimiga ainda escondida também vai para o arquivo
+    let ships = || ships_query.iter().map(|(ship, _)| ship).chain(hidden.ships());
+    let fleet = |side| starting_fleet(placed_fleet(ships(), cells_query, side), side, &shot_log.moves);
+    let enemy_fleet = match revealed {
+        Some(layout) => {
+            let mut placements = layout.placements().map_err(std::io::Error::other)?;
+            for placement in &mut placements {
+                placement.row += settings.rows() / 2;
+            }
+            placements
+        }
+        None => fleet(Side::Enemy),
+    };
+    let saved = SavedGame {
+        board_size: settings.board_size,
+        fleet: settings.fleet,
+        variant: settings.variant,
+        difficulty: settings.difficulty,
+        map: settings.map,
+        player_fleet: fleet(Side::Player),
+        enemy_fleet,
+        shots: shot_log
+            .records
+            .iter()
+            .map(|record| (record.shooter, record.row, record.column))
+            .collect(),
+        lies: shot_log
+            .records
+            .iter()
+            .enumerate()
+            .filter(|(_, record)| record.lie)
+            .map(|(position, _)| position)
+            .collect(),
+        abilities: shot_log.abilities.clone(),
+        moves: shot_log.moves.clone(),
+        penalty: game_state.penalty,
+        ship_moved: game_state.ship_moved,
+        winner: game_state.winner,
+        turn: game_state.shooter(),
+    };
+
+    if let Some(folder) = Path::new(SAVE_PATH).parent() {
+        fs::create_dir_all(folder)?;
+    }
+
+    fs::write(SAVE_PATH, saved.to_text())
+}
+
+pub fn load_game() -> Result<SavedGame, String> {
+    let text = fs::read_to_string(SAVE_PATH).map_err(|error| error.to_string())?;
+    SavedGame::parse(&text)
+}
+
+/**
+ * refaz os tiros do jogo salvo pelo mesmo caminho de uma jogada normal,
+ * para que placar, navios afundados e registro fiquem iguais aos de antes.
+ * os navios andam e as habilidades são gastas no mesmo ponto da sequência
+ */
+pub fn replay_saved_shots(
+    mut commands: Commands,
+    pending_load: Option<Res<PendingLoad>>,
+    mut game_state: ResMut<GameState>,
+    mut shot_grid: ResMut<ShotGrid>,
+    mut shot_log: ResMut<ShotLog>,
+    mut cells_query: Query<(Entity, &Cell, &mut Sprite)>,
+    mut fleet: Fleet,
+    mut motion_query: Query<(&mut Transform, &ShipDirection), With<Ship>>,
+    theme: Res<Theme>,
+    mut index: ResMut<BoardIndex>,
+    catalog: Res<ShipCatalog>,
+) {
+    let Some(pending_load) = pending_load else {
+        return;
+    };
+    commands.remove_resource::<PendingLoad>();
+
+    // os navios acabaram de nascer, antes de index_ships rodar: o tiro precisa achá-los já
+    for (entity, ship) in fleet.iter() {
+        index.place_ship(entity, &ship.cells);
+    }
+
+    let saved = &pending_load.0;
+    for (shot, (shooter, row, column)) in saved.shots.iter().enumerate() {
+        for record in saved.moves.iter().filter(|record| record.after == shot) {
+            replay_move(record, &mut fleet, &mut motion_query, &mut game_state, &mut index, &mut shot_log);
+        }
+        spend_saved_abilities(saved, shot, &mut fleet, &mut shot_log, &catalog);
+
+        let Some((entity, cell, mut sprite)) = index
+            .at(*row, *column)
+            .and_then(|entity| cells_query.get_mut(entity).ok())
+        else {
+            warn!("tiro salvo fora do tabuleiro: {row} {column}");
+            continue;
+        };
+
+        game_state.is_player_turn = *shooter == Side::Player;
+        let outcome = cell.mark(
+            &mut sprite,
+            &mut fleet,
+            entity,
+            &mut shot_grid,
+            &index,
+            &mut game_state,
+            theme.palette(),
+        );
+        shot_log.record(*shooter, cell, outcome);
+
+        // a resposta falsa da partida original continua marcada, para o replay mostrar
+        if saved.lies.contains(&shot) {
+            if let Some(record) = shot_log.records.last_mut() {
+                record.lie = true;
+            }
+        }
+    }
+    for record in saved.moves.iter().filter(|record| record.after == saved.shots.len()) {
+        replay_move(record, &mut fleet, &mut motion_query, &mut game_state, &mut index, &mut shot_log);
+    }
+    spend_saved_abilities(saved, saved.shots.len(), &mut fleet, &mut shot_log, &catalog);
+
+    saved.restore_turn(&mut game_state);
+}
+
+// anda com o navio que está na célula do registro, pelo mesmo move_ship da partida
+fn replay_move(
+    record: &MoveRecord,
+    fleet: &mut Fleet,
+    motion_query: &mut Query<(&mut Transform, &ShipDirection), With<Ship>>,
+    game_state: &mut GameState,
+    index: &mut BoardIndex,
+    shot_log: &mut ShotLog,
+) {
+    let ship_entity = index
+        .at(record.row, record.column)
+        .and_then(|cell| index.ship_at(cell));
+    let cells = ship_entity
+        .and_then(|ship| fleet.get(ship))
+        .and_then(|ship| {
+            ship.cells
+                .iter()
+                .map(|cell| {
+                    let (row, column) = index.board_position(*cell)?;
+                    index.at(
+                        row.checked_add_signed(record.step.0)?,
+                        column.checked_add_signed(record.step.1)?,
+                    )
+                })
+                .collect::<Option<Vec<Entity>>>()
+                .map(|cells| (ship.cells[0], cells))
+        });
+
+    let (Some(ship_entity), Some((from, cells))) = (ship_entity, cells) else {
+        warn!("movimento salvo inválido: {} {}", record.row, record.column);
+        return;
+    };
+
+    let center = |cell: Entity| index.board_position(cell).map(|(row, column)| index.center(row, column));
+    let Some(offset) = center(from).zip(center(cells[0])).map(|(from, to)| to - from) else {
+        return;
+    };
+    move_ship(ship_entity, (cells, offset), fleet, motion_query, game_state, index, shot_log);
+}
+
+// mesmo navio que use_ability escolheria: o primeiro do lado que ainda tem a habilidade
+fn spend_saved_abilities(
+    saved: &SavedGame,
+    shots: usize,
+    fleet: &mut Fleet,
+    shot_log: &mut ShotLog,
+    catalog: &ShipCatalog,
+) {
+    for record in saved.abilities.iter().filter(|record| record.after == shots) {
+        let spent = fleet
+            .iter()
+            .find(|(_, ship)| ship.side == record.shooter && ship.ability(catalog) == Some(record.ability))
+            .map(|(entity, _)| entity);
+        if let Some(ship) = spent.and_then(|entity| fleet.get_mut(entity)) {
+            ship.ability_used = true;
+        }
+        shot_log.record_ability(record.shooter, record.ability);
+    }
+}
@@ -0,0 +1,384 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::logic::{
+    engine::{Rules, ShipRule},
+    terrain::TerrainMap,
+};
+
+use super::{
+    ships::{ShipCatalog, ShipType},
+    theme::DEFAULT_PALETTE,
+};
+
+/**
+ * configurações escolhidas no menu. o tabuleiro, o painel de navios e o
+ * bot leem daqui no início de cada partida; partidas em rede usam sempre
+ * o padrão, que é o mesmo do servidor
+ */
+#[derive(Resource, Clone, Debug)]
+pub struct GameSettings {
+    pub board_size: BoardSize,
+    pub fleet: FleetPreset,
+    pub variant: RuleVariant,
+    pub map: MapPreset,
+    pub difficulty: BotDifficulty,
+    pub volume: u8, // volume geral, porcentagem de 10 em 10
+    pub music_volume: u8,
+    pub sfx_volume: u8,
+    pub muted: bool,
+    pub theme: String, // nome da paleta em assets/themes.txt
+    pub language: Language,
+    pub lan_address: String,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        GameSettings {
+            board_size: BoardSize::Classic,
+            fleet: FleetPreset::Classic,
+            variant: RuleVariant::Classic,
+            map: MapPreset::Open,
+            difficulty: BotDifficulty::Normal,
+            volume: 80,
+            music_volume: 60,
+            sfx_volume: 100,
+            muted: false,
+            theme: DEFAULT_PALETTE.to_string(),
+            language: Language::PtBr,
+            lan_address: "127.0.0.1:7878".to_string(),
+        }
+    }
+}
+
+impl GameSettings {
+    // linhas do tabuleiro inteiro; a metade de baixo é do jogador
+    pub fn rows(&self) -> usize {
+        self.board_size.dimensions().0
+    }
+
+    pub fn columns(&self) -> usize {
+        self.board_size.dimensions().1
+    }
+
+    // regras do motor para um dos lados do tabuleiro; nas avançadas cada navio leva a habilidade do tipo
+    pub fn rules(&self, catalog: &ShipCatalog) -> Rules {
+        let advanced = self.variant == RuleVariant::Advanced;
+
+        Rules {
+            rows: self.rows() / 2,
+            columns: self.columns(),
+            fleet: self
+                .fleet
+                .rules(catalog)
+                .into_iter()
+                .map(|ship| {
+                    let ability = catalog
+                        .from_name(&ship.name)
+                        .filter(|_| advanced)
+                        .and_then(|ship_type| catalog.spec(&ship_type).abilities.first().copied());
+                    ship.with_ability(ability)
+                })
+                .collect(),
+            terrain: self.map.terrain(),
+            moving: self.variant == RuleVariant::Moving,
+        }
+    }
+
+    // volume final de um canal, de 0 a 1
+    pub fn channel_volume(&self, channel: u8) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.volume as f32 / 100.0 * channel as f32 / 100.0
+        }
+    }
+
+    // as partidas em rede seguem as regras padrão do servidor
+    pub fn reset_rules(&mut self) {
+        self.board_size = BoardSize::Classic;
+        self.fleet = FleetPreset::Classic;
+        self.variant = RuleVariant::Classic;
+        self.map = MapPreset::Open;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardSize {
+    Small,
+    Classic,
+    Wide,
+}
+
+// nome de uma opção como aparece no arquivo de jogo salvo
+pub fn option_from_name<T: Copy + std::fmt::Debug>(options: &[T], name: &str) -> Option<T> {
+    options
+        .iter()
+        .copied()
+        .find(|option| format!("{option:?}") == name)
+}
+
+impl BoardSize {
+    pub const ALL: [BoardSize; 3] = [BoardSize::Small, BoardSize::Classic, BoardSize::Wide];
+
+    // (linhas, colunas) do tabuleiro inteiro
+    pub fn dimensions(self) -> (usize, usize) {
+        match self {
+            BoardSize::Small => (8, 8),
+            BoardSize::Classic => (10, 10),
+            BoardSize::Wide => (10, 12),
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            BoardSize::Small => BoardSize::Classic,
+            BoardSize::Classic => BoardSize::Wide,
+            BoardSize::Wide => BoardSize::Small,
+        }
+    }
+
+    pub fn label(self) -> String {
+        let (rows, columns) = self.dimensions();
+        format!("{rows}x{columns}")
+    }
+}
+
+/**
+ * navios da variante "guerra moderna": nomes e formatos vêm de um arquivo
+ * de regras (o tamanho do tabuleiro dele é ignorado, vale o das configurações).
+ * lido uma vez só, quando o ShipCatalog é criado
+ */
+pub const MODERN_RULES_PATH: &str = "assets/rules/modern.txt";
+const BUILTIN_MODERN_RULES: &str = include_str!("../../../assets/rules/modern.txt");
+
+pub fn modern_fleet() -> Vec<ShipRule> {
+    let text = fs::read_to_string(MODERN_RULES_PATH).unwrap_or_else(|_| BUILTIN_MODERN_RULES.to_string());
+    let rules = Rules::parse(&text).unwrap_or_else(|error| {
+        warn!("regras inválidas em {MODERN_RULES_PATH}: {error}");
+        Rules::parse(BUILTIN_MODERN_RULES).expect("regras embutidas válidas")
+    });
+
+    rules.fleet
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FleetPreset {
+    Classic,
+    Compact,
+    Armada,
+    Modern,
+}
+
+impl FleetPreset {
+    pub const ALL: [FleetPreset; 4] = [
+        FleetPreset::Classic,
+        FleetPreset::Compact,
+        FleetPreset::Armada,
+        FleetPreset::Modern,
+    ];
+
+    // navios da frota para o motor, com o formato de cada um
+    pub fn rules(self, catalog: &ShipCatalog) -> Vec<ShipRule> {
+        match self {
+            FleetPreset::Modern => catalog.modern_fleet().to_vec(),
+            _ => self
+                .ships()
+                .iter()
+                .map(|ship_type| ShipRule::line(ship_type.name(), catalog.size(ship_type)))
+                .collect(),
+        }
+    }
+
+    fn ships(self) -> Vec<ShipType> {
+        match self {
+            FleetPreset::Classic => vec![
+                ShipType::AircraftCarrier,
+                ShipType::LargeBattleship,
+                ShipType::Battleship,
+                ShipType::Destroyer,
+                ShipType::Submarine,
+            ],
+            FleetPreset::Compact => vec![
+                ShipType::LargeBattleship,
+                ShipType::Battleship,
+                ShipType::Submarine,
+            ],
+            FleetPreset::Armada => vec![
+                ShipType::AircraftCarrier,
+                ShipType::LargeBattleship,
+                ShipType::Battleship,
+                ShipType::Battleship,
+                ShipType::Destroyer,
+                ShipType::Submarine,
+                ShipType::Submarine,
+            ],
+            FleetPreset::Modern => Vec::new(), // os navios vêm do arquivo, por rules
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            FleetPreset::Classic => FleetPreset::Compact,
+            FleetPreset::Compact => FleetPreset::Armada,
+            FleetPreset::Armada => FleetPreset::Modern,
+            FleetPreset::Modern => FleetPreset::Classic,
+        }
+    }
+
+    // chave do texto em assets/locales
+    pub fn key(self) -> &'static str {
+        match self {
+            FleetPreset::Classic => "fleet-classic",
+            FleetPreset::Compact => "fleet-compact",
+            FleetPreset::Armada => "fleet-armada",
+            FleetPreset::Modern => "fleet-modern",
+        }
+    }
+}
+
+/**
+ * mapas com ilhas e minas, lidos de assets/maps/<nome>.txt no formato de
+ * TerrainMap::parse; sem o arquivo vale a cópia embutida. o mapa vale para
+ * os dois lados e o que passar do tamanho do tabuleiro fica de fora
+ */
+pub const MAPS_DIR: &str = "assets/maps";
+const BUILTIN_ARCHIPELAGO: &str = include_str!("../../../assets/maps/archipelago.txt");
+const BUILTIN_MINEFIELD: &str = include_str!("../../../assets/maps/minefield.txt");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapPreset {
+    Open, // só água, o jogo clássico
+    Archipelago,
+    Minefield,
+}
+
+impl MapPreset {
+    pub const ALL: [MapPreset; 3] = [MapPreset::Open, MapPreset::Archipelago, MapPreset::Minefield];
+
+    pub fn terrain(self) -> TerrainMap {
+        let (name, builtin) = match self {
+            MapPreset::Open => return TerrainMap::default(),
+            MapPreset::Archipelago => ("archipelago", BUILTIN_ARCHIPELAGO),
+            MapPreset::Minefield => ("minefield", BUILTIN_MINEFIELD),
+        };
+
+        let path = format!("{MAPS_DIR}/{name}.txt");
+        let text = fs::read_to_string(&path).unwrap_or_else(|_| builtin.to_string());
+        TerrainMap::parse(&text).unwrap_or_else(|error| {
+            warn!("mapa inválido em {path}: {error}");
+            TerrainMap::parse(builtin).expect("mapas embutidos válidos")
+        })
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            MapPreset::Open => MapPreset::Archipelago,
+            MapPreset::Archipelago => MapPreset::Minefield,
+            MapPreset::Minefield => MapPreset::Open,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            MapPreset::Open => "map-open",
+            MapPreset::Archipelago => "map-archipelago",
+            MapPreset::Minefield => "map-minefield",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RuleVariant {
+    #[default]
+    Classic,
+    HitAgain, // acertou, atira de novo
+    Advanced, // navios dão habilidades de uso único (ShipSpec::abilities)
+    Moving,   // no lugar do tiro, um navio sem dano anda uma célula
+}
+
+impl RuleVariant {
+    pub const ALL: [RuleVariant; 4] = [
+        RuleVariant::Classic,
+        RuleVariant::HitAgain,
+        RuleVariant::Advanced,
+        RuleVariant::Moving,
+    ];
+
+    pub fn next(self) -> Self {
+        match self {
+            RuleVariant::Classic => RuleVariant::HitAgain,
+            RuleVariant::HitAgain => RuleVariant::Advanced,
+            RuleVariant::Advanced => RuleVariant::Moving,
+            RuleVariant::Moving => RuleVariant::Classic,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            RuleVariant::Classic => "variant-classic",
+            RuleVariant::HitAgain => "variant-hit-again",
+            RuleVariant::Advanced => "variant-advanced",
+            RuleVariant::Moving => "variant-moving",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BotDifficulty {
+    Easy,   // tiros aleatórios
+    Normal, // caça e alvo
+    Hard,   // caça e alvo procurando em xadrez
+}
+
+impl BotDifficulty {
+    pub const ALL: [BotDifficulty; 3] = [BotDifficulty::Easy, BotDifficulty::Normal, BotDifficulty::Hard];
+
+    pub fn next(self) -> Self {
+        match self {
+            BotDifficulty::Easy => BotDifficulty::Normal,
+            BotDifficulty::Normal => BotDifficulty::Hard,
+            BotDifficulty::Hard => BotDifficulty::Easy,
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            BotDifficulty::Easy => "difficulty-easy",
+            BotDifficulty::Normal => "difficulty-normal",
+            BotDifficulty::Hard => "difficulty-hard",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    PtBr,
+    EnUs,
+}
+
+impl Language {
+    pub fn next(self) -> Self {
+        match self {
+            Language::PtBr => Language::EnUs,
+            Language::EnUs => Language::PtBr,
+        }
+    }
+
+    // cada idioma aparece com o próprio nome
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::PtBr => "português",
+            Language::EnUs => "english",
+        }
+    }
+
+    // nome do arquivo em assets/locales
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::PtBr => "pt-BR",
+            Language::EnUs => "en-US",
+        }
+    }
+}
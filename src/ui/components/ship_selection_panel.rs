@@ -3,12 +3,24 @@ use bevy::{
     window::{CursorGrabMode, PrimaryWindow},
 };
 
-use crate::logic::cell::{Cell, CellSide};
+use crate::logic::{
+    cell::{Cell, Side},
+    shape::Shape,
+    terrain::TerrainMap,
+};
 
 use super::{
-    board::{GameState, PLAYER_CELL_COLOR, SLOT_SIZE, SLOT_SPACE_BETWEEN},
+    board::{render_board, BoardIndex, GameState, SLOT_SIZE, SLOT_SPACE_BETWEEN},
+    hotseat::Hotseat,
+    layout::cursor_world_position,
+    main_menu::AppState,
+    pause_menu::PauseState,
+    replay::ReplaySession,
+    settings::GameSettings,
+    spectator::SpectatorSession,
+    theme::{Palette, Theme},
     ships::{
-        Ship, ShipBundle, ShipDirection, ShipType, AIRCRAFT_CARRIER_SIZE, BATTLESHIP_SIZE, LARGE_BATTLESHIP_SIZE, SUBMARINE_SIZE
+        SegmentState, Ship, ShipAtlas, ShipBundle, ShipCatalog, ShipDirection, ShipShape, ShipType,
     },
 };
 
@@ -20,6 +32,7 @@ pub struct ShipSelectionPanel;
 #[allow(dead_code)]
 pub struct ShipOption {
     pub ship_type: ShipType,
+    pub shape: Shape, // formato das regras, na posição inicial
 }
 
 #[derive(Component, Debug)]
@@ -29,20 +42,27 @@ pub struct SelectedShip(pub ShipType);
 #[allow(dead_code)]
 impl Plugin for ShipSelectionPanel {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, setup_ship_selection_panel)
-            .add_systems(
-                Update,
-                (
-                    handle_ship_selection_button_drag,
-                    handle_selected_ship_translation_with_cursor,
-                    handle_selected_ship_button_drop,
-                ),
-            );
+        app.add_systems(
+            OnEnter(AppState::InGame),
+            setup_ship_selection_panel
+                .after(render_board)
+                .run_if(not(resource_exists::<SpectatorSession>))
+                .run_if(not(resource_exists::<Hotseat>)) // as duas frotas são sorteadas
+                .run_if(not(resource_exists::<ReplaySession>)),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_ship_selection_button_drag,
+                handle_selected_ship_rotation,
+                handle_selected_ship_translation_with_cursor,
+                handle_selected_ship_button_drop,
+            )
+                .run_if(in_state(PauseState::Running)),
+        );
     }
 }
 
-const OPTIONS_BORDER_COLOR: Color = Color::srgb(0.172, 0.282, 0.561);
-const OPTIONS_BACKGROUND_COLOR: Color = Color::srgb(0.224, 0.451, 0.678);
 const OPTIONS_BORDER_RADIUS: f32 = 8.0;
 const OPTIONS_BORDER_WIDTH: f32 = 2.0;
 
@@ -56,93 +76,175 @@ pub struct OptionButtonUI {
 }
 
 impl OptionButtonUI {
-    pub fn new() -> Self {
+    // largura do maior navio do catálogo, para todos os botões terem o mesmo tamanho
+    pub fn new(palette: &Palette, longest_ship: usize) -> Self {
         OptionButtonUI {
             button: Button,
             node: Node {
-                width: Val::Px(SLOT_SIZE * AIRCRAFT_CARRIER_SIZE as f32),
+                width: Val::Px(SLOT_SIZE * longest_ship as f32),
                 height: Val::Px(SLOT_SIZE),
                 border: UiRect::all(Val::Px(OPTIONS_BORDER_WIDTH)),
                 ..Default::default()
             },
             border_radius: BorderRadius::all(Val::Px(OPTIONS_BORDER_RADIUS)),
-            border_color: BorderColor(OPTIONS_BORDER_COLOR),
-            background_color: BackgroundColor(OPTIONS_BACKGROUND_COLOR),
+            border_color: BorderColor(palette.panel_border),
+            background_color: BackgroundColor(palette.panel_background),
         }
     }
 }
 
 
+// células do navio arrastado relativas ao canto; o navio reto vira um formato de uma linha
+fn footprint(
+    catalog: &ShipCatalog,
+    ship_type: &ShipType,
+    direction: &ShipDirection,
+    shape: Option<&ShipShape>,
+) -> Shape {
+    match (shape, direction) {
+        (Some(ShipShape(shape)), _) => shape.clone(),
+        (None, ShipDirection::Horizontal) => Shape::line(catalog.size(ship_type)),
+        (None, ShipDirection::Vertical) => Shape::line(catalog.size(ship_type)).rotated(),
+    }
+}
+
+/**
+ * células do jogador cobertas pelo navio centrado no cursor, com a linha e
+ * a coluna do canto. nada se alguma célula cair fora do lado do jogador,
+ * numa ilha ou mina ou em cima de outro navio já posicionado
+ */
+fn covered_cells(
+    footprint: &Shape,
+    cursor: Vec2,
+    index: &BoardIndex,
+    terrain: &TerrainMap,
+    placed_query: &Query<&Ship, Without<SelectedShip>>,
+) -> Option<(usize, usize, Vec<Entity>)> {
+    let (rows, columns) = footprint.size();
+    let step = SLOT_SIZE + SLOT_SPACE_BETWEEN;
+    let corner = cursor - Vec2::new(columns as f32 - 1.0, rows as f32 - 1.0) * step / 2.0;
+    let (row, column) = index.nearest(corner)?;
+
+    let cells = footprint
+        .at(row, column)
+        .into_iter()
+        .map(|(row, column)| {
+            // ilhas e minas não recebem navio
+            terrain
+                .is_water(row, column)
+                .then(|| index.cell(&Side::Player, row, column))
+                .flatten()
+        })
+        .collect::<Option<Vec<Entity>>>()?;
+
+    let occupied = placed_query
+        .iter()
+        .any(|ship| ship.cells.iter().any(|cell| cells.contains(cell)));
 
+    (!occupied).then_some((row, column, cells))
+}
+
+// só os tipos de navio da frota escolhida nas configurações
 fn setup_ship_selection_panel(
-    assert_server: Res<AssetServer>,
+    atlas: Res<ShipAtlas>,
     mut commands: Commands,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    catalog: Res<ShipCatalog>,
 ) {
+    let mut options: Vec<(ShipType, Shape)> = Vec::new();
+    for rule in settings.fleet.rules(&catalog) {
+        let Some(ship_type) = catalog.from_name(&rule.name) else {
+            continue;
+        };
+        if !options.iter().any(|(option, _)| *option == ship_type) {
+            options.push((ship_type, rule.shape));
+        }
+    }
+    options.sort_by_key(|(_, shape)| shape.len());
+
     commands
-        .spawn(Node {
-            position_type: PositionType::Absolute,
-            top: Val::Percent(5.0),
-            left: Val::Percent(5.0),
-            width: Val::Px(150.0),
-            height: Val::Px(150.0),
-            border: UiRect::all(Val::Px(2.0)),
-            justify_content: JustifyContent::Center,
-            align_items: AlignItems::Center,
-            flex_direction: FlexDirection::Column,
-            row_gap: Val::Px(10.0),
-            ..Default::default()
-        })
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(5.0),
+                left: Val::Percent(5.0),
+                width: Val::Px(150.0),
+                height: Val::Px(150.0),
+                border: UiRect::all(Val::Px(2.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(10.0),
+                ..Default::default()
+            },
+            StateScoped(AppState::InGame),
+        ))
         .with_children(|parent| {
-            parent
-                .spawn(OptionButtonUI::new())
-                .insert(ShipOption {
-                    ship_type: ShipType::Submarine,
-                })
-                .with_children(|parent| {
-                    parent.spawn(ImageNode::new(assert_server.load("atlases/submarine.png")));
-                });
-            parent
-                .spawn(OptionButtonUI::new())
-                .insert(ShipOption {
-                    ship_type: ShipType::Battleship,
-                })
-                .with_children(|parent| {
-                    parent.spawn(ImageNode::new(assert_server.load("atlases/battleship.png")));
-                });
-            parent
-                .spawn(OptionButtonUI::new())
-                .insert(ShipOption {
-                    ship_type: ShipType::LargeBattleship,
-                })
-                .with_children(|parent| {
-                    parent.spawn(ImageNode::new(
-                        assert_server.load("atlases/large_battleship.png"),
-                    ));
-                });
-            parent
-                .spawn(OptionButtonUI::new())
-                .insert(ShipOption {
-                    ship_type: ShipType::AircraftCarrier,
-                })
-                .with_children(|parent| {
-                    parent.spawn(ImageNode::new(
-                        assert_server.load("atlases/aircraft_carrier.png"),
-                    ));
-                });
+            for (ship_type, shape) in options {
+                let spec = catalog.spec(&ship_type);
+                let (rows, _) = shape.size();
+                // formato em miniatura: a altura do botão dividida pelas linhas do formato
+                let tile_size = (SLOT_SIZE - OPTIONS_BORDER_WIDTH * 2.0) / rows as f32;
+
+                parent
+                    .spawn(OptionButtonUI::new(theme.palette(), catalog.longest_ship()))
+                    .insert(ShipOption {
+                        ship_type,
+                        shape: shape.clone(),
+                    })
+                    .with_children(|parent| {
+                        for (index, (row, column)) in shape.cells().iter().enumerate() {
+                            let node = if shape.is_line() {
+                                Node {
+                                    width: Val::Px(SLOT_SIZE - OPTIONS_BORDER_WIDTH),
+                                    height: Val::Px(SLOT_SIZE - OPTIONS_BORDER_WIDTH * 2.0),
+                                    ..Default::default()
+                                }
+                            } else {
+                                // a linha 0 do formato é a de baixo, como no tabuleiro
+                                Node {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Px(*column as f32 * tile_size),
+                                    top: Val::Px((rows - 1 - row) as f32 * tile_size),
+                                    width: Val::Px(tile_size),
+                                    height: Val::Px(tile_size),
+                                    ..Default::default()
+                                }
+                            };
+
+                            parent.spawn((
+                                ImageNode::from_atlas_image(
+                                    atlas.image.clone(),
+                                    TextureAtlas {
+                                        layout: atlas.layout.clone(),
+                                        index: ShipAtlas::tile(
+                                            index,
+                                            spec,
+                                            &ShipDirection::Horizontal,
+                                            SegmentState::Intact,
+                                        ),
+                                    },
+                                ),
+                                node,
+                            ));
+                        }
+                    });
+            }
         });
 }
 
 fn handle_ship_selection_button_drag(
-    asset_server: Res<AssetServer>,
     mut commands: Commands,
     mut interaction_query: Query<
         (&Button, &Interaction, &ShipOption, &mut Transform),
         (Changed<Interaction>, With<Button>),
     >,
     mut window_query: Query<&mut Window, With<PrimaryWindow>>,
-    cells_query: Query<(Entity, &Cell)>,
+    index: Res<BoardIndex>,
     selected_ship_query: Query<Entity, With<SelectedShip>>,
-    mut game_state: ResMut<GameState>,
+    settings: Res<GameSettings>,
+    catalog: Res<ShipCatalog>,
 ) {
     for (_, interaction, ship_option, mut transform) in interaction_query.iter_mut() {
         match *interaction {
@@ -158,43 +260,31 @@ fn handle_ship_selection_button_drag(
                 window.cursor_options.grab_mode = CursorGrabMode::None;
                 window.cursor_options.visible = true;
 
-                commands.spawn((
+                let spec = catalog.spec(&ship_option.ship_type);
+                let mut selected = commands.spawn((
                     SelectedShip(ship_option.ship_type.clone()),
-                    match ship_option.ship_type {
-                        ShipType::Submarine => ShipBundle::new_submarine(
-                            &asset_server,
-                            ShipDirection::Horizontal,
-                            0,
-                            0,
-                            &cells_query,
-                            &mut game_state,
-                        ),
-                        ShipType::Battleship => ShipBundle::new_battleship(
-                            &asset_server,
-                            ShipDirection::Horizontal,
-                            0,
-                            0,
-                            &cells_query,
-                            &mut game_state,
-                        ),
-                        ShipType::LargeBattleship => ShipBundle::new_large_battleship(
-                            &asset_server,
-                            ShipDirection::Horizontal,
-                            0,
-                            0,
-                            &cells_query,
-                            &mut game_state,
-                        ),
-                        ShipType::AircraftCarrier => ShipBundle::new_aircraft_carrier(
-                            &asset_server,
-                            ShipDirection::Horizontal,
-                            0,
-                            0,
-                            &cells_query,
-                            &mut game_state,
-                        ),
-                    },
+                    StateScoped(AppState::InGame),
                 ));
+
+                if ship_option.shape.is_line() {
+                    selected.insert(ShipBundle::spawn(
+                        spec,
+                        Side::Player,
+                        (0, 0),
+                        ShipDirection::Horizontal,
+                        &index,
+                        &settings,
+                    ));
+                } else {
+                    selected.insert(ShipBundle::spawn_shaped(
+                        spec,
+                        Side::Player,
+                        (0, 0),
+                        &ship_option.shape,
+                        &index,
+                        &settings,
+                    ));
+                }
             }
             Interaction::None => {
                 transform.scale = Vec3::splat(1.0);
@@ -206,108 +296,78 @@ fn handle_ship_selection_button_drag(
     }
 }
 
+// R gira o navio arrastado um quarto de volta; M espelha os que têm formato
+fn handle_selected_ship_rotation(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut selected_ship_query: Query<(&mut ShipDirection, Option<&mut ShipShape>), With<SelectedShip>>,
+) {
+    let Ok((mut direction, shape)) = selected_ship_query.get_single_mut() else {
+        return;
+    };
+
+    match shape {
+        Some(mut shape) => {
+            if keyboard.just_pressed(KeyCode::KeyR) {
+                shape.0 = shape.0.rotated();
+            }
+            if keyboard.just_pressed(KeyCode::KeyM) {
+                shape.0 = shape.0.mirrored();
+            }
+        }
+        None if keyboard.just_pressed(KeyCode::KeyR) => {
+            *direction = match *direction {
+                ShipDirection::Horizontal => ShipDirection::Vertical,
+                ShipDirection::Vertical => ShipDirection::Horizontal,
+            };
+        }
+        None => {}
+    }
+}
+
 fn handle_selected_ship_translation_with_cursor(
-    mut selected_ship_query: Query<(&SelectedShip, &ShipDirection, &mut Transform), Without<Cell>>,
-    mut cells_query: Query<(&mut Sprite, &Transform, &CellSide), With<Cell>>,
+    mut selected_ship_query: Query<
+        (&SelectedShip, &ShipDirection, Option<&ShipShape>, &mut Transform),
+        Without<Cell>,
+    >,
+    mut cells_query: Query<(Entity, &mut Sprite, &Cell)>,
+    placed_query: Query<&Ship, Without<SelectedShip>>,
     window_query: Query<&mut Window, With<PrimaryWindow>>,
+    camera_query: Single<(&Camera, &GlobalTransform)>,
+    index: Res<BoardIndex>,
+    game_state: Res<GameState>,
+    theme: Res<Theme>,
+    catalog: Res<ShipCatalog>,
 ) {
     let window = window_query.single();
+    let (camera, camera_transform) = *camera_query;
 
-    let Some(cursor_position) = window.cursor_position() else {
+    let Some(cursor) = cursor_world_position(window, camera, camera_transform) else {
         return;
     };
 
-    let x = cursor_position.x - window.width() / 2.0;
-    let y = (window.height() - cursor_position.y) - window.height() / 2.0;
-
-    let cursor_world_position = Vec3::new(x, y, Vec3::default().z);
-
-    let Some((selected_ship, ship_direction, mut ship_transform)) =
+    let Some((selected_ship, ship_direction, ship_shape, mut ship_transform)) =
         selected_ship_query.iter_mut().next()
     else {
         return;
     };
 
-    ship_transform.translation = cursor_world_position;
+    ship_transform.translation = cursor.extend(Vec3::default().z);
 
     // when the cursor is over the board, change covered cells color
+    let footprint = footprint(&catalog, &selected_ship.0, ship_direction, ship_shape);
+    let covered = covered_cells(&footprint, cursor, &index, &game_state.terrain, &placed_query)
+        .map(|(_, _, cells)| cells)
+        .unwrap_or_default();
 
-    for (mut cell_sprite, cell_transform, cell_side) in cells_query.iter_mut() {
-        if cell_side == &CellSide::Enemy {
+    for (cell_entity, mut cell_sprite, cell) in cells_query.iter_mut() {
+        if cell.side == Side::Enemy {
             continue;
         }
 
-        let x_range = match selected_ship.0 {
-            ShipType::Submarine => {
-                if ship_direction == &ShipDirection::Horizontal {
-                    SUBMARINE_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::Battleship => {
-                if ship_direction == &ShipDirection::Horizontal {
-                    BATTLESHIP_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::LargeBattleship => {
-                if ship_direction == &ShipDirection::Horizontal {
-                    LARGE_BATTLESHIP_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::AircraftCarrier => {
-                if ship_direction == &ShipDirection::Horizontal {
-                    AIRCRAFT_CARRIER_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-        } / 2.0;
-
-        let y_range = match selected_ship.0 {
-            ShipType::Submarine => {
-                if ship_direction == &ShipDirection::Vertical {
-                    SUBMARINE_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::Battleship => {
-                if ship_direction == &ShipDirection::Vertical {
-                    BATTLESHIP_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::LargeBattleship => {
-                if ship_direction == &ShipDirection::Vertical {
-                    LARGE_BATTLESHIP_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::AircraftCarrier => {
-                if ship_direction == &ShipDirection::Vertical {
-                    AIRCRAFT_CARRIER_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-        } / 2.0;
-
-        let x1 = cell_transform.translation.x - x_range;
-        let x2 = cell_transform.translation.x + x_range;
-        let y1 = cell_transform.translation.y - y_range;
-        let y2 = cell_transform.translation.y + y_range;
-
-        if x >= x1 && x <= x2 && y >= y1 && y <= y2 {
-            cell_sprite.color = Color::WHITE;
+        if covered.contains(&cell_entity) {
+            cell_sprite.color = theme.palette().highlight;
         } else {
-            cell_sprite.color = PLAYER_CELL_COLOR;
+            cell_sprite.color = theme.palette().ground(cell);
         }
     }
 }
@@ -315,38 +375,38 @@ fn handle_selected_ship_translation_with_cursor(
 fn handle_selected_ship_button_drop(
     mut commands: Commands,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
-    mut selected_ship_query: Query<
-        (
-            Entity,
-            &mut Ship,
-            &SelectedShip,
-            &ShipDirection,
-            &mut Transform,
-            &mut Sprite,
-        ),
-    >,
-    mut cells_query: Query<
-        (Entity, &Transform, &mut Sprite, &Cell, &CellSide),
-        Without<SelectedShip>,
-    >,
+    mut selected_ship_query: Query<(
+        Entity,
+        &mut Ship,
+        &SelectedShip,
+        &ShipDirection,
+        Option<&ShipShape>,
+        &mut Transform,
+    )>,
+    mut cells_query: Query<(&mut Sprite, &Cell), Without<SelectedShip>>,
+    placed_query: Query<&Ship, Without<SelectedShip>>,
     mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    camera_query: Single<(&Camera, &GlobalTransform)>,
+    index: Res<BoardIndex>,
+    game_state: Res<GameState>,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    catalog: Res<ShipCatalog>,
 ) {
     let mut window = window_query.single_mut();
+    let (camera, camera_transform) = *camera_query;
 
-    let Some(cursor_position) = window.cursor_position() else {
+    let Some(cursor) = cursor_world_position(&window, camera, camera_transform) else {
         return;
     };
 
-    let window_x = cursor_position.x - window.width() / 2.0;
-    let window_y = (window.height() - cursor_position.y) - window.height() / 2.0;
-
     let Ok((
         selected_ship_entity,
         mut ship,
         selected_ship,
         ship_direction,
+        ship_shape,
         mut ship_transform,
-        mut _ship_sprite,
     )) = selected_ship_query.get_single_mut()
     else {
         return;
@@ -355,142 +415,28 @@ fn handle_selected_ship_button_drop(
     if mouse_button_input.just_released(MouseButton::Right)
         && window.cursor_options.grab_mode == CursorGrabMode::None
     {
-        let x_range = match selected_ship.0 {
-            ShipType::Submarine => {
-                if ship_direction == &ShipDirection::Horizontal {
-                    SUBMARINE_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::Battleship => {
-                if ship_direction == &ShipDirection::Horizontal {
-                    BATTLESHIP_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::LargeBattleship => {
-                if ship_direction == &ShipDirection::Horizontal {
-                    LARGE_BATTLESHIP_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::AircraftCarrier => {
-                if ship_direction == &ShipDirection::Horizontal {
-                    AIRCRAFT_CARRIER_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-        } / 2.0;
-
-        let y_range = match selected_ship.0 {
-            ShipType::Submarine => {
-                if ship_direction == &ShipDirection::Vertical {
-                    SUBMARINE_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::Battleship => {
-                if ship_direction == &ShipDirection::Vertical {
-                    BATTLESHIP_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::LargeBattleship => {
-                if ship_direction == &ShipDirection::Vertical {
-                    LARGE_BATTLESHIP_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-            ShipType::AircraftCarrier => {
-                if ship_direction == &ShipDirection::Vertical {
-                    AIRCRAFT_CARRIER_SIZE as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                } else {
-                    SLOT_SIZE
-                }
-            }
-        } / 2.0;
-
-        let mut cells_entities_and_data: Vec<(Entity, &Cell)> = Vec::new();
-
-        for (cell_entity, cell_transform, mut cell_sprite, cell, cell_side) in cells_query.iter_mut() {
-            if cell_side == &CellSide::Enemy {
-                continue;
-            }
-
-            let x1 = cell_transform.translation.x - x_range;
-            let x2 = cell_transform.translation.x + x_range;
-            let y1 = cell_transform.translation.y - y_range;
-            let y2 = cell_transform.translation.y + y_range;
+        let footprint = footprint(&catalog, &selected_ship.0, ship_direction, ship_shape);
 
-            if window_x >= x1 && window_x <= x2 && window_y >= y1 && window_y <= y2 {
-                cell_sprite.color = Color::srgb(0.0, 1.0, 0.0);
-                println!("Selected cell: {:?}", cell);
-                cells_entities_and_data.push((cell_entity, cell));
-            }
-        }
-
-        if ship.cells.len() == 0 {
+        // fora do lado do jogador ou em cima de outro navio: o navio volta para o painel
+        let Some((row, column, cells)) = covered_cells(&footprint, cursor, &index, &game_state.terrain, &placed_query)
+        else {
             commands.entity(selected_ship_entity).despawn_recursive();
             window.cursor_options.grab_mode = CursorGrabMode::None;
             window.cursor_options.visible = true;
             return;
-        }
+        };
 
         commands
             .entity(selected_ship_entity)
             .remove::<SelectedShip>();
 
-        cells_entities_and_data.sort_by(|(_, a), (_, b)| {
-            if a.row == b.row {
-                a.column.cmp(&b.column)
-            } else {
-                a.row.cmp(&b.row)
-            }
-        });
-
-        let final_ship_position = ShipBundle::calculate_position(
-            match selected_ship.0 {
-                ShipType::Submarine => SUBMARINE_SIZE,
-                ShipType::Battleship => BATTLESHIP_SIZE,
-                ShipType::LargeBattleship => LARGE_BATTLESHIP_SIZE,
-                ShipType::AircraftCarrier => AIRCRAFT_CARRIER_SIZE,
-            },
-            ship_direction,
-            cells_entities_and_data[0].1.column as i8,
-            cells_entities_and_data[0].1.row as i8,
-        );
-
-        ship_transform.translation.x = final_ship_position.x;
-        ship_transform.translation.y = final_ship_position.y;
-        ship_transform.translation.z = final_ship_position.z;
-
-        ship.cells = cells_entities_and_data
-            .iter()
-            .map(|(cell_entity, _)| *cell_entity)
-            .collect();
-
-        //mudar cor do navio caso ele esteja numa posição inimiga (descomentar quando a funcionalidade do inimigo posicionar navio estiver funcionando)
-        // if let Some(last_cell) = cells.last() {
-
-        //     let new_color = if last_cell.row >= 4 {
-        //         Color::srgb(0.6, 0.6, 1.0) // Azul claro para inimigos
-        //     } else {
-        //         Color::srgb(1.0, 1.0, 1.0) // Cor padrão (branca) para o jogador
-        //     };
-
-        //     ship_sprite.color = new_color;
-        // }
+        ship_transform.translation =
+            ShipBundle::shape_position(&footprint, column as i8, row as i8, &settings);
+        ship.cells = cells;
 
-        for (_, _, mut cell_sprite, _, cell_side) in cells_query.iter_mut() {
-            if cell_side == &CellSide::Player {
-                cell_sprite.color = PLAYER_CELL_COLOR;
+        for (mut cell_sprite, cell) in cells_query.iter_mut() {
+            if cell.side == Side::Player {
+                cell_sprite.color = theme.palette().ground(cell);
             }
         }
 
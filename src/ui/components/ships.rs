@@ -1,19 +1,246 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
 
-use bevy::prelude::*;
-use rand::Rng;
+use bevy::{ecs::system::SystemParam, prelude::*};
 
-use crate::logic::cell::Cell;
+use crate::logic::{
+    ability::Ability,
+    bots::BotPlayer,
+    cell::{Cell, Side},
+    engine::{random_fleet, Match, ShipPlacement, ShipRule},
+    shape::Shape,
+};
 
-use super::board::{GameState, COLUMNS, ROWS, SLOT_SIZE, SLOT_SPACE_BETWEEN};
+use super::{
+    board::{BoardIndex, GameState, ShotGrid, SLOT_SIZE, SLOT_SPACE_BETWEEN},
+    external_bot::ExternalOpponent,
+    hotseat::Hotseat,
+    main_menu::AppState,
+    effects::{Projectile, Sinking},
+    network_match::NetworkSession,
+    replay::ReplaySession,
+    save_game::PendingLoad,
+    settings::{modern_fleet, GameSettings},
+    spectator::SpectatorSession,
+    theme::Theme,
+};
 
 #[derive(Component, Clone, PartialEq, Debug)]
 #[allow(dead_code)]
 pub enum ShipType {
     Submarine,
+    Destroyer,
     Battleship,
     LargeBattleship,
     AircraftCarrier,
+    Corvette,
+    Frigate,
+    Custom(String), // navio que só existe no arquivo de regras, pelo nome; a ficha fica no ShipCatalog
+}
+
+/**
+ * o que o jogo sabe de cada tipo de navio. construtor, painel, regras e
+ * protocolos leem daqui em vez de repetir um match por tipo
+ */
+#[derive(Clone, Debug)]
+pub struct ShipSpec {
+    pub ship_type: ShipType,
+    pub name: Cow<'static, str>, // nome usado pelo motor de regras e pelos protocolos de rede
+    pub key: Cow<'static, str>,  // chave do nome do navio em assets/locales
+    pub length: usize,
+    pub sprite: ShipSprite,
+    pub abilities: Cow<'static, [Ability]>, // só valem nas regras avançadas, enquanto o navio flutua
+}
+
+// como os segmentos do navio saem do atlas
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShipSprite {
+    Single,    // cada célula é um navio inteiro (bloco próprio do atlas)
+    Segmented, // proa, meio e popa
+}
+
+pub const SHIP_SPECS: [ShipSpec; 7] = [
+    ShipSpec {
+        ship_type: ShipType::Submarine,
+        name: Cow::Borrowed("Submarine"),
+        key: Cow::Borrowed("ship-submarine"),
+        length: SUBMARINE_SIZE,
+        sprite: ShipSprite::Single,
+        abilities: Cow::Borrowed(&[Ability::Sonar]),
+    },
+    ShipSpec {
+        ship_type: ShipType::Destroyer,
+        name: Cow::Borrowed("Destroyer"),
+        key: Cow::Borrowed("ship-destroyer"),
+        length: DESTROYER_SIZE,
+        sprite: ShipSprite::Segmented,
+        abilities: Cow::Borrowed(&[]),
+    },
+    ShipSpec {
+        ship_type: ShipType::Battleship,
+        name: Cow::Borrowed("Battleship"),
+        key: Cow::Borrowed("ship-battleship"),
+        length: BATTLESHIP_SIZE,
+        sprite: ShipSprite::Segmented,
+        abilities: Cow::Borrowed(&[Ability::Torpedo]),
+    },
+    ShipSpec {
+        ship_type: ShipType::LargeBattleship,
+        name: Cow::Borrowed("LargeBattleship"),
+        key: Cow::Borrowed("ship-large-battleship"),
+        length: LARGE_BATTLESHIP_SIZE,
+        sprite: ShipSprite::Segmented,
+        abilities: Cow::Borrowed(&[]),
+    },
+    ShipSpec {
+        ship_type: ShipType::AircraftCarrier,
+        name: Cow::Borrowed("AircraftCarrier"),
+        key: Cow::Borrowed("ship-aircraft-carrier"),
+        length: AIRCRAFT_CARRIER_SIZE,
+        sprite: ShipSprite::Segmented,
+        abilities: Cow::Borrowed(&[Ability::Airstrike]),
+    },
+    // os dois da variante moderna têm o formato no arquivo de regras
+    ShipSpec {
+        ship_type: ShipType::Corvette,
+        name: Cow::Borrowed("Corvette"),
+        key: Cow::Borrowed("ship-corvette"),
+        length: CORVETTE_SIZE,
+        sprite: ShipSprite::Single,
+        abilities: Cow::Borrowed(&[]),
+    },
+    ShipSpec {
+        ship_type: ShipType::Frigate,
+        name: Cow::Borrowed("Frigate"),
+        key: Cow::Borrowed("ship-frigate"),
+        length: FRIGATE_SIZE,
+        sprite: ShipSprite::Single,
+        abilities: Cow::Borrowed(&[]),
+    },
+];
+
+impl Ship {
+    // habilidade que o navio ainda pode usar; as regras avançadas são conferidas por quem chama
+    pub fn ability(&self, catalog: &ShipCatalog) -> Option<Ability> {
+        if self.sunk || self.ability_used {
+            return None;
+        }
+        catalog.spec(&self.r#type).abilities.first().copied()
+    }
+}
+
+impl ShipType {
+    pub fn from_name(name: &str) -> Option<ShipType> {
+        SHIP_SPECS
+            .iter()
+            .find(|spec| spec.name == name)
+            .map(|spec| spec.ship_type.clone())
+    }
+
+    // só pelo nome, como nas estatísticas do perfil: o que não é embutido fica como navio do arquivo
+    pub fn named(name: &str) -> ShipType {
+        ShipType::from_name(name).unwrap_or_else(|| ShipType::Custom(name.to_string()))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            ShipType::Custom(name) => name,
+            _ => &builtin_spec(self).name,
+        }
+    }
+
+    // navio do arquivo de regras usa o próprio nome como chave: sem tradução no .ftl aparece o nome
+    pub fn key(&self) -> &str {
+        match self {
+            ShipType::Custom(name) => name,
+            _ => &builtin_spec(self).key,
+        }
+    }
+}
+
+fn builtin_spec(ship_type: &ShipType) -> &'static ShipSpec {
+    SHIP_SPECS
+        .iter()
+        .find(|spec| spec.ship_type == *ship_type)
+        .expect("todo tipo de navio embutido tem uma entrada em SHIP_SPECS")
+}
+
+/**
+ * fichas dos navios da partida: as embutidas de SHIP_SPECS mais as dos
+ * navios do arquivo de regras que não têm tipo próprio. o arquivo da
+ * variante moderna é lido uma vez, quando o recurso é criado; os navios
+ * de lá usam o sprite de uma célula por segmento
+ */
+#[derive(Resource, Clone, Debug)]
+pub struct ShipCatalog {
+    modern: Vec<ShipRule>,
+    custom: Vec<ShipSpec>,
+}
+
+impl Default for ShipCatalog {
+    fn default() -> Self {
+        ShipCatalog::new(modern_fleet())
+    }
+}
+
+impl ShipCatalog {
+    pub fn new(modern: Vec<ShipRule>) -> Self {
+        let mut custom: Vec<ShipSpec> = Vec::new();
+
+        for rule in &modern {
+            if ShipType::from_name(&rule.name).is_some() || custom.iter().any(|spec| spec.name == rule.name) {
+                continue;
+            }
+            custom.push(ShipSpec {
+                ship_type: ShipType::Custom(rule.name.clone()),
+                name: Cow::Owned(rule.name.clone()),
+                key: Cow::Owned(rule.name.clone()),
+                length: rule.length,
+                sprite: ShipSprite::Single,
+                abilities: Cow::Owned(rule.ability.into_iter().collect()),
+            });
+        }
+
+        ShipCatalog { modern, custom }
+    }
+
+    // navios da variante moderna, como vieram do arquivo
+    pub fn modern_fleet(&self) -> &[ShipRule] {
+        &self.modern
+    }
+
+    pub fn spec(&self, ship_type: &ShipType) -> &ShipSpec {
+        match ship_type {
+            ShipType::Custom(name) => self
+                .custom
+                .iter()
+                .find(|spec| spec.name == *name)
+                .expect("navio do arquivo de regras tem ficha no catálogo"),
+            _ => builtin_spec(ship_type),
+        }
+    }
+
+    pub fn from_name(&self, name: &str) -> Option<ShipType> {
+        ShipType::from_name(name).or_else(|| {
+            self.custom
+                .iter()
+                .find(|spec| spec.name == name)
+                .map(|spec| spec.ship_type.clone())
+        })
+    }
+
+    pub fn size(&self, ship_type: &ShipType) -> usize {
+        self.spec(ship_type).length
+    }
+
+    // maior navio do catálogo, para o tamanho dos botões do painel
+    pub fn longest_ship(&self) -> usize {
+        SHIP_SPECS
+            .iter()
+            .chain(&self.custom)
+            .map(|spec| spec.length)
+            .max()
+            .unwrap_or(1)
+    }
 }
 
 #[derive(Component, Clone, PartialEq, Debug)]
@@ -27,245 +254,301 @@ pub enum ShipDirection {
 #[allow(dead_code)]
 pub struct Ship {
     pub r#type: ShipType,
+    pub side: Side,
     pub cells: Vec<Entity>,
     pub sunk: bool,
+    pub ability_used: bool, // cada navio usa a própria habilidade uma vez só
 }
 
+// navio que não é reto, já no giro e espelho escolhidos; os retos só têm ShipDirection
+#[derive(Component, Clone, Debug)]
+pub struct ShipShape(pub Shape);
+
 #[derive(Bundle)]
 #[allow(dead_code)]
 pub struct ShipBundle {
     pub ship: Ship,
     pub direction: ShipDirection,
     pub transform: Transform,
-    pub sprite: Sprite,
+    pub visibility: Visibility, // os segmentos são filhos e herdam a visibilidade
+}
+
+/**
+ * frota inimiga que o jogador ainda não viu. os navios ficam só aqui, fora do
+ * mundo de entidades e da renderização, até afundarem ou a partida acabar
+ * (reveal_ships). cada um já tem uma entidade vazia reservada, a mesma que o
+ * BoardIndex guarda nas células, e que recebe o ShipBundle na revelação
+ */
+#[derive(Resource, Default, Debug)]
+pub struct HiddenFleet {
+    ships: Vec<HiddenShip>,
+}
+
+#[derive(Debug)]
+struct HiddenShip {
+    entity: Entity,
+    ship: Ship,
+    direction: ShipDirection,
+    shape: Option<ShipShape>,
 }
 
+impl HiddenFleet {
+    pub fn get(&self, entity: Entity) -> Option<&Ship> {
+        self.ships.iter().find(|hidden| hidden.entity == entity).map(|hidden| &hidden.ship)
+    }
+
+    pub fn ships(&self) -> impl Iterator<Item = &Ship> {
+        self.ships.iter().map(|hidden| &hidden.ship)
+    }
+}
+
+/**
+ * todos os navios da partida, os do mundo e os ainda escondidos, pela
+ * entidade. regras de tiro, habilidades e movimento passam por aqui para
+ * não precisar saber se o navio já foi revelado
+ */
+#[derive(SystemParam)]
+pub struct Fleet<'w, 's> {
+    ships: Query<'w, 's, (Entity, &'static mut Ship, &'static ShipDirection)>,
+    hidden: ResMut<'w, HiddenFleet>,
+}
+
+impl Fleet<'_, '_> {
+    pub fn get(&self, entity: Entity) -> Option<&Ship> {
+        match self.ships.get(entity) {
+            Ok((_, ship, _)) => Some(ship),
+            Err(_) => self.hidden.get(entity),
+        }
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut Ship> {
+        match self.ships.get_mut(entity) {
+            Ok((_, ship, _)) => Some(ship.into_inner()),
+            Err(_) => self
+                .hidden
+                .ships
+                .iter_mut()
+                .find(|hidden| hidden.entity == entity)
+                .map(|hidden| &mut hidden.ship),
+        }
+    }
+
+    pub fn direction(&self, entity: Entity) -> Option<&ShipDirection> {
+        match self.ships.get(entity) {
+            Ok((_, _, direction)) => Some(direction),
+            Err(_) => self
+                .hidden
+                .ships
+                .iter()
+                .find(|hidden| hidden.entity == entity)
+                .map(|hidden| &hidden.direction),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &Ship)> {
+        self.ships
+            .iter()
+            .map(|(entity, ship, _)| (entity, ship))
+            .chain(self.hidden.ships.iter().map(|hidden| (hidden.entity, &hidden.ship)))
+    }
+}
+
+/**
+ * atlas com um bloco por segmento de navio (proa, meio, popa e o navio de
+ * uma célula só), em linhas: intacto, avariado e destroço na horizontal,
+ * depois os mesmos três na vertical com a proa para baixo
+ */
+pub const SHIP_ATLAS_PATH: &str = "atlases/ship_segments.png";
+const SHIP_TILE_SIZE: u32 = 64;
+const SHIP_ATLAS_COLUMNS: u32 = 4;
+const SHIP_ATLAS_ROWS: u32 = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentState {
+    Intact,
+    Damaged,
+    Wreck,
+}
+
+#[derive(Resource, Clone)]
+pub struct ShipAtlas {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+impl ShipAtlas {
+    // bloco do atlas para o segmento `index` de um navio do tipo `spec`
+    pub fn tile(index: usize, spec: &ShipSpec, direction: &ShipDirection, state: SegmentState) -> usize {
+        let length = spec.length;
+        let column = if spec.sprite == ShipSprite::Single {
+            3
+        } else if index == 0 {
+            0
+        } else if index == length - 1 {
+            2
+        } else {
+            1
+        };
+
+        let row = match state {
+            SegmentState::Intact => 0,
+            SegmentState::Damaged => 1,
+            SegmentState::Wreck => 2,
+        } + if *direction == ShipDirection::Vertical { 3 } else { 0 };
+
+        row * SHIP_ATLAS_COLUMNS as usize + column
+    }
+
+    pub fn sprite(&self, tile: usize, size: Vec2) -> Sprite {
+        Sprite {
+            image: self.image.clone(),
+            texture_atlas: Some(TextureAtlas {
+                layout: self.layout.clone(),
+                index: tile,
+            }),
+            custom_size: Some(size),
+            ..Default::default()
+        }
+    }
+}
+
+// segmento desenhado de um navio; o índice é o mesmo da célula em Ship.cells
+#[derive(Component, Debug)]
+pub struct ShipSegment {
+    pub index: usize,
+}
+
+// navio inimigo que sobrou no fim da partida, revelado com destaque
+#[derive(Component, Debug)]
+pub struct MissedShip;
+
+pub struct ShipSprites;
+
+impl Plugin for ShipSprites {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShipCatalog>()
+            .init_resource::<HiddenFleet>()
+            .add_systems(Startup, load_ship_atlas)
+            .add_systems(Update, reveal_ships.run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (attach_ship_segments, update_ship_segments).chain());
+    }
+}
+
+fn load_ship_atlas(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    commands.insert_resource(ShipAtlas {
+        image: asset_server.load(SHIP_ATLAS_PATH),
+        layout: layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(SHIP_TILE_SIZE),
+            SHIP_ATLAS_COLUMNS,
+            SHIP_ATLAS_ROWS,
+            None,
+            None,
+        )),
+    });
+}
+
+const WRECK_TINT: Color = Color::srgb(0.6, 0.6, 0.65);
+
 pub const SUBMARINE_SIZE: usize = 1;
+pub const DESTROYER_SIZE: usize = 2;
 pub const BATTLESHIP_SIZE: usize = 3;
 pub const LARGE_BATTLESHIP_SIZE: usize = 4;
 pub const AIRCRAFT_CARRIER_SIZE: usize = 5;
+pub const CORVETTE_SIZE: usize = 4;
+pub const FRIGATE_SIZE: usize = 4;
+
+// posição de um navio no tabuleiro inteiro, como os construtores de ShipBundle recebem
+pub type BoardShip = (Side, ShipType, ShipDirection, i8, i8, Option<Shape>);
 
 #[allow(dead_code)]
 impl ShipBundle {
-    pub fn new_submarine(
-        asset_server: &Res<AssetServer>,
+    // navio de qualquer tipo a partir do registro; origem em (coluna, linha) do tabuleiro inteiro
+    pub fn spawn(
+        spec: &ShipSpec,
+        side: Side,
+        origin: (i8, i8),
         direction: ShipDirection,
-        x: i8,
-        y: i8,
-        cells_query: &Query<(Entity, &Cell)>,
-        game_state: &mut ResMut<GameState>,
+        index: &BoardIndex,
+        settings: &GameSettings,
     ) -> ShipBundle {
-        let color = if y > 4 {
-            game_state.total_ships_bot += 1;
-            Color::srgba(1.0, 1.0, 1.0, 0.0) // Azul claro para inimigos
-        } else {
-            game_state.total_ships_player += 1;
-            Color::srgba(1.0, 1.0, 1.0, 1.0) // Cor padrão para o jogador
-        };
+        let (x, y) = origin;
 
         ShipBundle {
             ship: Ship {
-                r#type: ShipType::Submarine,
-                cells: Self::find_cells_for_ship(x, y, SUBMARINE_SIZE, &direction, &cells_query),
+                r#type: spec.ship_type.clone(),
+                side,
+                cells: Self::find_cells_for_ship(x, y, spec.length, &direction, index),
                 sunk: false,
+                ability_used: false,
             },
-            direction: direction.clone(),
             transform: Transform {
-                translation: ShipBundle::calculate_position(SUBMARINE_SIZE, &direction, x, y),
-                rotation: match direction {
-                    ShipDirection::Horizontal => Quat::from_rotation_z(0.0),
-                    ShipDirection::Vertical => Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
-                },
-                ..Default::default()
-            },
-            sprite: Sprite {
-                custom_size: Some(Vec2::new(SUBMARINE_SIZE as f32 * SLOT_SIZE, SLOT_SIZE)),
-                image: asset_server.load("atlases/submarine.png"),
-                color,
+                translation: ShipBundle::calculate_position(spec.length, &direction, x, y, settings),
                 ..Default::default()
             },
+            direction,
+            visibility: Visibility::Inherited,
         }
     }
 
-    pub fn new_battleship(
-        asset_server: &Res<AssetServer>,
-        direction: ShipDirection,
-        x: i8,
-        y: i8,
-        cells_query: &Query<(Entity, &Cell)>,
-        game_state: &mut ResMut<GameState>,
-    ) -> ShipBundle {
-        let color = if y > 4 {
-            game_state.total_ships_bot += 1;
-            Color::srgba(1.0, 1.0, 1.0, 0.0) // Azul claro para inimigos
-        } else {
-            game_state.total_ships_player += 1;
-            Color::srgba(1.0, 1.0, 1.0, 1.0) // Cor padrão para o jogador
-        };
+    // navio com formato, com o canto do formato na célula (coluna, linha) da origem
+    pub fn spawn_shaped(
+        spec: &ShipSpec,
+        side: Side,
+        origin: (i8, i8),
+        shape: &Shape,
+        index: &BoardIndex,
+        settings: &GameSettings,
+    ) -> (ShipBundle, ShipShape) {
+        let (x, y) = origin;
+        let mut bundle = ShipBundle::spawn(spec, side, origin, ShipDirection::Horizontal, index, settings);
 
-        ShipBundle {
-            ship: Ship {
-                r#type: ShipType::Battleship,
-                cells: Self::find_cells_for_ship(x, y, BATTLESHIP_SIZE, &direction, &cells_query),
-                sunk: false,
-            },
-            direction: direction.clone(),
-            transform: Transform {
-                translation: ShipBundle::calculate_position(BATTLESHIP_SIZE, &direction, x, y),
-                rotation: match direction {
-                    ShipDirection::Horizontal => Quat::from_rotation_z(0.0),
-                    ShipDirection::Vertical => Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
-                },
-                ..Default::default()
-            },
-            sprite: Sprite {
-                custom_size: Some(Vec2::new(BATTLESHIP_SIZE as f32 * SLOT_SIZE, SLOT_SIZE)),
-                image: asset_server.load("atlases/battleship.png"),
-                color,
-                ..Default::default()
-            },
-        }
+        bundle.ship.cells = shape
+            .at(y as usize, x as usize)
+            .into_iter()
+            .filter_map(|(row, column)| index.at(row, column))
+            .collect();
+        bundle.transform.translation = ShipBundle::shape_position(shape, x, y, settings);
+
+        (bundle, ShipShape(shape.clone()))
     }
 
-    pub fn new_large_battleship(
-        asset_server: &Res<AssetServer>,
-        direction: ShipDirection,
-        x: i8,
-        y: i8,
-        cells_query: &Query<(Entity, &Cell)>,
-        game_state: &mut ResMut<GameState>,
-    ) -> ShipBundle {
-        let color = if y > 4 {
-            game_state.total_ships_bot += 1;
-            Color::srgba(1.0, 1.0, 1.0, 0.0) // Azul claro para inimigos
-        } else {
-            game_state.total_ships_player += 1;
-            Color::srgba(1.0, 1.0, 1.0, 1.0) // Cor padrão para o jogador
-        };
+    // centro do retângulo do formato; para um navio reto dá o mesmo que calculate_position
+    pub fn shape_position(shape: &Shape, x: i8, y: i8, settings: &GameSettings) -> Vec3 {
+        let (rows, columns) = shape.size();
+        let step = SLOT_SIZE + SLOT_SPACE_BETWEEN;
+        let corner = ShipBundle::calculate_position(1, &ShipDirection::Horizontal, x, y, settings);
 
-        ShipBundle {
-            ship: Ship {
-                r#type: ShipType::LargeBattleship,
-                cells: Self::find_cells_for_ship(
-                    x,
-                    y,
-                    LARGE_BATTLESHIP_SIZE,
-                    &direction,
-                    &cells_query,
-                ),
-                sunk: false,
-            },
-            direction: direction.clone(),
-            transform: Transform {
-                translation: ShipBundle::calculate_position(
-                    LARGE_BATTLESHIP_SIZE,
-                    &direction,
-                    x,
-                    y,
-                ),
-                rotation: match direction {
-                    ShipDirection::Horizontal => Quat::from_rotation_z(0.0),
-                    ShipDirection::Vertical => Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
-                },
-                ..Default::default()
-            },
-            sprite: Sprite {
-                custom_size: Some(Vec2::new(
-                    LARGE_BATTLESHIP_SIZE as f32 * SLOT_SIZE,
-                    SLOT_SIZE,
-                )),
-                image: asset_server.load("atlases/large_battleship.png"),
-                color,
-                ..Default::default()
-            },
-        }
+        corner + Vec3::new(columns as f32 - 1.0, rows as f32 - 1.0, 0.0) * step / 2.0
     }
 
-    pub fn new_aircraft_carrier(
-        asset_server: &Res<AssetServer>,
-        direction: ShipDirection,
+    // centro do navio que começa na célula (x, y) e segue para a direita ou para cima
+    pub fn calculate_position(
+        ship_size: usize,
+        direction: &ShipDirection,
         x: i8,
         y: i8,
-        cells_query: &Query<(Entity, &Cell)>,
-        game_state: &mut ResMut<GameState>,
-    ) -> ShipBundle {
-        let color = if y > 4 {
-            game_state.total_ships_bot += 1;
-            Color::srgba(1.0, 1.0, 1.0, 0.0) // Azul claro para inimigos
-        } else {
-            game_state.total_ships_player += 1;
-            Color::srgba(1.0, 1.0, 1.0, 1.0) // Cor padrão para o jogador
-        };
+        settings: &GameSettings,
+    ) -> Vec3 {
+        let step = SLOT_SIZE + SLOT_SPACE_BETWEEN;
 
-        ShipBundle {
-            ship: Ship {
-                r#type: ShipType::LargeBattleship,
-                cells: Self::find_cells_for_ship(
-                    x,
-                    y,
-                    AIRCRAFT_CARRIER_SIZE,
-                    &direction,
-                    &cells_query,
-                ),
-                sunk: false,
-            },
-            direction: direction.clone(),
-            transform: Transform {
-                translation: ShipBundle::calculate_position(
-                    AIRCRAFT_CARRIER_SIZE,
-                    &direction,
-                    x,
-                    y,
-                ),
-                rotation: match direction {
-                    ShipDirection::Horizontal => Quat::from_rotation_z(0.0),
-                    ShipDirection::Vertical => Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
-                },
-                ..Default::default()
-            },
-            sprite: Sprite {
-                custom_size: Some(Vec2::new(
-                    AIRCRAFT_CARRIER_SIZE as f32 * SLOT_SIZE,
-                    SLOT_SIZE,
-                )),
-                image: asset_server.load("atlases/aircraft_carrier.png"),
-                color,
-                ..Default::default()
-            },
-        }
-    }
+        // centro da primeira célula, calculado como em render_board
+        let first = Vec2::new(
+            x as f32 * step - settings.columns() as f32 * step / 2.0,
+            y as f32 * step - settings.rows() as f32 * step / 2.0,
+        );
 
-    pub fn calculate_position(ship_size: usize, direction: &ShipDirection, x: i8, y: i8) -> Vec3 {
-        let x = x as f32;
-        let y = y as f32;
-
-        Vec3::new(
-            if ship_size % 2 == 0 && direction == &ShipDirection::Horizontal {
-                (x) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                    - (ROWS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0)
-                    + (SLOT_SIZE * 1.5)
-            } else if ship_size == SUBMARINE_SIZE {
-                (x) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                    - (ROWS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0)
-            } else if ship_size == BATTLESHIP_SIZE {
-                (x) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                    - (ROWS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0)
-                    + SLOT_SIZE
-            } else {
-                (x) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                    - (ROWS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0)
-                    + (SLOT_SIZE * 2.0 + SLOT_SPACE_BETWEEN)
-            },
-            if ship_size % 2 == 0 && direction == &ShipDirection::Vertical {
-                (y) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                    - (COLUMNS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0)
-                    + (SLOT_SIZE * 1.5)
-            } else {
-                (y) * (SLOT_SIZE + SLOT_SPACE_BETWEEN)
-                    - (COLUMNS as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0)
-            },
-            Vec3::default().z + 1.0,
-        )
+        // cada célula a mais empurra o centro meio passo na direção do navio
+        let along = (ship_size as f32 - 1.0) / 2.0 * step;
+        let center = match direction {
+            ShipDirection::Horizontal => first + Vec2::new(along, 0.0),
+            ShipDirection::Vertical => first + Vec2::new(0.0, along),
+        };
+
+        center.extend(Vec3::default().z + 1.0)
     }
 
     fn find_cells_for_ship(
@@ -273,7 +556,7 @@ impl ShipBundle {
         y: i8,
         ship_size: usize,
         direction: &ShipDirection,
-        cells_query: &Query<(Entity, &Cell)>,
+        index: &BoardIndex,
     ) -> Vec<Entity> {
         let mut cells = Vec::new();
 
@@ -289,10 +572,7 @@ impl ShipBundle {
                 y
             };
 
-            if let Some((entity, _)) = cells_query
-                .iter()
-                .find(|(_, cell)| cell.column == target_x as usize && cell.row == target_y as usize)
-            {
+            if let Some(entity) = index.at(target_y as usize, target_x as usize) {
                 cells.push(entity);
             }
         }
@@ -301,85 +581,345 @@ impl ShipBundle {
     }
 }
 
-pub fn debug_spawn_submarine(
+/**
+ * frotas do começo da partida: a do jogador carregada do arquivo entra no
+ * mundo, a inimiga (sorteada, do motor externo ou do arquivo) fica na
+ * HiddenFleet até ser revelada. com dois jogadores as duas são sorteadas e
+ * entram no mundo, como no replay, onde as duas ficam à mostra
+ */
+pub fn spawn_fleets(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    cells_query: Query<(Entity, &Cell)>,
-    mut game_state: ResMut<GameState>,
+    mut index: ResMut<BoardIndex>,
+    settings: Res<GameSettings>,
+    network: Option<Res<NetworkSession>>,
+    spectator: Option<Res<SpectatorSession>>,
+    external: Option<ResMut<ExternalOpponent>>,
+    pending_load: Option<Res<PendingLoad>>,
+    catalog: Res<ShipCatalog>,
+    mut hidden: ResMut<HiddenFleet>,
+    (hotseat, replay): (Option<Res<Hotseat>>, Option<Res<ReplaySession>>),
+) {
+    hidden.ships.clear();
+
+    // numa partida em rede a frota inimiga fica só no servidor
+    if network.is_some() || spectator.is_some() {
+        return;
+    }
+
+    // jogo carregado: as duas frotas vêm do arquivo, em linhas do tabuleiro inteiro
+    let saved = pending_load.map(|pending| {
+        let mut ships = board_ships(&catalog, &pending.0.enemy_fleet, Side::Enemy, 0)?;
+        ships.extend(board_ships(&catalog, &pending.0.player_fleet, Side::Player, 0)?);
+        Ok::<_, String>(ships)
+    });
+
+    // motor externo posiciona a própria frota; se errar, volta para o posicionamento aleatório
+    let placements = match saved {
+        Some(Ok(placements)) => placements,
+        Some(Err(error)) => {
+            warn!("jogo salvo com frota inválida: {error}");
+            random_side_fleet(&settings, &catalog, Side::Enemy)
+        }
+        None if hotseat.is_some() => {
+            let mut ships = random_side_fleet(&settings, &catalog, Side::Player);
+            ships.extend(random_side_fleet(&settings, &catalog, Side::Enemy));
+            ships
+        }
+        None => match external.map(|mut opponent| external_fleet(&mut opponent, &settings, &catalog)) {
+            Some(Ok(placements)) => placements,
+            Some(Err(error)) => {
+                warn!("motor externo não posicionou a frota: {error}");
+                random_side_fleet(&settings, &catalog, Side::Enemy)
+            }
+            None => random_side_fleet(&settings, &catalog, Side::Enemy),
+        },
+    };
+
+    for (side, ship_type, direction, x, y, shape) in placements {
+        let spec = catalog.spec(&ship_type);
+        let (bundle, shape) = match shape {
+            Some(shape) => {
+                let (bundle, shape) = ShipBundle::spawn_shaped(spec, side, (x, y), &shape, &index, &settings);
+                (bundle, Some(shape))
+            }
+            None => (ShipBundle::spawn(spec, side, (x, y), direction, &index, &settings), None),
+        };
+
+        if side == Side::Player || hotseat.is_some() || replay.is_some() {
+            let mut entity = commands.spawn((bundle, StateScoped(AppState::InGame)));
+            if let Some(shape) = shape {
+                entity.insert(shape);
+            }
+            continue;
+        }
+
+        // só a entidade vazia: o navio entra nela em reveal_ships
+        let entity = commands.spawn(StateScoped(AppState::InGame)).id();
+        index.place_ship(entity, &bundle.ship.cells);
+        hidden.ships.push(HiddenShip {
+            entity,
+            ship: bundle.ship,
+            direction: bundle.direction,
+            shape,
+        });
+    }
+}
+
+// navio escondido entra no mundo ao afundar; no fim da partida os que sobraram entram com destaque
+fn reveal_ships(
+    mut commands: Commands,
+    mut hidden: ResMut<HiddenFleet>,
+    game_state: Res<GameState>,
+    index: Res<BoardIndex>,
+) {
+    let over = game_state.winner.is_some();
+    if !hidden.ships.iter().any(|hidden| hidden.ship.sunk || over) {
+        return;
+    }
+
+    let (revealed, kept): (Vec<HiddenShip>, Vec<HiddenShip>) = std::mem::take(&mut hidden.ships)
+        .into_iter()
+        .partition(|hidden| hidden.ship.sunk || over);
+    hidden.ships = kept;
+
+    for HiddenShip { entity, ship, direction, shape } in revealed {
+        // centro do retângulo das células, como shape_position e calculate_position calculam
+        let positions: Vec<(usize, usize)> = ship.cells.iter().filter_map(|cell| index.board_position(*cell)).collect();
+        let low = positions.iter().fold((usize::MAX, usize::MAX), |low, cell| (low.0.min(cell.0), low.1.min(cell.1)));
+        let high = positions.iter().fold((0, 0), |high, cell| (high.0.max(cell.0), high.1.max(cell.1)));
+        let center = (index.center(low.0, low.1) + index.center(high.0, high.1)) / 2.0;
+        let missed = !ship.sunk;
+
+        let mut entity = commands.entity(entity);
+        entity.insert(ShipBundle {
+            ship,
+            direction,
+            transform: Transform::from_translation(center.extend(1.0)),
+            visibility: Visibility::Inherited,
+        });
+        if let Some(shape) = shape {
+            entity.insert(shape);
+        }
+        if missed {
+            entity.insert(MissedShip);
+        }
+    }
+}
+
+// mesmo sorteio do servidor, então vale para navios com formato
+fn random_side_fleet(settings: &GameSettings, catalog: &ShipCatalog, side: Side) -> Vec<BoardShip> {
+    let row_offset = side.index() * settings.rows() / 2;
+
+    random_fleet(&settings.rules(catalog), &mut rand::thread_rng())
+        .and_then(|placements| board_ships(catalog, &placements, side, row_offset))
+        .unwrap_or_else(|error| {
+            warn!("frota sorteada inválida: {error}");
+            Vec::new()
+        })
+}
+
+// frota do motor externo, validada pelas mesmas regras do servidor antes de entrar no tabuleiro
+fn external_fleet(
+    opponent: &mut ExternalOpponent,
+    settings: &GameSettings,
+    catalog: &ShipCatalog,
+) -> Result<Vec<BoardShip>, String> {
+    let rules = settings.rules(catalog);
+
+    opponent.bot.new_game(&rules)?;
+    let placements = opponent.bot.place(&rules)?;
+    Match::new(rules)
+        .place_fleet(1, &placements)
+        .map_err(|error| error.to_string())?;
+
+    board_ships(catalog, &placements, Side::Enemy, settings.rows() / 2)
+}
+
+// posicionamentos do motor ou do arquivo salvo no formato dos construtores de ShipBundle
+fn board_ships(
+    catalog: &ShipCatalog,
+    placements: &[ShipPlacement],
+    side: Side,
+    row_offset: usize,
+) -> Result<Vec<BoardShip>, String> {
+    placements
+        .iter()
+        .map(|placement| {
+            let ship_type = catalog
+                .from_name(&placement.name)
+                .ok_or_else(|| format!("navio desconhecido: {}", placement.name))?;
+            let direction = if placement.vertical {
+                ShipDirection::Vertical
+            } else {
+                ShipDirection::Horizontal
+            };
+
+            Ok((
+                side,
+                ship_type,
+                direction,
+                placement.column as i8,
+                (placement.row + row_offset) as i8,
+                placement.shape.clone(),
+            ))
+        })
+        .collect()
+}
+
+// caminho inverso: a frota de um lado como posicionamentos, em linhas do tabuleiro inteiro
+pub fn placed_fleet<'a>(
+    ships: impl Iterator<Item = &'a Ship>,
+    cells_query: &Query<&Cell>,
+    side: Side,
+) -> Vec<ShipPlacement> {
+    let mut placements = Vec::new();
+
+    // o formato sai das próprias células, então vale para navios retos e com formato
+    for ship in ships.filter(|ship| ship.side == side) {
+        let cells: Vec<(usize, usize)> = ship
+            .cells
+            .iter()
+            .filter_map(|entity| cells_query.get(*entity).ok())
+            .map(|cell| (cell.row, cell.column))
+            .collect();
+
+        let Ok(shape) = Shape::new(&cells) else {
+            continue;
+        };
+        let row = cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+        let column = cells.iter().map(|(_, column)| *column).min().unwrap_or(0);
+
+        placements.push(ShipPlacement::from_shape(ship.r#type.name(), row, column, &shape));
+    }
+
+    placements
+}
+
+// função que vai escutar a mudança de sunk e vai fazer algo a partir disso
+// um filho por célula, centrados no navio e enfileirados na direção dele.
+// girar o navio durante o arrasto troca a direção ou o formato e refaz os filhos.
+// o navio inimigo só chega aqui depois de revelado (reveal_ships)
+fn attach_ship_segments(
+    mut commands: Commands,
+    atlas: Res<ShipAtlas>,
+    ships_query: Query<
+        (Entity, &Ship, &ShipDirection, Option<&ShipShape>, Option<&Children>),
+        Or<(Changed<ShipDirection>, Changed<ShipShape>)>,
+    >,
+    segments_query: Query<(), With<ShipSegment>>,
+    catalog: Res<ShipCatalog>,
 ) {
-    let mut rng = rand::thread_rng();
-    let mut occupied_cells: HashSet<(usize, usize)> = HashSet::new();
-
-    let ships_to_spawn = vec![
-        (ShipType::AircraftCarrier, 5),
-        (ShipType::LargeBattleship, 4),
-        (ShipType::Battleship, 3),
-        (ShipType::Submarine, 1),
-    ];
-
-    for (ship_type, size) in ships_to_spawn {
-        let mut placed = false;
-
-        while !placed {
-            let x = rng.gen_range(0..=(10 - size as i8));
-            let y = rng.gen_range(5..10);
-
-            let ship_cells: Vec<(usize, usize)> = (0..size)
-                .map(|i| ((x + i as i8) as usize, y as usize))
-                .collect();
-
-            if ship_cells.iter().all(|pos| !occupied_cells.contains(pos)) {
-                for cell in &ship_cells {
-                    occupied_cells.insert(*cell);
+    let step = SLOT_SIZE + SLOT_SPACE_BETWEEN;
+
+    for (entity, ship, direction, shape, children) in ships_query.iter() {
+        let spec = catalog.spec(&ship.r#type);
+        let length = spec.length;
+
+        for child in children.into_iter().flatten() {
+            if segments_query.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+
+        commands.entity(entity).with_children(|parent| {
+            // navio com formato: um bloco inteiro por célula, na ordem de Shape::at
+            if let Some(ShipShape(shape)) = shape {
+                let (rows, columns) = shape.size();
+                let center = Vec2::new(columns as f32 - 1.0, rows as f32 - 1.0) / 2.0;
+
+                for (index, (row, column)) in shape.cells().iter().enumerate() {
+                    let offset = (Vec2::new(*column as f32, *row as f32) - center) * step;
+                    parent.spawn((
+                        atlas.sprite(
+                            ShipAtlas::tile(index, spec, direction, SegmentState::Intact),
+                            Vec2::splat(SLOT_SIZE),
+                        ),
+                        Transform::from_translation(offset.extend(0.0)),
+                        ShipSegment { index },
+                    ));
                 }
+                return;
+            }
 
-                let bundle = match ship_type {
-                    ShipType::AircraftCarrier => ShipBundle::new_aircraft_carrier(
-                        &asset_server,
-                        ShipDirection::Horizontal,
-                        x,
-                        y,
-                        &cells_query,
-                        &mut game_state,
-                    ),
-                    ShipType::LargeBattleship => ShipBundle::new_large_battleship(
-                        &asset_server,
-                        ShipDirection::Horizontal,
-                        x,
-                        y,
-                        &cells_query,
-                        &mut game_state,
-                    ),
-                    ShipType::Battleship => ShipBundle::new_battleship(
-                        &asset_server,
-                        ShipDirection::Horizontal,
-                        x,
-                        y,
-                        &cells_query,
-                        &mut game_state,
-                    ),
-                    ShipType::Submarine => ShipBundle::new_submarine(
-                        &asset_server,
-                        ShipDirection::Horizontal,
-                        x,
-                        y,
-                        &cells_query,
-                        &mut game_state,
-                    ),
+            for index in 0..length {
+                let offset = (index as f32 - (length as f32 - 1.0) / 2.0) * step;
+                let (translation, size) = match direction {
+                    ShipDirection::Horizontal => (Vec3::new(offset, 0.0, 0.0), Vec2::new(step, SLOT_SIZE)),
+                    ShipDirection::Vertical => (Vec3::new(0.0, offset, 0.0), Vec2::new(SLOT_SIZE, step)),
                 };
 
-                commands.spawn(bundle);
-                placed = true;
+                parent.spawn((
+                    atlas.sprite(ShipAtlas::tile(index, spec, direction, SegmentState::Intact), size),
+                    Transform::from_translation(translation),
+                    ShipSegment { index },
+                ));
+            }
+        });
+    }
+}
+
+/**
+ * segmento atingido mostra a marca de queimado quando o projétil chega,
+ * o navio afundado escurece até virar destroço e o navio inimigo que
+ * sobrou no fim da partida ganha a cor de destaque
+ */
+fn update_ship_segments(
+    ships_query: Query<(&Ship, &ShipDirection, &Children, Option<&Sinking>, Has<MissedShip>)>,
+    mut segments_query: Query<(&ShipSegment, &mut Sprite)>,
+    shot_grid: Res<ShotGrid>,
+    index: Res<BoardIndex>,
+    projectiles_query: Query<&Projectile>,
+    theme: Res<Theme>,
+    catalog: Res<ShipCatalog>,
+) {
+    for (ship, direction, children, sinking, missed) in ships_query.iter() {
+        let spec = catalog.spec(&ship.r#type);
+        let sinking_progress = sinking.map(Sinking::progress);
+
+        for child in children.iter() {
+            let Ok((segment, mut sprite)) = segments_query.get_mut(*child) else {
+                continue;
+            };
+
+            let hit = ship.cells.get(segment.index).is_some_and(|cell_entity| {
+                index
+                    .position(*cell_entity)
+                    .is_some_and(|(side, row, column)| shot_grid.is_known(&side, row, column))
+                    && !projectiles_query
+                        .iter()
+                        .any(|projectile| projectile.target == *cell_entity)
+            });
+
+            let state = match sinking_progress {
+                Some(progress) if progress >= 1.0 => SegmentState::Wreck,
+                _ if hit => SegmentState::Damaged,
+                _ => SegmentState::Intact,
+            };
+            let tile = ShipAtlas::tile(segment.index, spec, direction, state);
+
+            if let Some(texture_atlas) = sprite.texture_atlas.as_mut() {
+                if texture_atlas.index != tile {
+                    texture_atlas.index = tile;
+                }
+            }
+
+            let base = if missed { theme.palette().missed_ship } else { Color::WHITE };
+            let color = base.mix(&WRECK_TINT, sinking_progress.unwrap_or(0.0));
+            if sprite.color != color {
+                sprite.color = color;
             }
         }
     }
 }
 
-// função que vai escutar a mudança de sunk e vai fazer algo a partir disso
-pub fn check_sunk_change(mut query: Query<(&mut Ship, &mut Sprite)>) {
-    for (ship, mut sprite) in query.iter_mut() {
+// navio recém-afundado ganha a animação de afundar (que também revela o navio inimigo)
+pub fn check_sunk_change(
+    mut commands: Commands,
+    query: Query<(Entity, &Ship, &Transform), (Changed<Ship>, Without<Sinking>)>,
+) {
+    for (entity, ship, transform) in query.iter() {
         if ship.sunk {
-            sprite.color = Color::srgba(1.0, 1.0, 1.0, 1.0);
+            commands.entity(entity).insert(Sinking::new(transform));
         }
     }
 }
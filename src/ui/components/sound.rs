@@ -0,0 +1,488 @@
+use std::{sync::Arc, time::Duration};
+
+use bevy::{
+    audio::{AddAudioSource, Source, Volume},
+    prelude::*,
+};
+
+use crate::logic::shot::ShotOutcome;
+
+use super::{
+    board::{GameState, MatchEnded, ShotResolved},
+    effects::ShotLanded,
+    main_menu::AppState,
+    settings::GameSettings,
+    ship_selection_panel::SelectedShip,
+};
+
+pub struct SoundPlugin;
+
+const SAMPLE_RATE: u32 = 44_100;
+// quadros que um som espera pelo dispositivo antes de o áudio ser dado como ausente
+const DEVICE_WAIT_FRAMES: u32 = 30;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    Triangle,
+}
+
+// uma nota com o tom deslizando de start_hz até end_hz
+#[derive(Clone, Copy, Debug)]
+pub struct Note {
+    pub start_hz: f32,
+    pub end_hz: f32,
+    pub seconds: f32,
+}
+
+const fn note(start_hz: f32, end_hz: f32, seconds: f32) -> Note {
+    Note {
+        start_hz,
+        end_hz,
+        seconds,
+    }
+}
+
+/**
+ * som sintetizado na hora, sem arquivo de áudio: uma sequência de notas
+ * misturada com ruído (água, explosão). cada nota decai até o silêncio,
+ * menos na música, que usa envelope mais suave
+ */
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct Synth {
+    pub notes: Arc<[Note]>,
+    pub waveform: Waveform,
+    pub noise: f32, // 0 = só tom, 1 = só ruído
+    pub decay: bool,
+}
+
+impl Synth {
+    fn new(notes: &[Note], waveform: Waveform, noise: f32, decay: bool) -> Self {
+        Synth {
+            notes: notes.into(),
+            waveform,
+            noise,
+            decay,
+        }
+    }
+}
+
+pub struct SynthDecoder {
+    synth: Synth,
+    note: usize,
+    sample: u32, // amostra dentro da nota atual
+    phase: f32,
+    noise_state: u32,
+}
+
+impl Iterator for SynthDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let note = *self.synth.notes.get(self.note)?;
+        let length = (note.seconds * SAMPLE_RATE as f32) as u32;
+
+        if self.sample >= length {
+            self.note += 1;
+            self.sample = 0;
+            return self.next();
+        }
+
+        let progress = self.sample as f32 / length as f32;
+        self.sample += 1;
+
+        let frequency = note.start_hz + (note.end_hz - note.start_hz) * progress;
+        self.phase = (self.phase + frequency / SAMPLE_RATE as f32).fract();
+
+        let tone = match self.synth.waveform {
+            Waveform::Sine => (self.phase * std::f32::consts::TAU).sin(),
+            Waveform::Square => {
+                if self.phase < 0.5 {
+                    0.5
+                } else {
+                    -0.5
+                }
+            }
+            Waveform::Triangle => 1.0 - 4.0 * (self.phase - 0.5).abs(),
+        };
+
+        // xorshift: ruído barato e sem depender de rand dentro da thread de áudio
+        self.noise_state ^= self.noise_state << 13;
+        self.noise_state ^= self.noise_state >> 17;
+        self.noise_state ^= self.noise_state << 5;
+        let noise = self.noise_state as f32 / u32::MAX as f32 * 2.0 - 1.0;
+
+        let envelope = if self.synth.decay {
+            (1.0 - progress).powi(2)
+        } else {
+            (progress * 20.0).min(1.0) * ((1.0 - progress) * 20.0).min(1.0) // sem estalos entre notas
+        };
+
+        Some((tone * (1.0 - self.synth.noise) + noise * self.synth.noise) * envelope * 0.6)
+    }
+}
+
+impl Source for SynthDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        let seconds: f32 = self.synth.notes.iter().map(|note| note.seconds).sum();
+        Some(Duration::from_secs_f32(seconds))
+    }
+}
+
+impl bevy::audio::Decodable for Synth {
+    type DecoderItem = f32;
+    type Decoder = SynthDecoder;
+
+    fn decoder(&self) -> SynthDecoder {
+        SynthDecoder {
+            synth: self.clone(),
+            note: 0,
+            sample: 0,
+            phase: 0.0,
+            noise_state: 0x9e37_79b9,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundEffect {
+    Fire,
+    Splash,
+    Hit,
+    Sink,
+    Victory,
+    Defeat,
+}
+
+// fase do jogo que define a música de fundo
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusicPhase {
+    Menu,
+    Battle,
+    GameOver, // silêncio depois da fanfarra de vitória ou derrota
+}
+
+#[derive(Resource)]
+struct Sounds {
+    fire: Handle<Synth>,
+    splash: Handle<Synth>,
+    hit: Handle<Synth>,
+    sink: Handle<Synth>,
+    victory: Handle<Synth>,
+    defeat: Handle<Synth>,
+    menu_music: Handle<Synth>,
+    battle_music: Handle<Synth>,
+}
+
+impl Sounds {
+    fn effect(&self, effect: SoundEffect) -> Handle<Synth> {
+        match effect {
+            SoundEffect::Fire => self.fire.clone(),
+            SoundEffect::Splash => self.splash.clone(),
+            SoundEffect::Hit => self.hit.clone(),
+            SoundEffect::Sink => self.sink.clone(),
+            SoundEffect::Victory => self.victory.clone(),
+            SoundEffect::Defeat => self.defeat.clone(),
+        }
+    }
+}
+
+/**
+ * sem placa de som (servidor, CI, container) o bevy só avisa e nunca toca
+ * nada, e os sons ficariam acumulados para sempre. o primeiro som que não
+ * ganha AudioSink em DEVICE_WAIT_FRAMES quadros desliga o áudio do jogo
+ */
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioDevice {
+    Unknown,
+    Available,
+    Missing,
+}
+
+#[derive(Component)]
+struct Music;
+
+#[derive(Component)]
+struct Sfx;
+
+// som esperando o dispositivo de áudio responder
+#[derive(Component)]
+struct AwaitingSink {
+    frames: u32,
+}
+
+#[derive(Resource)]
+struct CurrentMusic {
+    phase: Option<MusicPhase>,
+    entity: Option<Entity>,
+}
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_source::<Synth>()
+            .insert_resource(AudioDevice::Unknown)
+            .insert_resource(CurrentMusic {
+                phase: None,
+                entity: None,
+            })
+            .add_systems(Startup, create_sounds)
+            .add_systems(
+                Update,
+                (
+                    play_shot_sounds,
+                    play_match_end_sound,
+                    switch_music,
+                    apply_volume.run_if(resource_changed::<GameSettings>),
+                    toggle_mute_on_key,
+                    detect_audio_device,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn create_sounds(mut commands: Commands, mut synths: ResMut<Assets<Synth>>) {
+    // notas: dó, mi, sol, dó da oitava de cima
+    let (c, e, g, high_c) = (261.6, 329.6, 392.0, 523.3);
+
+    commands.insert_resource(Sounds {
+        fire: synths.add(Synth::new(&[note(140.0, 50.0, 0.3)], Waveform::Sine, 0.55, true)),
+        splash: synths.add(Synth::new(&[note(900.0, 300.0, 0.5)], Waveform::Sine, 0.85, true)),
+        hit: synths.add(Synth::new(&[note(220.0, 70.0, 0.45)], Waveform::Square, 0.5, true)),
+        sink: synths.add(Synth::new(&[note(320.0, 40.0, 1.4)], Waveform::Triangle, 0.4, true)),
+        victory: synths.add(Synth::new(
+            &[note(c, c, 0.15), note(e, e, 0.15), note(g, g, 0.15), note(high_c, high_c, 0.5)],
+            Waveform::Triangle,
+            0.0,
+            true,
+        )),
+        defeat: synths.add(Synth::new(
+            &[note(g, g, 0.25), note(e, e, 0.25), note(c, c, 0.25), note(c / 2.0, c / 2.0, 0.7)],
+            Waveform::Triangle,
+            0.0,
+            true,
+        )),
+        menu_music: synths.add(Synth::new(
+            &[
+                note(c, c, 0.6),
+                note(g, g, 0.6),
+                note(e, e, 0.6),
+                note(g, g, 0.6),
+                note(c * 0.9, c * 0.9, 0.6),
+                note(e * 0.9, e * 0.9, 0.6),
+                note(g * 0.9, g * 0.9, 1.2),
+            ],
+            Waveform::Sine,
+            0.0,
+            false,
+        )),
+        battle_music: synths.add(Synth::new(
+            &[
+                note(c / 4.0, c / 4.0, 0.25),
+                note(c / 4.0, c / 4.0, 0.25),
+                note(g / 4.0, g / 4.0, 0.25),
+                note(c / 4.0, c / 4.0, 0.25),
+                note(e / 4.0, e / 4.0, 0.25),
+                note(e / 4.0, e / 4.0, 0.25),
+                note(g / 4.0, g / 4.0, 0.5),
+            ],
+            Waveform::Square,
+            0.1,
+            false,
+        )),
+    });
+}
+
+fn spawn_sound(
+    commands: &mut Commands,
+    device: AudioDevice,
+    synth: Handle<Synth>,
+    settings: PlaybackSettings,
+    marker: impl Component,
+) -> Option<Entity> {
+    if device == AudioDevice::Missing {
+        return None;
+    }
+
+    let mut sound = commands.spawn((AudioPlayer(synth), settings, marker));
+    if device == AudioDevice::Unknown {
+        sound.insert(AwaitingSink { frames: 0 });
+    }
+
+    Some(sound.id())
+}
+
+fn play_effect(commands: &mut Commands, device: AudioDevice, sounds: &Sounds, settings: &GameSettings, effect: SoundEffect) {
+    let volume = settings.channel_volume(settings.sfx_volume);
+    if volume == 0.0 {
+        return;
+    }
+
+    spawn_sound(
+        commands,
+        device,
+        sounds.effect(effect),
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(volume)),
+        Sfx,
+    );
+}
+
+// o disparo soa na saída do projétil; água, acerto e afundamento quando ele chega
+fn play_shot_sounds(
+    mut commands: Commands,
+    mut shots: EventReader<ShotResolved>,
+    mut landed: EventReader<ShotLanded>,
+    device: Res<AudioDevice>,
+    sounds: Res<Sounds>,
+    settings: Res<GameSettings>,
+) {
+    for _ in shots.read() {
+        play_effect(&mut commands, *device, &sounds, &settings, SoundEffect::Fire);
+    }
+
+    for shot in landed.read() {
+        let effect = match shot.outcome {
+            ShotOutcome::Miss => SoundEffect::Splash,
+            ShotOutcome::Hit => SoundEffect::Hit,
+            ShotOutcome::Sunk => SoundEffect::Sink,
+        };
+        play_effect(&mut commands, *device, &sounds, &settings, effect);
+    }
+}
+
+fn play_match_end_sound(
+    mut commands: Commands,
+    mut match_ended: EventReader<MatchEnded>,
+    device: Res<AudioDevice>,
+    sounds: Res<Sounds>,
+    settings: Res<GameSettings>,
+) {
+    for ended in match_ended.read() {
+        let effect = if ended.summary.won {
+            SoundEffect::Victory
+        } else {
+            SoundEffect::Defeat
+        };
+        play_effect(&mut commands, *device, &sounds, &settings, effect);
+    }
+}
+
+fn switch_music(
+    mut commands: Commands,
+    state: Res<State<AppState>>,
+    game_state: Res<GameState>,
+    device: Res<AudioDevice>,
+    sounds: Res<Sounds>,
+    settings: Res<GameSettings>,
+    mut current: ResMut<CurrentMusic>,
+) {
+    let phase = match state.get() {
+        AppState::InGame | AppState::Restarting if game_state.winner.is_some() => MusicPhase::GameOver,
+        AppState::InGame | AppState::Restarting => MusicPhase::Battle,
+        _ => MusicPhase::Menu,
+    };
+
+    if current.phase == Some(phase) {
+        return;
+    }
+    current.phase = Some(phase);
+
+    if let Some(entity) = current.entity.take() {
+        if let Some(mut music) = commands.get_entity(entity) {
+            music.despawn();
+        }
+    }
+
+    let synth = match phase {
+        MusicPhase::Menu => sounds.menu_music.clone(),
+        MusicPhase::Battle => sounds.battle_music.clone(),
+        MusicPhase::GameOver => return,
+    };
+
+    // a música toca mesmo no volume zero, para continuar de onde estava quando o som voltar
+    current.entity = spawn_sound(
+        &mut commands,
+        *device,
+        synth,
+        PlaybackSettings::LOOP.with_volume(Volume::new(settings.channel_volume(settings.music_volume))),
+        Music,
+    );
+}
+
+fn apply_volume(
+    settings: Res<GameSettings>,
+    music_query: Query<&AudioSink, With<Music>>,
+    sfx_query: Query<&AudioSink, With<Sfx>>,
+) {
+    for sink in music_query.iter() {
+        sink.set_volume(settings.channel_volume(settings.music_volume));
+    }
+
+    for sink in sfx_query.iter() {
+        sink.set_volume(settings.channel_volume(settings.sfx_volume));
+    }
+}
+
+// M liga e desliga o som em qualquer tela, menos com um navio na mão, quando M espelha o navio
+fn toggle_mute_on_key(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<GameSettings>,
+    selected_query: Query<(), With<SelectedShip>>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyM) && selected_query.is_empty() {
+        settings.muted = !settings.muted;
+    }
+}
+
+fn detect_audio_device(
+    mut commands: Commands,
+    mut device: ResMut<AudioDevice>,
+    mut current: ResMut<CurrentMusic>,
+    mut awaiting_query: Query<(Entity, &mut AwaitingSink, Has<AudioSink>)>,
+) {
+    if *device != AudioDevice::Unknown {
+        return;
+    }
+
+    for (entity, mut awaiting, has_sink) in awaiting_query.iter_mut() {
+        if has_sink {
+            *device = AudioDevice::Available;
+            commands.entity(entity).remove::<AwaitingSink>();
+            continue;
+        }
+
+        awaiting.frames += 1;
+        if awaiting.frames > DEVICE_WAIT_FRAMES {
+            *device = AudioDevice::Missing;
+        }
+    }
+
+    match *device {
+        AudioDevice::Missing => {
+            info!("nenhum dispositivo de áudio; o jogo segue sem som");
+            for (entity, _, _) in awaiting_query.iter() {
+                commands.entity(entity).despawn();
+            }
+            current.entity = None;
+        }
+        AudioDevice::Available => {
+            for (entity, _, _) in awaiting_query.iter() {
+                commands.entity(entity).remove::<AwaitingSink>();
+            }
+        }
+        AudioDevice::Unknown => {}
+    }
+}
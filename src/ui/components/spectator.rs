@@ -0,0 +1,347 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    arg_value,
+    api::{
+        client::ServerConnection,
+        protocol::{ClientMessage, ServerMessage, SpectatorView},
+    },
+    logic::{
+        cell::{Cell, Side},
+        engine::ShipPlacement,
+        shot::ShotOutcome,
+    },
+};
+
+use super::{
+    board::{start_match, BoardIndex, GameState, ShotGrid, ShotState, SLOT_SIZE, SLOT_SPACE_BETWEEN},
+    localization::Locale,
+    main_menu::AppState,
+    settings::GameSettings,
+    theme::Theme,
+};
+
+pub struct SpectatorPlugin;
+
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+
+/**
+ * modo espectador, ativado por --spectate <endereço> [--room <sala>] [--view FULL|0|1]:
+ * assiste uma sala do servidor dedicado (ou a transmissão do torneio) sem jogar.
+ * o tabuleiro do lugar da perspectiva fica embaixo; V troca a visão
+ */
+#[derive(Resource)]
+pub struct SpectatorSession {
+    pub address: String,
+    pub room: String,
+    pub view: SpectatorView,
+    pub connection: Option<ServerConnection>,
+    pub last_attempt: Option<Instant>,
+    pub names: [String; 2],
+    pub fleets: [Option<Vec<ShipPlacement>>; 2],
+    pub shots: Vec<(usize, usize, usize, ShotOutcome)>, // lugar que atirou, linha, coluna, resultado
+    pub turn: Option<usize>,
+    pub winner: Option<usize>,
+    pub dirty: bool, // o tabuleiro precisa ser repintado
+}
+
+impl SpectatorSession {
+    pub fn from_args() -> Option<Self> {
+        Some(SpectatorSession {
+            address: arg_value("--spectate")?,
+            room: arg_value("--room").unwrap_or_else(|| "principal".to_string()),
+            view: arg_value("--view")
+                .and_then(|value| SpectatorView::parse(&value).ok())
+                .unwrap_or(SpectatorView::Full),
+            connection: None,
+            last_attempt: None,
+            names: ["-".to_string(), "-".to_string()],
+            fleets: [None, None],
+            shots: Vec::new(),
+            turn: None,
+            winner: None,
+            dirty: true,
+        })
+    }
+
+    fn send(&mut self, message: &ClientMessage) {
+        let Some(connection) = self.connection.as_mut() else {
+            return;
+        };
+
+        if connection.send(message).is_err() {
+            self.connection = None;
+        }
+    }
+
+    fn sunk_by(&self, seat: usize) -> usize {
+        self.shots
+            .iter()
+            .filter(|(shooter, _, _, outcome)| *shooter == seat && *outcome == ShotOutcome::Sunk)
+            .count()
+    }
+}
+
+#[derive(Component)]
+struct SpectatorHud;
+
+impl Plugin for SpectatorPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(session) = SpectatorSession::from_args() else {
+            return;
+        };
+
+        app.insert_resource(session)
+            .add_systems(
+                OnEnter(AppState::InGame),
+                setup_spectator
+                    .after(start_match)
+                    .run_if(resource_exists::<SpectatorSession>), // some ao sair para o menu
+            )
+            .add_systems(
+                Update,
+                (
+                    keep_watching,
+                    receive_spectator_messages,
+                    switch_view_on_key,
+                    render_spectator_board,
+                    update_spectator_hud,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(resource_exists::<SpectatorSession>),
+            );
+    }
+}
+
+// espectador não clica no tabuleiro e o bot local fica desligado
+fn setup_spectator(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    settings: Res<GameSettings>,
+) {
+    game_state.is_player_turn = false;
+
+    let top = settings.rows() as f32 * (SLOT_SIZE + SLOT_SPACE_BETWEEN) / 2.0;
+
+    commands.spawn((
+        Text2d::new(""),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, top, 1.0)),
+        SpectatorHud,
+        StateScoped(AppState::InGame),
+    ));
+}
+
+fn keep_watching(mut session: ResMut<SpectatorSession>) {
+    if session.connection.is_some() {
+        return;
+    }
+
+    if session
+        .last_attempt
+        .is_some_and(|attempt| attempt.elapsed() < RECONNECT_INTERVAL)
+    {
+        return;
+    }
+    session.last_attempt = Some(Instant::now());
+
+    let Ok(connection) = ServerConnection::connect(&session.address) else {
+        warn!("não foi possível conectar em {}", session.address);
+        return;
+    };
+
+    session.connection = Some(connection);
+
+    let message = ClientMessage::Spectate {
+        room: session.room.clone(),
+        view: session.view,
+    };
+    session.send(&message);
+}
+
+fn receive_spectator_messages(mut session: ResMut<SpectatorSession>) {
+    loop {
+        let message = match session.connection.as_ref().map(|connection| connection.try_recv()) {
+            Some(Ok(Some(message))) => message,
+            Some(Ok(None)) | None => return,
+            Some(Err(_)) => {
+                warn!("conexão com o servidor perdida");
+                session.connection = None;
+                return;
+            }
+        };
+
+        session.dirty = true;
+
+        match message {
+            // início de um retrato completo da sala: tudo o que vem depois substitui o estado atual
+            ServerMessage::Spectating { view } => {
+                session.view = view;
+                session.fleets = [None, None];
+                session.shots.clear();
+                session.turn = None;
+                session.winner = None;
+            }
+            ServerMessage::Players { names } => session.names = names,
+            ServerMessage::FleetOf { seat, placements } => session.fleets[seat] = Some(placements),
+            ServerMessage::Turn { seat } => session.turn = Some(seat),
+            ServerMessage::Shot {
+                seat,
+                row,
+                column,
+                outcome,
+            } => session.shots.push((seat, row, column, outcome)),
+            // os tiros de uma habilidade chegam depois como SHOT
+            ServerMessage::Ability { seat, ability, .. } => {
+                info!("{} usou {}", session.names[seat], ability.name());
+            }
+            ServerMessage::Sonar { seat, found, .. } => {
+                info!("sonar de {}: {}", session.names[seat], if found { "navio na área" } else { "área limpa" });
+            }
+            // a posição nova chega num FLEETOF quando a visão mostra a frota
+            ServerMessage::Moved { seat } => {
+                info!("{} andou com um navio", session.names[seat]);
+            }
+            ServerMessage::Timeout { seat } => {
+                info!("{} deixou o tempo esgotar", session.names[seat]);
+            }
+            ServerMessage::GameOver { winner } => {
+                session.winner = Some(winner);
+                session.turn = None;
+            }
+            ServerMessage::Error(error) => warn!("servidor: {error}"),
+            ServerMessage::Welcome { .. }
+            | ServerMessage::YourFleet(_)
+            | ServerMessage::FleetAccepted
+            | ServerMessage::Start { .. }
+            | ServerMessage::OpponentDisconnected
+            | ServerMessage::OpponentReconnected
+            | ServerMessage::Commit { .. }
+            | ServerMessage::Reveal { .. } => {}
+        }
+    }
+}
+
+// V alterna entre as duas frotas e a neblina de cada lugar
+fn switch_view_on_key(keyboard: Res<ButtonInput<KeyCode>>, mut session: ResMut<SpectatorSession>) {
+    if !keyboard.just_pressed(KeyCode::KeyV) {
+        return;
+    }
+
+    let view = session.view.next();
+    session.view = view;
+    session.dirty = true;
+    session.send(&ClientMessage::View(view));
+}
+
+fn render_spectator_board(
+    mut session: ResMut<SpectatorSession>,
+    settings: Res<GameSettings>,
+    theme: Res<Theme>,
+    mut shot_grid: ResMut<ShotGrid>,
+    index: Res<BoardIndex>,
+    mut cells_query: Query<(Entity, &Cell, &mut Sprite)>,
+) {
+    if !session.dirty {
+        return;
+    }
+    session.dirty = false;
+
+    let half = settings.rows() / 2;
+    let perspective = session.view.perspective();
+    let board_row = |seat: usize, row: usize| if seat == perspective { row } else { row + half };
+
+    // depois do fim de jogo as duas frotas ficam à mostra em qualquer visão
+    let mut ship_cells = HashSet::new();
+    for seat in 0..2 {
+        if session.winner.is_none() && !session.view.shows_fleet(seat) {
+            continue;
+        }
+
+        for placement in session.fleets[seat].iter().flatten() {
+            for (row, column) in placement.cells() {
+                ship_cells.insert((board_row(seat, row), column));
+            }
+        }
+    }
+
+    // o tiro de um lugar cai no tabuleiro do outro; embaixo fica o lado do jogador
+    *shot_grid = ShotGrid::new(half, settings.columns());
+    for (seat, row, column, outcome) in &session.shots {
+        let side = if *seat == perspective { Side::Enemy } else { Side::Player };
+        shot_grid.set(&side, *row, *column, (*outcome).into());
+    }
+
+    for (entity, cell, mut sprite) in cells_query.iter_mut() {
+        let position = (cell.row, cell.column);
+
+        sprite.color = if ship_cells.contains(&position) {
+            theme.palette().ship_cell
+        } else {
+            theme.palette().ground(cell)
+        };
+
+        let Some((side, row, column)) = index.position(entity) else {
+            continue;
+        };
+        let outcome = match shot_grid.get(&side, row, column) {
+            ShotState::Unknown => continue,
+            ShotState::Miss => ShotOutcome::Miss,
+            ShotState::Hit => ShotOutcome::Hit,
+            ShotState::Sunk => ShotOutcome::Sunk,
+        };
+        cell.show_outcome(&mut sprite, outcome, side.opponent(), theme.palette());
+    }
+}
+
+fn update_spectator_hud(
+    session: Res<SpectatorSession>,
+    mut hud_query: Query<&mut Text2d, With<SpectatorHud>>,
+    locale: Res<Locale>,
+) {
+    let Ok(mut text) = hud_query.get_single_mut() else {
+        return;
+    };
+
+    let perspective = session.view.perspective();
+    let names = &session.names;
+
+    let view = match session.view {
+        SpectatorView::Full => locale.get("spectator-full-view"),
+        SpectatorView::Seat(seat) => locale.format("spectator-seat-view", &[("name", &names[seat])]),
+    };
+
+    let status = match (session.winner, session.turn) {
+        (Some(winner), _) => locale.format("spectator-winner", &[("name", &names[winner])]),
+        (None, Some(turn)) => locale.format("spectator-turn", &[("name", &names[turn])]),
+        (None, None) if session.connection.is_none() => locale.get("spectator-connecting"),
+        (None, None) => locale.get("spectator-waiting"),
+    };
+
+    let hud = format!(
+        "{}\n{}",
+        locale.format("spectator-status", &[("view", &view), ("status", &status)]),
+        locale.format(
+            "spectator-boards",
+            &[
+                ("bottom", &names[perspective]),
+                ("bottom-sunk", &session.sunk_by(perspective)),
+                ("top", &names[1 - perspective]),
+                ("top-sunk", &session.sunk_by(1 - perspective)),
+            ],
+        ),
+    );
+
+    if text.0 != hud {
+        text.0 = hud;
+    }
+}
@@ -0,0 +1,217 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    logic::{
+        cell::{Cell, Side},
+        shot::ShotOutcome,
+        terrain::Terrain,
+    },
+    ui::components::board::ShotState,
+};
+
+pub const THEMES_PATH: &str = "assets/themes.txt";
+
+// cópia embutida para o jogo abrir mesmo sem a pasta assets ao lado
+const BUILTIN_THEMES: &str = include_str!("../../../assets/themes.txt");
+
+pub const DEFAULT_PALETTE: &str = "classic";
+
+#[derive(Clone, Debug)]
+pub struct Palette {
+    pub name: String,
+    pub label: String,
+    pub background: Color,
+    pub player_cell: Color,
+    pub enemy_cell: Color,
+    pub player_hit: Color, // acerto do jogador no tabuleiro inimigo
+    pub enemy_hit: Color,  // acerto do adversário no tabuleiro do jogador
+    pub miss: Color,
+    pub hit_marker: Color, // cor do X
+    pub miss_marker: Color, // cor do ponto
+    pub highlight: Color,  // células sob o navio sendo arrastado
+    pub valid: Color,      // células onde o navio foi solto
+    pub ship_cell: Color,  // navios na visão do espectador
+    pub island: Color,
+    pub mine: Color,
+    pub missed_ship: Color, // navios inimigos que sobraram, revelados no fim da partida
+    pub panel_border: Color,
+    pub panel_background: Color,
+}
+
+impl Palette {
+    // paleta sem cores definidas, preenchida linha a linha pelo arquivo
+    fn empty(name: &str, label: &str) -> Self {
+        Palette {
+            name: name.to_string(),
+            label: label.to_string(),
+            background: Color::BLACK,
+            player_cell: Color::BLACK,
+            enemy_cell: Color::BLACK,
+            player_hit: Color::BLACK,
+            enemy_hit: Color::BLACK,
+            miss: Color::BLACK,
+            hit_marker: Color::BLACK,
+            miss_marker: Color::BLACK,
+            highlight: Color::BLACK,
+            valid: Color::BLACK,
+            ship_cell: Color::BLACK,
+            island: Color::BLACK,
+            mine: Color::BLACK,
+            missed_ship: Color::BLACK,
+            panel_border: Color::BLACK,
+            panel_background: Color::BLACK,
+        }
+    }
+
+    fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+            "background" => &mut self.background,
+            "player_cell" => &mut self.player_cell,
+            "enemy_cell" => &mut self.enemy_cell,
+            "player_hit" => &mut self.player_hit,
+            "enemy_hit" => &mut self.enemy_hit,
+            "miss" => &mut self.miss,
+            "hit_marker" => &mut self.hit_marker,
+            "miss_marker" => &mut self.miss_marker,
+            "highlight" => &mut self.highlight,
+            "valid" => &mut self.valid,
+            "ship_cell" => &mut self.ship_cell,
+            "island" => &mut self.island,
+            "mine" => &mut self.mine,
+            "missed_ship" => &mut self.missed_ship,
+            "panel_border" => &mut self.panel_border,
+            "panel_background" => &mut self.panel_background,
+            _ => return None,
+        })
+    }
+
+    // cor da célula ainda sem tiro
+    pub fn cell(&self, side: &Side) -> Color {
+        match side {
+            Side::Player => self.player_cell,
+            Side::Enemy => self.enemy_cell,
+        }
+    }
+
+    // cor de uma célula sem tiro: ilhas e minas por cima da cor do lado
+    pub fn ground(&self, cell: &Cell) -> Color {
+        match cell.terrain {
+            Terrain::Water => self.cell(&cell.side),
+            Terrain::Island => self.island,
+            Terrain::Mine => self.mine,
+        }
+    }
+
+    // cor da célula depois do tiro
+    pub fn outcome(&self, outcome: ShotOutcome, shooter: Side) -> Color {
+        match (outcome, shooter) {
+            (ShotOutcome::Miss, _) => self.miss,
+            (_, Side::Player) => self.player_hit,
+            (_, Side::Enemy) => self.enemy_hit,
+        }
+    }
+
+    // símbolo desenhado por cima da célula, para não depender só da cor.
+    // a fonte padrão só tem ascii, então o ponto do tiro na água é um "o"
+    pub fn marker(&self, state: ShotState) -> Option<(&'static str, Color)> {
+        match state {
+            ShotState::Unknown => None,
+            ShotState::Miss => Some(("o", self.miss_marker)),
+            ShotState::Hit | ShotState::Sunk => Some(("X", self.hit_marker)),
+        }
+    }
+}
+
+/**
+ * paletas carregadas de assets/themes.txt e a escolhida nas configurações.
+ * quem pinta alguma coisa do tabuleiro ou do painel lê a cor daqui
+ */
+#[derive(Resource, Debug)]
+pub struct Theme {
+    pub palettes: Vec<Palette>,
+    pub active: usize,
+}
+
+impl Theme {
+    pub fn palette(&self) -> &Palette {
+        &self.palettes[self.active]
+    }
+
+    // nome desconhecido (arquivo editado, por exemplo) mantém a paleta atual
+    pub fn select(&mut self, name: &str) {
+        if let Some(index) = self.palettes.iter().position(|palette| palette.name == name) {
+            self.active = index;
+        }
+    }
+
+    pub fn label(&self, name: &str) -> String {
+        self.palettes
+            .iter()
+            .find(|palette| palette.name == name)
+            .map_or(name.to_string(), |palette| palette.label.clone())
+    }
+
+    // próxima paleta do arquivo, para o botão das configurações
+    pub fn next_name(&self, name: &str) -> String {
+        let index = self
+            .palettes
+            .iter()
+            .position(|palette| palette.name == name)
+            .map_or(0, |index| (index + 1) % self.palettes.len());
+
+        self.palettes[index].name.clone()
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut palettes: Vec<Palette> = Vec::new();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = value.trim();
+
+            if key == "palette" {
+                let (name, label) = value.split_once(' ').unwrap_or((value, value));
+                if name.is_empty() {
+                    return Err(format!("linha inválida: {line}"));
+                }
+                palettes.push(Palette::empty(name, label.trim()));
+                continue;
+            }
+
+            let palette = palettes
+                .last_mut()
+                .ok_or_else(|| format!("linha fora de uma paleta: {line}"))?;
+            let color = palette
+                .color_mut(key)
+                .ok_or_else(|| format!("cor desconhecida: {line}"))?;
+            *color = Srgba::hex(value)
+                .map_err(|_| format!("cor inválida: {line}"))?
+                .into();
+        }
+
+        if palettes.is_empty() {
+            return Err("nenhuma paleta".to_string());
+        }
+
+        let mut theme = Theme { palettes, active: 0 };
+        theme.select(DEFAULT_PALETTE);
+        Ok(theme)
+    }
+
+    // arquivo ausente ou estragado cai nas paletas embutidas
+    pub fn load() -> Self {
+        let text = fs::read_to_string(THEMES_PATH).unwrap_or_else(|_| BUILTIN_THEMES.to_string());
+
+        Theme::parse(&text).unwrap_or_else(|error| {
+            warn!("temas inválidos em {THEMES_PATH}: {error}");
+            Theme::parse(BUILTIN_THEMES).expect("paletas embutidas válidas")
+        })
+    }
+}
@@ -0,0 +1,198 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use naval_battle_game_interface::{
+    logic::{cell::Side, shot::ShotOutcome},
+    ui::components::{
+        board::{
+            index_ships, move_ship, BoardIndex, GameState, ShotGrid, ShotLog, ShotState, SLOT_SIZE,
+            SLOT_SPACE_BETWEEN,
+        },
+        settings::RuleVariant,
+        ships::{Fleet, HiddenFleet, Ship, ShipDirection, ShipType},
+    },
+};
+
+const STEP: f32 = SLOT_SIZE + SLOT_SPACE_BETWEEN;
+
+// tabuleiro inteiro de 4 linhas por 3 colunas, com uma entidade por célula como o render_board monta
+fn board(world: &mut World) -> BoardIndex {
+    let mut index = BoardIndex::new(4, 3);
+    for _ in 0..4 * 3 {
+        index.push(world.spawn_empty().id());
+    }
+    index
+}
+
+fn ship(world: &mut World, side: Side, cells: Vec<Entity>) -> Entity {
+    world
+        .spawn((
+            Ship {
+                r#type: ShipType::Corvette,
+                side,
+                cells,
+                sunk: false,
+                ability_used: false,
+            },
+            ShipDirection::Horizontal,
+            Transform::default(),
+        ))
+        .id()
+}
+
+#[test]
+fn points_on_the_cell_border_hit_and_the_gap_misses() {
+    let mut world = World::new();
+    let index = board(&mut world);
+    let center = index.center(1, 2);
+
+    assert_eq!(index.cell_at_point(center), index.at(1, 2));
+    assert_eq!(index.cell_at_point(center + Vec2::new(-SLOT_SIZE / 2.0, SLOT_SIZE / 2.0)), index.at(1, 2));
+
+    // no espaço entre duas células não há célula, mas a mais perto continua sendo a mesma
+    let gap = center + Vec2::new(0.0, SLOT_SIZE / 2.0 + SLOT_SPACE_BETWEEN / 4.0);
+    assert_eq!(index.cell_at_point(gap), None);
+    assert_eq!(index.nearest(gap), Some((1, 2)));
+
+    // passando da metade do passo, a mais perto já é a de cima
+    assert_eq!(index.nearest(center + Vec2::new(0.0, STEP * 0.6)), Some((2, 2)));
+}
+
+#[test]
+fn points_outside_the_board_have_no_cell() {
+    let mut world = World::new();
+    let index = board(&mut world);
+
+    for point in [
+        index.center(0, 0) - Vec2::new(STEP, 0.0),
+        index.center(0, 0) - Vec2::new(0.0, STEP),
+        index.center(3, 2) + Vec2::new(STEP, 0.0),
+        index.center(3, 2) + Vec2::new(0.0, STEP),
+    ] {
+        assert_eq!(index.nearest(point), None, "{point}");
+        assert_eq!(index.cell_at_point(point), None, "{point}");
+    }
+
+    assert_eq!(index.at(4, 0), None);
+    assert_eq!(index.at(0, 3), None);
+    assert_eq!(index.cell(&Side::Player, 2, 0), None);
+    assert_eq!(index.cell(&Side::Enemy, 1, 2), index.at(3, 2));
+    assert_eq!(index.position(index.at(3, 2).unwrap()), Some((Side::Enemy, 1, 2)));
+}
+
+#[test]
+fn shaped_ships_occupy_every_cell() {
+    let mut world = World::new();
+    let index = board(&mut world);
+
+    // um L no lado inimigo, em células de duas linhas
+    let cells: Vec<Entity> = [(2, 0), (3, 0), (3, 1)]
+        .into_iter()
+        .filter_map(|(row, column)| index.at(row, column))
+        .collect();
+    let corvette = ship(&mut world, Side::Enemy, cells.clone());
+
+    world.insert_resource(index);
+    world.run_system_once(index_ships).unwrap();
+    let index = world.resource::<BoardIndex>();
+
+    for cell in &cells {
+        assert_eq!(index.ship_at(*cell), Some(corvette));
+    }
+    assert_eq!(index.ship_at(index.at(2, 1).unwrap()), None);
+    assert_eq!(index.ship_at(index.at(0, 0).unwrap()), None);
+}
+
+#[test]
+fn moved_ships_are_indexed_in_the_new_cells() {
+    let mut world = World::new();
+    let index = board(&mut world);
+    let from: Vec<Entity> = [(0, 0), (0, 1)].into_iter().filter_map(|(row, column)| index.at(row, column)).collect();
+    let to: Vec<Entity> = [(1, 0), (1, 1)].into_iter().filter_map(|(row, column)| index.at(row, column)).collect();
+    let destroyer = ship(&mut world, Side::Player, from.clone());
+
+    world.insert_resource(index);
+    world.insert_resource(GameState::new());
+    world.insert_resource(ShotLog::default());
+    world.insert_resource(HiddenFleet::default());
+    world.run_system_once(index_ships).unwrap();
+
+    let cells = to.clone();
+    world
+        .run_system_once(
+            move |mut fleet: Fleet,
+                  mut motion_query: Query<(&mut Transform, &ShipDirection), With<Ship>>,
+                  mut game_state: ResMut<GameState>,
+                  mut index: ResMut<BoardIndex>,
+                  mut shot_log: ResMut<ShotLog>| {
+                move_ship(
+                    destroyer,
+                    (cells.clone(), Vec2::new(0.0, STEP)),
+                    &mut fleet,
+                    &mut motion_query,
+                    &mut game_state,
+                    &mut index,
+                    &mut shot_log,
+                );
+            },
+        )
+        .unwrap();
+
+    let index = world.resource::<BoardIndex>();
+    for (old, new) in from.iter().zip(&to) {
+        assert_eq!(index.ship_at(*old), None);
+        assert_eq!(index.ship_at(*new), Some(destroyer));
+    }
+    assert_eq!(world.get::<Ship>(destroyer).unwrap().cells, to);
+    assert_eq!(world.get::<Transform>(destroyer).unwrap().translation, Vec3::new(0.0, STEP, 0.0));
+    assert_eq!(world.resource::<ShotLog>().moves[0].step, (1, 0));
+    assert_eq!(world.resource::<GameState>().ship_moved, Some(Side::Player));
+}
+
+#[test]
+fn shot_grid_marks_each_side_apart() {
+    let mut grid = ShotGrid::new(2, 3);
+
+    grid.set(&Side::Enemy, 1, 2, ShotOutcome::Hit.into());
+    assert_eq!(grid.get(&Side::Enemy, 1, 2), ShotState::Hit);
+    assert!(grid.is_known(&Side::Enemy, 1, 2));
+    assert!(!grid.is_known(&Side::Player, 1, 2));
+    assert_eq!(grid.count(&Side::Enemy, ShotState::Hit), 1);
+    assert_eq!(grid.unknown(&Side::Enemy).count(), 5);
+    assert_eq!(grid.unknown(&Side::Player).count(), 6);
+}
+
+#[test]
+fn repeated_shots_are_refused() {
+    let mut grid = ShotGrid::new(2, 3);
+    let mut game_state = GameState::new();
+
+    assert!(game_state.can_fire(&grid, &Side::Enemy, 0, 0));
+    grid.set(&Side::Enemy, 0, 0, ShotState::Miss);
+    grid.set(&Side::Enemy, 0, 1, ShotState::Sunk);
+    assert!(!game_state.can_fire(&grid, &Side::Enemy, 0, 0));
+    assert!(!game_state.can_fire(&grid, &Side::Enemy, 0, 1));
+    assert!(game_state.can_fire(&grid, &Side::Player, 0, 0));
+
+    // com navios em movimento o tiro na água pode ser repetido, o acerto não
+    game_state.variant = RuleVariant::Moving;
+    assert!(game_state.can_fire(&grid, &Side::Enemy, 0, 0));
+    assert!(!game_state.can_fire(&grid, &Side::Enemy, 0, 1));
+}
+
+#[test]
+fn shots_outside_a_side_are_ignored() {
+    let mut grid = ShotGrid::new(2, 3);
+    let game_state = GameState::new();
+
+    for side in [Side::Player, Side::Enemy] {
+        // a linha 2 já seria o outro lado no tabuleiro inteiro: não vaza para ele
+        grid.set(&side, 2, 0, ShotState::Hit);
+        grid.set(&side, 0, 3, ShotState::Hit);
+
+        assert_eq!(grid.get(&side, 2, 0), ShotState::Unknown);
+        assert_eq!(grid.get(&side, 0, 3), ShotState::Unknown);
+        assert!(!game_state.can_fire(&grid, &side, 2, 0));
+        assert!(!game_state.can_fire(&grid, &side, 0, 3));
+    }
+    assert_eq!(grid.unknown(&Side::Player).count(), 6);
+    assert_eq!(grid.unknown(&Side::Enemy).count(), 6);
+}
@@ -0,0 +1,95 @@
+use naval_battle_game_interface::logic::{
+    commitment::{audit, random_salt, FleetCommitment, FleetLayout, FleetReveal, PlacedShip, ReportedShot,
+        ReportedSonar},
+    engine::Rules,
+    shot::ShotOutcome,
+};
+
+// frota padrão, um navio por linha a partir da coluna 0
+fn layout() -> FleetLayout {
+    let ships = [("AircraftCarrier", 5), ("LargeBattleship", 4), ("Battleship", 3), ("Destroyer", 2), ("Submarine", 1)];
+
+    FleetLayout {
+        ships: ships
+            .iter()
+            .enumerate()
+            .map(|(row, (name, length))| PlacedShip {
+                name: name.to_string(),
+                cells: (0..*length).map(|column| (row, column)).collect(),
+            })
+            .collect(),
+    }
+}
+
+fn reveal(layout: FleetLayout) -> (FleetCommitment, FleetReveal) {
+    let reveal = FleetReveal {
+        layout,
+        salt: random_salt(),
+    };
+    (FleetCommitment::new(&reveal.layout, &reveal.salt), reveal)
+}
+
+fn shot(row: usize, column: usize, reported: ShotOutcome) -> ReportedShot {
+    ReportedShot { row, column, reported }
+}
+
+#[test]
+fn honest_answers_pass_the_audit() {
+    let (commitment, reveal) = reveal(layout());
+    let shots = [shot(4, 0, ShotOutcome::Sunk), shot(4, 5, ShotOutcome::Miss), shot(3, 0, ShotOutcome::Hit)];
+
+    assert!(audit(&Rules::default(), &commitment, &reveal, &shots, &[]).is_honest());
+}
+
+#[test]
+fn a_hit_reported_as_miss_is_a_lie() {
+    let (commitment, reveal) = reveal(layout());
+    let report = audit(&Rules::default(), &commitment, &reveal, &[shot(0, 2, ShotOutcome::Miss)], &[]);
+
+    assert_eq!(report.lies.len(), 1);
+    assert_eq!(report.lies[0].actual, ShotOutcome::Hit);
+}
+
+#[test]
+fn sonar_answers_are_checked_against_the_shots_before_them() {
+    let (commitment, reveal) = reveal(layout());
+    // depois do primeiro disparo o submarino em (4,0) não conta mais, mas a área ainda tem o contratorpedeiro
+    let shots = [shot(4, 0, ShotOutcome::Sunk), shot(2, 8, ShotOutcome::Miss)];
+    let sonar = |after, row, column, found| ReportedSonar { after, row, column, found };
+    let sonars = [sonar(0, 4, 0, true), sonar(1, 4, 0, true), sonar(2, 1, 8, false), sonar(2, 3, 3, false)];
+
+    let report = audit(&Rules::default(), &commitment, &reveal, &shots, &sonars);
+    assert!(report.lies.is_empty());
+    // o último sonar nega o encouraçado em (2,2)
+    assert_eq!(report.sonar_lies, vec![3]);
+    assert!(!report.is_honest());
+}
+
+#[test]
+fn revealed_fleet_must_follow_the_rules() {
+    // sem o submarino o adversário nunca precisaria admitir o último afundado
+    let mut short = layout();
+    short.ships.pop();
+    let (commitment, reveal) = reveal(short);
+
+    let report = audit(&Rules::default(), &commitment, &reveal, &[], &[]);
+    assert!(report.commitment_valid);
+    assert!(!report.fleet_valid);
+    assert!(!report.is_honest());
+}
+
+#[test]
+fn reveal_with_another_salt_breaks_the_commitment() {
+    let (commitment, mut reveal) = reveal(layout());
+    reveal.salt = random_salt();
+
+    assert!(!audit(&Rules::default(), &commitment, &reveal, &[], &[]).commitment_valid);
+}
+
+#[test]
+fn canonical_layout_parses_back() {
+    let layout = layout();
+    let parsed = FleetLayout::parse(&layout.canonical()).unwrap();
+
+    assert_eq!(parsed.canonical(), layout.canonical());
+}
@@ -0,0 +1,239 @@
+use naval_battle_game_interface::logic::{
+    ability::Ability,
+    engine::{moved_cells, random_fleet, Match, RuleError, Rules, ShipPlacement},
+    shot::ShotOutcome,
+    terrain::{Terrain, TerrainMap},
+};
+
+// lado de 3x4 com um contratorpedeiro e um submarino
+fn rules() -> Rules {
+    Rules::parse("board 3 4\nship Destroyer 2\nship Submarine 1").unwrap()
+}
+
+fn ship(name: &str, row: usize, column: usize, length: usize, vertical: bool) -> ShipPlacement {
+    ShipPlacement {
+        name: name.to_string(),
+        row,
+        column,
+        length,
+        vertical,
+        shape: None,
+    }
+}
+
+fn fleet() -> Vec<ShipPlacement> {
+    vec![ship("Destroyer", 0, 0, 2, false), ship("Submarine", 2, 3, 1, false)]
+}
+
+fn started() -> Match {
+    let mut game = Match::new(rules());
+    game.place_fleet(0, &fleet()).unwrap();
+    game.place_fleet(1, &fleet()).unwrap();
+    game
+}
+
+#[test]
+fn fleet_must_match_the_rules() {
+    let mut game = Match::new(rules());
+
+    assert_eq!(game.place_fleet(0, &fleet()[..1]), Err(RuleError::WrongFleet));
+    assert_eq!(
+        game.place_fleet(0, &[ship("Destroyer", 0, 3, 2, false), ship("Submarine", 2, 0, 1, false)]),
+        Err(RuleError::OutOfBounds)
+    );
+    assert_eq!(
+        game.place_fleet(0, &[ship("Destroyer", 0, 0, 2, true), ship("Submarine", 1, 0, 1, false)]),
+        Err(RuleError::Overlap)
+    );
+
+    game.place_fleet(0, &fleet()).unwrap();
+    assert_eq!(game.place_fleet(0, &fleet()), Err(RuleError::FleetAlreadyPlaced));
+    assert!(!game.is_started());
+}
+
+#[test]
+fn fire_needs_both_fleets_and_the_turn() {
+    let mut game = Match::new(rules());
+    game.place_fleet(0, &fleet()).unwrap();
+    assert_eq!(game.fire(0, 0, 0), Err(RuleError::NotStarted));
+
+    let mut game = started();
+    assert_eq!(game.fire(1, 0, 0), Err(RuleError::NotYourTurn));
+    assert_eq!(game.fire(0, 3, 0), Err(RuleError::OutOfBounds));
+
+    assert_eq!(game.fire(0, 1, 1), Ok(ShotOutcome::Miss));
+    assert_eq!(game.turn, 1);
+    assert_eq!(game.fire(1, 1, 1), Ok(ShotOutcome::Miss));
+    assert_eq!(game.fire(0, 1, 1), Err(RuleError::AlreadyFired));
+}
+
+#[test]
+fn sinking_the_whole_fleet_wins() {
+    let mut game = started();
+    let shots = [(0, 0), (0, 1), (2, 3)];
+    let mut outcomes = Vec::new();
+
+    for (row, column) in shots {
+        outcomes.push(game.fire(0, row, column).unwrap());
+        if game.winner.is_none() {
+            game.fire(1, 1, column).unwrap();
+        }
+    }
+
+    assert_eq!(outcomes, [ShotOutcome::Hit, ShotOutcome::Sunk, ShotOutcome::Sunk]);
+    assert_eq!(game.winner, Some(0));
+    assert_eq!(game.sunk_ships(1), 2);
+    assert_eq!(game.fire(1, 2, 2), Err(RuleError::GameOver));
+}
+
+#[test]
+fn forfeit_gives_the_win_to_the_other_side() {
+    let mut game = started();
+    game.forfeit(0);
+    assert_eq!(game.winner, Some(1));
+
+    // o primeiro resultado vale
+    game.forfeit(1);
+    assert_eq!(game.winner, Some(1));
+}
+
+#[test]
+fn torpedo_only_hits_where_it_stops() {
+    let area = Ability::Torpedo.area(2, 0, 3, 4);
+    let occupied = |cell: &(usize, usize)| *cell == (2, 3);
+
+    assert_eq!(Ability::Torpedo.strikes(&area, |_| false, occupied), vec![(2, 3)]);
+    // sem navio na linha ele explode na última célula livre
+    assert_eq!(Ability::Torpedo.strikes(&area, |cell| cell.1 == 3, occupied), vec![(2, 2)]);
+}
+
+#[test]
+fn rules_reject_a_fleet_that_cannot_fit() {
+    assert!(Rules::parse("board 3 4\nship AircraftCarrier 5").is_err());
+    // 2x2 de água: o contratorpedeiro cabe, mas não sobram células para o submarino
+    assert!(Rules::parse("board 2 2\nship Destroyer 2\nship Destroyer 2\nship Submarine 1").is_err());
+    assert!(Rules::parse("board 2 2\nship Destroyer 2\nship Submarine 1\nmap oo").is_err());
+}
+
+#[test]
+fn random_fleet_gives_up_instead_of_hanging() {
+    // a cruz ocupa o meio de toda linha e coluna, então o navio de 3 nunca encaixa
+    let rules = Rules::parse("board 3 3\nship Cross 0,1;1,0;1,1;1,2;2,1\nship Battleship 3").unwrap();
+
+    assert!(random_fleet(&rules, &mut rand::thread_rng()).is_err());
+    assert!(random_fleet(&Rules::default(), &mut rand::thread_rng()).is_ok());
+}
+
+// as regras de rules() com uma mina em (0,3) e uma ilha em (1,0), fora da frota de fleet()
+fn terrain_rules() -> Rules {
+    Rules::parse("board 3 4\nship Destroyer 2\nship Submarine 1\nmap ...x\nmap o...").unwrap()
+}
+
+fn started_on_terrain() -> Match {
+    let mut game = Match::new(terrain_rules());
+    game.place_fleet(0, &fleet()).unwrap();
+    game.place_fleet(1, &fleet()).unwrap();
+    game
+}
+
+#[test]
+fn terrain_map_parses_islands_and_mines() {
+    let map = TerrainMap::parse("# canto do mapa\nmap .o\n\nmap x.").unwrap();
+
+    assert_eq!(map.get(0, 1), Terrain::Island);
+    assert_eq!(map.get(1, 0), Terrain::Mine);
+    assert_eq!(map.get(0, 0), Terrain::Water);
+    // fora do mapa é tudo água
+    assert!(map.is_water(5, 5));
+    assert_eq!(map.to_string(), "map .o\nmap x.\n");
+
+    assert!(TerrainMap::parse("map .z").is_err());
+    assert!(TerrainMap::parse("ship Submarine 1").is_err());
+    assert!(TerrainMap::parse("").unwrap().is_empty());
+}
+
+#[test]
+fn ships_and_shots_stay_off_islands() {
+    let mut game = Match::new(terrain_rules());
+    assert_eq!(
+        game.place_fleet(0, &[ship("Destroyer", 0, 0, 2, true), ship("Submarine", 2, 3, 1, false)]),
+        Err(RuleError::OnTerrain)
+    );
+    assert_eq!(
+        game.place_fleet(0, &[ship("Destroyer", 0, 2, 2, false), ship("Submarine", 2, 3, 1, false)]),
+        Err(RuleError::OnTerrain)
+    );
+
+    let mut game = started_on_terrain();
+    assert_eq!(game.fire(0, 1, 0), Err(RuleError::Island));
+    assert_eq!(game.turn, 0);
+    assert!(game.shots_by(0).is_empty());
+}
+
+#[test]
+fn a_mine_costs_the_next_turn() {
+    let mut game = started_on_terrain();
+
+    assert_eq!(game.fire(0, 0, 3), Ok(ShotOutcome::Miss));
+    assert_eq!(game.turn, 1);
+
+    // o lado 0 perde a vez seguinte e o lado 1 atira duas vezes
+    game.fire(1, 1, 1).unwrap();
+    assert_eq!(game.turn, 1);
+    game.fire(1, 1, 2).unwrap();
+    assert_eq!(game.turn, 0);
+
+    assert_eq!(game.fire(0, 0, 3), Err(RuleError::AlreadyFired));
+}
+
+// as regras de rules() com navios em movimento
+fn moving(placements: &[ShipPlacement]) -> Match {
+    let mut game = Match::new(Rules::parse("board 3 4\nship Destroyer 2\nship Submarine 1\nmoving").unwrap());
+    game.place_fleet(0, placements).unwrap();
+    game.place_fleet(1, &fleet()).unwrap();
+    game
+}
+
+#[test]
+fn ships_move_along_their_axis_inside_the_board() {
+    // o contratorpedeiro não volta da coluna 0 e o submarino não passa da última
+    let mut game = moving(&fleet());
+    assert_eq!(game.moves(0), vec![(0, true), (1, false)]);
+
+    assert_eq!(game.move_ship(0, 1, true), Err(RuleError::CannotMove));
+    game.move_ship(0, 0, true).unwrap();
+    assert_eq!(game.turn, 1);
+    assert_eq!(game.ship_at(0, 0, 0), None);
+    assert_eq!(game.ship_at(0, 0, 2), Some(("Destroyer", 2)));
+    assert_eq!(game.move_ship(0, 0, true), Err(RuleError::NotYourTurn));
+
+    // sem a linha "moving" ninguém anda
+    assert!(started().moves(0).is_empty());
+}
+
+#[test]
+fn blocked_and_damaged_ships_stay_put() {
+    // o submarino em (0,2) fecha a frente do contratorpedeiro
+    let mut game = moving(&[ship("Destroyer", 0, 0, 2, false), ship("Submarine", 0, 2, 1, false)]);
+    assert_eq!(game.moves(0), vec![(1, true)]);
+    assert_eq!(game.move_ship(0, 0, true), Err(RuleError::CannotMove));
+
+    // navio atingido não anda mais
+    let mut game = moving(&fleet());
+    game.fire(0, 1, 1).unwrap();
+    assert_eq!(game.fire(1, 0, 1), Ok(ShotOutcome::Hit));
+    assert_eq!(game.moves(0), vec![(1, false)]);
+}
+
+#[test]
+fn moved_cells_follow_the_longer_axis() {
+    let vertical = [(1, 2), (2, 2)];
+    assert_eq!(moved_cells(&vertical, false, true), Some(vec![(2, 2), (3, 2)]));
+    assert_eq!(moved_cells(&vertical, false, false), Some(vec![(0, 2), (1, 2)]));
+    assert_eq!(moved_cells(&[(0, 0), (1, 0)], true, false), None);
+
+    // um navio de uma célula anda no eixo do posicionamento
+    assert_eq!(moved_cells(&[(0, 3)], false, true), Some(vec![(0, 4)]));
+    assert_eq!(moved_cells(&[(0, 3)], true, true), Some(vec![(1, 3)]));
+    assert_eq!(moved_cells(&[(2, 0)], false, false), None);
+}
@@ -0,0 +1,37 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use naval_battle_game_interface::{api::external_bot::ExternalBot, logic::bots::BotPlayer};
+
+#[test]
+fn poll_fire_returns_while_the_engine_is_thinking() {
+    // motor que nunca responde
+    let mut bot = ExternalBot::spawn("sleep 30", Duration::from_millis(300)).unwrap();
+
+    let started = Instant::now();
+    assert!(bot.poll_fire().is_none());
+    assert!(bot.is_thinking());
+    assert!(bot.poll_fire().is_none());
+    assert!(started.elapsed() < Duration::from_millis(100));
+
+    thread::sleep(Duration::from_millis(350));
+    let reply = bot.poll_fire().expect("tempo esgotado vira resposta");
+    assert!(reply.unwrap_err().contains("tempo esgotado"));
+    assert!(!bot.is_thinking());
+}
+
+#[test]
+fn poll_fire_reads_the_engine_reply() {
+    // `cat` devolve o próprio pedido, que não é um tiro válido
+    let mut bot = ExternalBot::spawn("cat", Duration::from_secs(5)).unwrap();
+
+    let reply = loop {
+        if let Some(reply) = bot.poll_fire() {
+            break reply;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    assert_eq!(reply, Err("tiro inválido: fire".to_string()));
+}
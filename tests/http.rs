@@ -0,0 +1,143 @@
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+};
+
+use naval_battle_game_interface::{api::http::HttpApi, logic::engine::Rules};
+
+fn api() -> SocketAddr {
+    api_with(Rules::default())
+}
+
+fn api_with(rules: Rules) -> SocketAddr {
+    HttpApi::bind("127.0.0.1:0", rules).unwrap().spawn().unwrap()
+}
+
+// manda a requisição crua e devolve o status e o corpo da resposta
+fn send(addr: SocketAddr, request: &str) -> (u16, String) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(request.as_bytes()).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let status = response.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let body = response.split_once("\r\n\r\n").map_or("", |(_, body)| body).to_string();
+    (status, body)
+}
+
+fn post(addr: SocketAddr, path: &str, token: &str, body: &str) -> (u16, String) {
+    send(
+        addr,
+        &format!(
+            "POST {path} HTTP/1.1\r\nAuthorization: Bearer {token}\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        ),
+    )
+}
+
+fn create_game(addr: SocketAddr) -> (String, String) {
+    create_game_with(addr, "")
+}
+
+fn create_game_with(addr: SocketAddr, body: &str) -> (String, String) {
+    let (status, body) = post(addr, "/games", "", body);
+    assert_eq!(status, 201);
+
+    let field = |name: &str| {
+        let start = body.find(&format!("\"{name}\":\"")).unwrap() + name.len() + 4;
+        body[start..].split('"').next().unwrap().to_string()
+    };
+    (field("id"), field("token"))
+}
+
+#[test]
+fn oversized_content_length_is_refused_before_reading_the_body() {
+    let (status, body) = send(api(), "POST /games HTTP/1.1\r\nContent-Length: 18446744073709551615\r\n\r\n");
+
+    assert_eq!(status, 413);
+    assert!(body.contains("error"));
+}
+
+#[test]
+fn unparsable_content_length_is_a_bad_request() {
+    let (status, _) = send(api(), "POST /games HTTP/1.1\r\nContent-Length: muito\r\n\r\n");
+    assert_eq!(status, 400);
+}
+
+#[test]
+fn oversized_headers_are_refused() {
+    let header = "x".repeat(20 * 1024);
+    let (status, _) = send(api(), &format!("GET /games HTTP/1.1\r\nX-Lixo: {header}\r\n\r\n"));
+    assert_eq!(status, 431);
+}
+
+#[test]
+fn events_need_the_game_token() {
+    let addr = api();
+    let (id, _) = create_game(addr);
+
+    let (status, _) = send(addr, &format!("GET /games/{id}/events HTTP/1.1\r\n\r\n"));
+    assert_eq!(status, 401);
+
+    let (status, _) = send(
+        addr,
+        &format!("GET /games/{id}/events HTTP/1.1\r\nAuthorization: Bearer outro\r\n\r\n"),
+    );
+    assert_eq!(status, 403);
+}
+
+#[test]
+fn events_stream_for_a_seated_player() {
+    let addr = api();
+    let (id, token) = create_game(addr);
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET /games/{id}/events HTTP/1.1\r\nAuthorization: Bearer {token}\r\n\r\n").unwrap();
+
+    let mut response = [0; 512];
+    let read = stream.read(&mut response).unwrap();
+    let response = String::from_utf8_lossy(&response[..read]);
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+}
+
+// lado 3x3 quase todo de ilhas: sobram (1,2), (2,1) e (2,2) para os submarinos
+#[test]
+fn bot_never_fires_at_islands_nor_keeps_the_turn() {
+    let rules = Rules::parse("board 3 3\nship Submarine 1\nmap ooo\nmap oo.\nmap o..").unwrap();
+    let addr = api_with(rules);
+    let (id, token) = create_game_with(addr, r#"{"opponent": "bot"}"#);
+
+    let ships = r#"{"ships": [{"name": "Submarine", "row": 1, "column": 2, "length": 1}]}"#;
+    let (status, _) = post(addr, &format!("/games/{id}/ships"), &token, ships);
+    assert_eq!(status, 200);
+
+    let (status, _) = post(addr, &format!("/games/{id}/fire"), &token, r#"{"row": 0, "column": 0}"#);
+    assert_eq!(status, 409);
+
+    for (row, column) in [(1, 2), (2, 1), (2, 2)] {
+        let (status, body) = post(
+            addr,
+            &format!("/games/{id}/fire"),
+            &token,
+            &format!(r#"{{"row": {row}, "column": {column}}}"#),
+        );
+        assert_eq!(status, 200, "{body}");
+
+        let result: serde_json::Value = serde_json::from_str(&body).unwrap();
+        if !result["winner"].is_null() {
+            break;
+        }
+        assert_eq!(result["turn"], 0, "o bot ficou com a vez: {body}");
+    }
+
+    let (_, board) = send(
+        addr,
+        &format!("GET /games/{id} HTTP/1.1\r\nAuthorization: Bearer {token}\r\n\r\n"),
+    );
+    let board: serde_json::Value = serde_json::from_str(&board).unwrap();
+    for shot in board["incoming_shots"].as_array().unwrap() {
+        let cell = (shot["row"].as_u64().unwrap(), shot["column"].as_u64().unwrap());
+        assert!([(1, 2), (2, 1), (2, 2)].contains(&cell), "tiro do bot na ilha {cell:?}");
+    }
+}
@@ -0,0 +1,16 @@
+use naval_battle_game_interface::ui::components::profiles::Profiles;
+
+#[test]
+fn names_with_spaces_survive_the_file() {
+    let mut profiles = Profiles::default();
+    profiles.select("Ana Maria");
+
+    let parsed = Profiles::parse(&profiles.to_text()).unwrap();
+    assert_eq!(parsed.active().name, "Ana Maria");
+    assert_eq!(parsed.profiles.len(), 2);
+}
+
+#[test]
+fn profile_line_without_name_is_rejected() {
+    assert!(Profiles::parse("profile   \n").is_err());
+}
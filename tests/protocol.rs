@@ -0,0 +1,136 @@
+use naval_battle_game_interface::{
+    api::protocol::{ClientMessage, ServerMessage, SpectatorView},
+    logic::{
+        ability::Ability,
+        commitment::{FleetCommitment, FleetLayout, FleetReveal, PlacedShip},
+        engine::ShipPlacement,
+        shape::Shape,
+        shot::ShotOutcome,
+    },
+};
+
+fn fleet() -> Vec<ShipPlacement> {
+    vec![
+        ShipPlacement {
+            name: "Destroyer".to_string(),
+            row: 1,
+            column: 2,
+            length: 2,
+            vertical: true,
+            shape: None,
+        },
+        ShipPlacement::from_shape("Corvette", 0, 5, &Shape::parse("0,0;1,0;2,0;2,1").unwrap()),
+    ]
+}
+
+fn reveal() -> FleetReveal {
+    FleetReveal {
+        layout: FleetLayout {
+            ships: vec![PlacedShip {
+                name: "Destroyer".to_string(),
+                cells: vec![(1, 2), (2, 2)],
+            }],
+        },
+        salt: [7; 16],
+    }
+}
+
+#[test]
+fn client_messages_round_trip() {
+    let messages = [
+        ClientMessage::Join {
+            room: "sala1".to_string(),
+            name: "ana".to_string(),
+        },
+        ClientMessage::Reconnect {
+            token: "abc123".to_string(),
+        },
+        ClientMessage::Fleet(fleet()),
+        ClientMessage::Fire { row: 4, column: 9 },
+        ClientMessage::Ability {
+            ability: Ability::Torpedo,
+            row: 2,
+            column: 0,
+        },
+        ClientMessage::Move { ship: 2, forward: false },
+        ClientMessage::Commit(FleetCommitment([3; 32])),
+        ClientMessage::Reveal(reveal()),
+        ClientMessage::Spectate {
+            room: "sala1".to_string(),
+            view: SpectatorView::Seat(1),
+        },
+        ClientMessage::View(SpectatorView::Full),
+        ClientMessage::Leave,
+    ];
+
+    for message in messages {
+        assert_eq!(ClientMessage::parse(&message.to_line()), Ok(message));
+    }
+}
+
+#[test]
+fn server_messages_round_trip() {
+    let messages = [
+        ServerMessage::Welcome {
+            seat: 1,
+            token: "abc123".to_string(),
+        },
+        ServerMessage::Spectating {
+            view: SpectatorView::Seat(0),
+        },
+        ServerMessage::Players {
+            names: ["ana".to_string(), "bia".to_string()],
+        },
+        ServerMessage::YourFleet(fleet()),
+        ServerMessage::FleetOf {
+            seat: 0,
+            placements: fleet(),
+        },
+        ServerMessage::FleetAccepted,
+        ServerMessage::Start { first: 0 },
+        ServerMessage::Turn { seat: 1 },
+        ServerMessage::Shot {
+            seat: 0,
+            row: 2,
+            column: 3,
+            outcome: ShotOutcome::Sunk,
+        },
+        ServerMessage::Ability {
+            seat: 1,
+            ability: Ability::Sonar,
+            row: 3,
+            column: 3,
+        },
+        ServerMessage::Sonar {
+            seat: 1,
+            row: 3,
+            column: 3,
+            found: true,
+        },
+        ServerMessage::Moved { seat: 0 },
+        ServerMessage::Timeout { seat: 1 },
+        ServerMessage::GameOver { winner: 0 },
+        ServerMessage::Commit {
+            seat: 1,
+            commitment: FleetCommitment([3; 32]),
+        },
+        ServerMessage::Reveal {
+            seat: 0,
+            reveal: reveal(),
+        },
+        ServerMessage::OpponentDisconnected,
+        ServerMessage::OpponentReconnected,
+        ServerMessage::Error("não é a sua vez".to_string()),
+    ];
+
+    for message in messages {
+        assert_eq!(ServerMessage::parse(&message.to_line()), Ok(message));
+    }
+}
+
+#[test]
+fn malformed_lines_are_rejected() {
+    for line in ["", "FIRE 1", "FIRE a b", "FLEET Destroyer:1,2", "VIEW 2", "COMMIT zz", "ABILITY laser 1 1", "MOVE 0 UP", "DANCE"] {
+        assert!(ClientMessage::parse(line).is_err(), "{line}");
+    }
+}
@@ -0,0 +1,120 @@
+use naval_battle_game_interface::{
+    logic::{ability::Ability, cell::Side},
+    ui::components::{board::GameState, save_game::SavedGame},
+};
+
+#[test]
+fn abilities_keep_their_place_between_the_shots() {
+    let text = "settings Classic Classic Advanced Normal Open\n\
+                player\n\
+                enemy\n\
+                shot P 5 0\n\
+                ability B sonar\n\
+                shot B 1 1\n\
+                ability P torpedo\n\
+                turn B\n";
+    let saved = SavedGame::parse(text).unwrap();
+
+    let abilities: Vec<(usize, Side, Ability)> = saved
+        .abilities
+        .iter()
+        .map(|record| (record.after, record.shooter, record.ability))
+        .collect();
+    assert_eq!(abilities, vec![(1, Side::Enemy, Ability::Sonar), (2, Side::Player, Ability::Torpedo)]);
+    assert_eq!(saved.to_text(), text);
+}
+
+#[test]
+fn moves_are_saved_in_order_with_the_shots() {
+    let text = "settings Classic Classic Moving Normal Open\n\
+                player\n\
+                enemy\n\
+                move P 0 0 up\n\
+                shot P 5 0\n\
+                move B 6 2 left\n\
+                turn P\n";
+    let saved = SavedGame::parse(text).unwrap();
+
+    let moves: Vec<(usize, Side, (usize, usize), (isize, isize))> = saved
+        .moves
+        .iter()
+        .map(|record| (record.after, record.side, (record.row, record.column), record.step))
+        .collect();
+    assert_eq!(moves, vec![(0, Side::Player, (0, 0), (1, 0)), (1, Side::Enemy, (6, 2), (0, -1))]);
+    assert_eq!(saved.to_text(), text);
+}
+
+#[test]
+fn a_mine_hit_still_costs_the_turn_after_loading() {
+    // (6,3) é a mina da linha 1 do campo minado, na metade do bot
+    let text = "settings Classic Classic Classic Normal Minefield\n\
+                player\n\
+                enemy\n\
+                shot P 6 3\n\
+                penalty P\n\
+                turn B\n";
+    let saved = SavedGame::parse(text).unwrap();
+    assert_eq!(saved.penalty, Some(Side::Player));
+    assert_eq!(saved.to_text(), text);
+
+    let mut game_state = GameState::new();
+    saved.restore_turn(&mut game_state);
+    assert!(!game_state.is_player_turn);
+
+    // o bot joga e, com o jogador pagando pela mina, joga de novo
+    game_state.pass_turn();
+    assert!(!game_state.is_player_turn);
+    game_state.pass_turn();
+    assert!(game_state.is_player_turn);
+}
+
+#[test]
+fn the_replay_stops_at_the_chosen_shot() {
+    let text = "settings Classic Classic Moving Normal Open\n\
+                player\n\
+                enemy\n\
+                shot P 5 0\n\
+                move B 6 2 left\n\
+                shot P 5 1\n\
+                shot B 1 1\n\
+                moved B\n\
+                turn P\n";
+    let saved = SavedGame::parse(text).unwrap();
+
+    let first = saved.until(1);
+    assert_eq!(first.shots, vec![(Side::Player, 5, 0)]);
+    assert_eq!(first.moves.len(), 1);
+    assert_eq!(first.turn, Side::Player);
+    assert_eq!(first.ship_moved, None);
+
+    assert_eq!(saved.until(0).moves.len(), 0);
+    assert_eq!(saved.until(2).turn, Side::Enemy);
+    assert_eq!(saved.until(3).to_text(), text);
+}
+
+#[test]
+fn lies_and_the_winner_are_kept_for_the_replay() {
+    let text = "settings Classic Classic Classic Normal Open\n\
+                player\n\
+                enemy\n\
+                shot P 5 0 lie\n\
+                shot B 1 1\n\
+                shot P 5 1 lie\n\
+                winner B\n\
+                turn P\n";
+    let saved = SavedGame::parse(text).unwrap();
+    assert_eq!(saved.lies, vec![0, 2]);
+    assert_eq!(saved.winner, Some(Side::Enemy));
+    assert_eq!(saved.to_text(), text);
+
+    // o passo do replay só conhece as mentiras até ali, e ainda não tem vencedor
+    let step = saved.until(2);
+    assert_eq!(step.lies, vec![0]);
+    assert_eq!(step.winner, None);
+
+    let mut game_state = GameState::new();
+    saved.restore_turn(&mut game_state);
+    assert_eq!(game_state.winner, Some(Side::Enemy));
+
+    assert!(SavedGame::parse("shot P 5 0 mentira\n").is_err());
+}
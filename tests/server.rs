@@ -0,0 +1,281 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use naval_battle_game_interface::{
+    api::{
+        client::ServerConnection,
+        protocol::{parse_placement, ClientMessage, ServerMessage, SpectatorView},
+        server::{GameServer, ServerConfig},
+    },
+    logic::{
+        ability::Ability,
+        engine::{Rules, ShipPlacement},
+        shot::ShotOutcome,
+    },
+};
+
+const WAIT: Duration = Duration::from_secs(5);
+
+fn server() -> String {
+    server_with("board 3 4\nship Destroyer 2\nship Submarine 1")
+}
+
+fn server_with(rules: &str) -> String {
+    let config = ServerConfig {
+        rules: Rules::parse(rules).unwrap(),
+        ..ServerConfig::default()
+    };
+
+    GameServer::bind("127.0.0.1:0", config)
+        .unwrap()
+        .spawn()
+        .unwrap()
+        .to_string()
+}
+
+fn fleet() -> Vec<ShipPlacement> {
+    ["Destroyer:0,0,2,H", "Submarine:2,3,1,H"]
+        .iter()
+        .map(|ship| parse_placement(ship).unwrap())
+        .collect()
+}
+
+// descarta mensagens até chegar uma que `wanted` aceite
+fn expect<T>(connection: &ServerConnection, mut wanted: impl FnMut(ServerMessage) -> Option<T>) -> T {
+    loop {
+        let message = connection.recv_timeout(WAIT).expect("servidor não respondeu");
+        if let Some(found) = wanted(message) {
+            return found;
+        }
+    }
+}
+
+fn join(addr: &str, name: &str) -> (ServerConnection, usize, String) {
+    let mut connection = ServerConnection::connect(addr).unwrap();
+    connection
+        .send(&ClientMessage::Join {
+            room: "sala".to_string(),
+            name: name.to_string(),
+        })
+        .unwrap();
+
+    let (seat, token) = expect(&connection, |message| match message {
+        ServerMessage::Welcome { seat, token } => Some((seat, token)),
+        _ => None,
+    });
+    (connection, seat, token)
+}
+
+fn fire(connection: &mut ServerConnection, row: usize, column: usize) -> ShotOutcome {
+    connection.send(&ClientMessage::Fire { row, column }).unwrap();
+
+    expect(connection, |message| match message {
+        ServerMessage::Shot { row: r, column: c, outcome, .. } if (r, c) == (row, column) => Some(outcome),
+        ServerMessage::Error(error) => panic!("tiro recusado: {error}"),
+        _ => None,
+    })
+}
+
+#[test]
+fn two_clients_play_a_game_on_loopback() {
+    let addr = server();
+    let (mut ana, ana_seat, _) = join(&addr, "ana");
+    let (mut bia, bia_seat, _) = join(&addr, "bia");
+    assert_eq!((ana_seat, bia_seat), (0, 1));
+
+    for connection in [&mut ana, &mut bia] {
+        connection.send(&ClientMessage::Fleet(fleet())).unwrap();
+        expect(connection, |message| matches!(message, ServerMessage::FleetAccepted).then_some(()));
+    }
+    expect(&ana, |message| matches!(message, ServerMessage::Start { first: 0 }).then_some(()));
+
+    // fora da vez o servidor recusa
+    bia.send(&ClientMessage::Fire { row: 0, column: 0 }).unwrap();
+    let error = expect(&bia, |message| match message {
+        ServerMessage::Error(error) => Some(error),
+        _ => None,
+    });
+    assert_eq!(error, "não é a sua vez");
+
+    assert_eq!(fire(&mut ana, 0, 0), ShotOutcome::Hit);
+    assert_eq!(fire(&mut bia, 1, 1), ShotOutcome::Miss);
+    assert_eq!(fire(&mut ana, 0, 1), ShotOutcome::Sunk);
+    assert_eq!(fire(&mut bia, 1, 2), ShotOutcome::Miss);
+    assert_eq!(fire(&mut ana, 2, 3), ShotOutcome::Sunk);
+
+    for connection in [&ana, &bia] {
+        let winner = expect(connection, |message| match message {
+            ServerMessage::GameOver { winner } => Some(winner),
+            _ => None,
+        });
+        assert_eq!(winner, 0);
+    }
+}
+
+#[test]
+fn old_connection_closing_does_not_drop_the_reconnected_player() {
+    let addr = server();
+
+    // conexão crua, para poder fechá-la de verdade no meio do teste
+    let mut ana = TcpStream::connect(&addr).unwrap();
+    writeln!(ana, "JOIN sala ana").unwrap();
+    let mut welcome = String::new();
+    BufReader::new(&ana).read_line(&mut welcome).unwrap();
+    let Ok(ServerMessage::Welcome { token, .. }) = ServerMessage::parse(welcome.trim()) else {
+        panic!("resposta inesperada: {welcome}");
+    };
+
+    let mut again = ServerConnection::connect(&addr).unwrap();
+    again.send(&ClientMessage::Reconnect { token }).unwrap();
+    expect(&again, |message| matches!(message, ServerMessage::Welcome { .. }).then_some(()));
+
+    // a conexão antiga cai depois da reconexão
+    drop(ana);
+    std::thread::sleep(Duration::from_millis(200));
+
+    let (mut bia, _, _) = join(&addr, "bia");
+    again.send(&ClientMessage::Fleet(fleet())).unwrap();
+    bia.send(&ClientMessage::Fleet(fleet())).unwrap();
+
+    expect(&again, |message| matches!(message, ServerMessage::Start { .. }).then_some(()));
+}
+
+#[test]
+fn spectators_only_see_fleets_after_the_game_ends() {
+    let addr = server();
+    let (mut ana, _, _) = join(&addr, "ana");
+    let (mut bia, _, _) = join(&addr, "bia");
+
+    // qualquer um pode assistir, inclusive quem joga numa segunda conexão
+    let mut spy = ServerConnection::connect(&addr).unwrap();
+    spy.send(&ClientMessage::Spectate {
+        room: "sala".to_string(),
+        view: SpectatorView::Full,
+    })
+    .unwrap();
+
+    for connection in [&mut ana, &mut bia] {
+        connection.send(&ClientMessage::Fleet(fleet())).unwrap();
+    }
+    expect(&spy, |message| match message {
+        ServerMessage::FleetOf { .. } => panic!("frota mostrada durante a partida"),
+        ServerMessage::Start { .. } => Some(()),
+        _ => None,
+    });
+
+    // nem trocando de visão
+    spy.send(&ClientMessage::View(SpectatorView::Seat(1))).unwrap();
+    expect(&spy, |message| match message {
+        ServerMessage::FleetOf { .. } => panic!("frota mostrada durante a partida"),
+        ServerMessage::Turn { .. } => Some(()),
+        _ => None,
+    });
+
+    ana.send(&ClientMessage::Leave).unwrap();
+    expect(&spy, |message| matches!(message, ServerMessage::GameOver { winner: 1 }).then_some(()));
+
+    let mut revealed = [false, false];
+    while revealed != [true, true] {
+        let seat = expect(&spy, |message| match message {
+            ServerMessage::FleetOf { seat, .. } => Some(seat),
+            _ => None,
+        });
+        revealed[seat] = true;
+    }
+}
+
+#[test]
+fn abilities_are_played_over_the_network() {
+    let addr = server_with("board 3 4\nship Destroyer 2 airstrike\nship Submarine 1 sonar");
+    let (mut ana, _, _) = join(&addr, "ana");
+    let (mut bia, _, _) = join(&addr, "bia");
+
+    for connection in [&mut ana, &mut bia] {
+        connection.send(&ClientMessage::Fleet(fleet())).unwrap();
+        expect(connection, |message| matches!(message, ServerMessage::FleetAccepted).then_some(()));
+    }
+
+    // o ataque aéreo cai em (0,0), (0,1) e (0,2) e afunda o contratorpedeiro
+    ana.send(&ClientMessage::Ability {
+        ability: Ability::Airstrike,
+        row: 0,
+        column: 1,
+    })
+    .unwrap();
+    expect(&bia, |message| {
+        matches!(message, ServerMessage::Ability { seat: 0, ability: Ability::Airstrike, .. }).then_some(())
+    });
+    let mut strikes = Vec::new();
+    while strikes.len() < 3 {
+        strikes.push(expect(&bia, |message| match message {
+            ServerMessage::Shot { seat: 0, row, column, outcome } => Some((row, column, outcome)),
+            _ => None,
+        }));
+    }
+    assert_eq!(
+        strikes,
+        [(0, 0, ShotOutcome::Hit), (0, 1, ShotOutcome::Sunk), (0, 2, ShotOutcome::Miss)]
+    );
+    for connection in [&ana, &bia] {
+        expect(connection, |message| matches!(message, ServerMessage::Turn { seat: 1 }).then_some(()));
+    }
+
+    // o sonar responde só com a presença de navio, sem atirar
+    bia.send(&ClientMessage::Ability {
+        ability: Ability::Sonar,
+        row: 1,
+        column: 2,
+    })
+    .unwrap();
+    let found = expect(&ana, |message| match message {
+        ServerMessage::Sonar { seat: 1, found, .. } => Some(found),
+        ServerMessage::Shot { .. } => panic!("sonar não atira"),
+        _ => None,
+    });
+    assert!(found);
+
+    // cada navio usa a habilidade uma vez só
+    ana.send(&ClientMessage::Ability {
+        ability: Ability::Airstrike,
+        row: 2,
+        column: 2,
+    })
+    .unwrap();
+    let error = expect(&ana, |message| match message {
+        ServerMessage::Error(error) => Some(error),
+        _ => None,
+    });
+    assert_eq!(error, "habilidade indisponível");
+}
+
+#[test]
+fn ships_move_over_the_network() {
+    let addr = server_with("board 3 4\nship Destroyer 2\nship Submarine 1\nmoving");
+    let (mut ana, _, _) = join(&addr, "ana");
+    let (mut bia, _, _) = join(&addr, "bia");
+
+    for connection in [&mut ana, &mut bia] {
+        connection.send(&ClientMessage::Fleet(fleet())).unwrap();
+        expect(connection, |message| matches!(message, ServerMessage::FleetAccepted).then_some(()));
+    }
+
+    // o contratorpedeiro de ana sai de (0,0)-(0,1) para (0,1)-(0,2)
+    ana.send(&ClientMessage::Move { ship: 0, forward: true }).unwrap();
+    expect(&bia, |message| matches!(message, ServerMessage::Moved { seat: 0 }).then_some(()));
+    expect(&bia, |message| matches!(message, ServerMessage::Turn { seat: 1 }).then_some(()));
+
+    assert_eq!(fire(&mut bia, 0, 0), ShotOutcome::Miss);
+    assert_eq!(fire(&mut ana, 1, 0), ShotOutcome::Miss);
+    assert_eq!(fire(&mut bia, 0, 2), ShotOutcome::Hit);
+
+    // o submarino já está na última coluna
+    ana.send(&ClientMessage::Move { ship: 1, forward: true }).unwrap();
+    let error = expect(&ana, |message| match message {
+        ServerMessage::Error(error) => Some(error),
+        _ => None,
+    });
+    assert_eq!(error, "navio não pode andar para lá");
+}
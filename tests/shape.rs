@@ -0,0 +1,53 @@
+use naval_battle_game_interface::logic::shape::Shape;
+
+fn l_shape() -> Shape {
+    // 2 . .
+    // 1 .
+    // 0 . .
+    Shape::parse("0,0;1,0;2,0;2,1").unwrap()
+}
+
+#[test]
+fn cells_are_normalized_to_the_corner() {
+    let shape = Shape::new(&[(5, 4), (3, 3), (4, 3), (5, 3)]).unwrap();
+
+    assert_eq!(shape, l_shape());
+    assert_eq!(shape.cells(), &[(0, 0), (1, 0), (2, 0), (2, 1)]);
+    assert_eq!(shape.size(), (3, 2));
+    assert_eq!(Shape::new(&[(0, 0), (0, 0)]), Err("formato com células repetidas".to_string()));
+    assert_eq!(Shape::new(&[(0, 0), (0, 2)]), Err("formato com células soltas".to_string()));
+    assert!(Shape::new(&[]).is_err());
+}
+
+#[test]
+fn rotation_turns_clockwise_and_comes_back_after_four() {
+    let rotated = l_shape().rotated();
+
+    assert_eq!(rotated, Shape::parse("0,0;0,1;0,2;1,0").unwrap());
+    assert_eq!(rotated.size(), (2, 3));
+    assert_eq!(Shape::line(3).rotated(), Shape::parse("0,0;1,0;2,0").unwrap());
+    assert_eq!(l_shape().oriented(4, false), l_shape());
+    assert_eq!(l_shape().oriented(1, false), rotated);
+}
+
+#[test]
+fn mirror_flips_left_and_right() {
+    let mirrored = l_shape().mirrored();
+
+    assert_eq!(mirrored, Shape::parse("0,1;1,1;2,0;2,1").unwrap());
+    assert_eq!(mirrored.mirrored(), l_shape());
+    assert_eq!(Shape::line(4).mirrored(), Shape::line(4));
+}
+
+#[test]
+fn orientations_are_distinct() {
+    assert_eq!(Shape::line(1).orientations().len(), 1);
+    assert_eq!(Shape::line(3).orientations().len(), 2);
+    assert_eq!(Shape::parse("0,0;0,1;1,0;1,1").unwrap().orientations().len(), 1);
+    assert_eq!(Shape::parse("0,0;0,1;0,2;1,1").unwrap().orientations().len(), 4);
+
+    let orientations = l_shape().orientations();
+    assert_eq!(orientations.len(), 8);
+    assert!(orientations.iter().all(|shape| shape.len() == 4 && shape.matches(&l_shape())));
+    assert!(!Shape::line(4).matches(&l_shape()));
+}
@@ -0,0 +1,29 @@
+use naval_battle_game_interface::{
+    logic::{ability::Ability, engine::ShipRule, shape::Shape},
+    ui::components::ships::{ShipCatalog, ShipSprite, ShipType},
+};
+
+#[test]
+fn ship_from_the_rules_file_keeps_its_name_and_shape() {
+    let rule = ShipRule::shaped("Cruzador", Shape::parse("0,0;0,1;1,1").unwrap());
+    let catalog = ShipCatalog::new(vec![rule.clone(), rule]);
+    let ship_type = catalog.from_name("Cruzador").unwrap();
+    let spec = catalog.spec(&ship_type);
+
+    assert_eq!(ship_type, ShipType::Custom("Cruzador".to_string()));
+    assert_eq!(spec.name, "Cruzador");
+    assert_eq!(spec.key, "Cruzador");
+    assert_eq!(spec.length, 3);
+    assert_eq!(spec.sprite, ShipSprite::Single);
+    assert_eq!(catalog.longest_ship(), 5);
+    assert_eq!(ShipType::named("Cruzador"), ship_type);
+}
+
+#[test]
+fn known_names_keep_their_builtin_type() {
+    let catalog = ShipCatalog::new(vec![ShipRule::line("Destroyer", 2)]);
+
+    assert_eq!(catalog.from_name("Destroyer"), Some(ShipType::Destroyer));
+    assert_eq!(catalog.from_name("Cruzador"), None);
+    assert_eq!(catalog.spec(&ShipType::Submarine).abilities.as_ref(), &[Ability::Sonar]);
+}